@@ -9,16 +9,31 @@ extern "C" {
     pub fn abort(a: i32, b: i32, c: i32, d: i32);
     pub fn __load_storage(k: i32, v: i32) -> i32;
     pub fn __request_storage(k: i32) -> i32;
+    pub fn __request_external_storage(id: i32, k: i32) -> i32;
+    pub fn __load_external_storage(id: i32, k: i32, v: i32) -> i32;
+    pub fn __request_storage_keys(prefix: i32, offset: i32, limit: i32) -> i32;
+    pub fn __load_storage_keys(prefix: i32, offset: i32, limit: i32, output: i32) -> i32;
     pub fn __log(v: i32);
+    pub fn __emit(topic: i32, data: i32);
     pub fn __balance(who: i32, what: i32, output: i32);
     pub fn __request_context() -> i32;
     pub fn __load_context(output: i32) -> i32;
     pub fn __sequence(output: i32);
     pub fn __fuel(output: i32);
     pub fn __height(output: i32);
+    pub fn __header(output: i32);
+    pub fn __sha256(data: i32, output: i32);
+    pub fn __hash160(data: i32, output: i32);
+    pub fn __keccak256(data: i32, output: i32);
+    pub fn __codehash(id: i32, output: i32) -> i32;
+    pub fn __is_deployed(id: i32) -> i32;
+    pub fn __verify_schnorr(pubkey: i32, message: i32, signature: i32) -> i32;
+    pub fn __verify_ecdsa(pubkey: i32, message: i32, signature: i32) -> i32;
     pub fn __returndatacopy(output: i32);
     pub fn __request_transaction() -> i32;
     pub fn __load_transaction(output: i32);
+    pub fn __request_prevouts() -> i32;
+    pub fn __load_prevouts(output: i32) -> i32;
     pub fn __request_block() -> i32;
     pub fn __load_block(output: i32);
     pub fn __call(cellpack: i32, incoming_alkanes: i32, checkpoint: i32, start_fuel: u64) -> i32;
@@ -34,6 +49,24 @@ extern "C" {
         checkpoint: i32,
         start_fuel: u64,
     ) -> i32;
+    pub fn __try_call(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32;
+    pub fn __try_staticcall(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32;
+    pub fn __try_delegatecall(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32;
 }
 
 #[cfg(feature = "test-utils")]
@@ -70,9 +103,22 @@ mod exports {
     pub fn __request_storage(k: i32) -> i32 {
         0
     }
+    pub fn __request_external_storage(id: i32, k: i32) -> i32 {
+        0
+    }
+    pub fn __load_external_storage(id: i32, k: i32, v: i32) -> i32 {
+        v
+    }
+    pub fn __request_storage_keys(prefix: i32, offset: i32, limit: i32) -> i32 {
+        0
+    }
+    pub fn __load_storage_keys(prefix: i32, offset: i32, limit: i32, output: i32) -> i32 {
+        output
+    }
     pub fn __log(ptr: i32) -> () {
         externs::write(format!("{}", String::from_utf8(ptr_to_vec(ptr)).unwrap()).as_str());
     }
+    pub fn __emit(topic: i32, data: i32) {}
     pub fn __balance(who: i32, what: i32, output: i32) -> i32 {
         0
     }
@@ -101,11 +147,33 @@ mod exports {
     pub fn __sequence(output: i32) {}
     pub fn __fuel(output: i32) {}
     pub fn __height(output: i32) {}
+    pub fn __header(output: i32) {}
+    pub fn __sha256(data: i32, output: i32) {}
+    pub fn __hash160(data: i32, output: i32) {}
+    pub fn __keccak256(data: i32, output: i32) {}
+    pub fn __codehash(id: i32, output: i32) -> i32 {
+        0
+    }
+    pub fn __is_deployed(id: i32) -> i32 {
+        0
+    }
+    pub fn __verify_schnorr(pubkey: i32, message: i32, signature: i32) -> i32 {
+        0
+    }
+    pub fn __verify_ecdsa(pubkey: i32, message: i32, signature: i32) -> i32 {
+        0
+    }
     pub fn __returndatacopy(output: i32) {}
     pub fn __request_transaction() -> i32 {
         0
     }
     pub fn __load_transaction(output: i32) {}
+    pub fn __request_prevouts() -> i32 {
+        0
+    }
+    pub fn __load_prevouts(output: i32) -> i32 {
+        output
+    }
     pub fn __request_block() -> i32 {
         0
     }
@@ -129,6 +197,30 @@ mod exports {
     ) -> i32 {
         0
     }
+    pub fn __try_call(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32 {
+        0
+    }
+    pub fn __try_staticcall(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32 {
+        0
+    }
+    pub fn __try_delegatecall(
+        cellpack: i32,
+        incoming_alkanes: i32,
+        checkpoint: i32,
+        start_fuel: u64,
+    ) -> i32 {
+        0
+    }
 }
 
 #[cfg(feature = "test-utils")]
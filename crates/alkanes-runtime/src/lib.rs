@@ -11,13 +11,30 @@ pub use crate::stdio::stdout;
 
 #[macro_export]
 macro_rules! declare_alkane {
+    // `#[nonreentrant]` makes the VM reject any call back into this alkane while one of its
+    // frames is still on the call stack
+    (#[nonreentrant] impl AlkaneResponder for $struct_name:ident {
+        type Message = $message_type:ident;
+    }) => {
+        $crate::declare_alkane! {
+            impl AlkaneResponder for $struct_name {
+                type Message = $message_type;
+            }
+        }
+
+        #[no_mangle]
+        pub extern "C" fn __nonreentrant() {}
+    };
     (impl AlkaneResponder for $struct_name:ident {
         type Message = $message_type:ident;
     }) => {
         #[no_mangle]
         pub extern "C" fn __execute() -> i32 {
             use alkanes_runtime::runtime::AlkaneResponder;
-            use alkanes_runtime::runtime::{handle_error, handle_success, prepare_response};
+            use alkanes_runtime::runtime::{
+                handle_error, handle_revert, handle_success, prepare_response,
+            };
+            use alkanes_support::revert::{Revert, REVERT_CODE_CONTRACT};
             use metashrew_support::compat::{to_arraybuffer_layout, to_passback_ptr};
 
             let mut context = $struct_name::default().context().unwrap();
@@ -39,8 +56,12 @@ macro_rules! declare_alkane {
             let extended = match result {
                 Ok(res) => handle_success(res),
                 Err(err) => {
+                    // a typed error keeps its code, including one passed up from a failed call
+                    let code = err
+                        .downcast_ref::<Revert>()
+                        .map_or(REVERT_CODE_CONTRACT, |revert| revert.code);
                     let error_msg = format!("Error: {}", err);
-                    let extended = handle_error(&error_msg);
+                    let extended = handle_revert(code, &error_msg);
                     return alkanes_runtime::runtime::response_to_i32(extended);
                 }
             };
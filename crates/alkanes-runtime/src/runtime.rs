@@ -1,9 +1,12 @@
 #[allow(unused_imports)]
 use crate::imports::{
-    __balance, __call, __delegatecall, __fuel, __height, __load_block, __load_context,
-    __load_storage, __load_transaction, __log, __request_block, __request_context,
-    __request_storage, __request_transaction, __returndatacopy, __sequence, __staticcall,
-    abort, /*, __load_output, __request_output */
+    __balance, __call, __codehash, __delegatecall, __emit, __fuel, __hash160, __header, __height,
+    __is_deployed, __keccak256, __load_block, __load_context, __load_external_storage,
+    __load_prevouts, __load_storage, __load_storage_keys, __load_transaction, __log,
+    __request_block, __request_context, __request_external_storage, __request_prevouts,
+    __request_storage, __request_storage_keys, __request_transaction, __returndatacopy, __sequence,
+    __sha256, __staticcall, __try_call, __try_delegatecall, __try_staticcall, __verify_ecdsa,
+    __verify_schnorr, abort, /*, __load_output, __request_output */
 };
 use crate::storage::StoragePointer;
 #[allow(unused_imports)]
@@ -12,6 +15,9 @@ use crate::{
     stdio::{stdout, Write},
 };
 use anyhow::{anyhow, Result};
+use bitcoin::block::Header;
+use bitcoin::consensus::deserialize;
+use bitcoin::BlockHash;
 #[allow(unused_imports)]
 use metashrew_support::compat::{to_arraybuffer_layout, to_passback_ptr, to_ptr};
 use metashrew_support::index_pointer::KeyValuePointer;
@@ -26,8 +32,10 @@ use alkanes_support::{
     context::Context,
     id::AlkaneId,
     parcel::{AlkaneTransfer, AlkaneTransferParcel},
+    prevout::Prevouts,
     response::{CallResponse, ExtendedCallResponse},
-    storage::StorageMap,
+    revert::{Revert, REVERT_CODE_CONTRACT, REVERT_CODE_VM},
+    storage::{StorageKeyPage, StorageMap},
 };
 #[cfg(feature = "panic-hook")]
 use std::panic;
@@ -65,14 +73,23 @@ pub fn handle_success(response: CallResponse) -> ExtendedCallResponse {
 }
 
 pub fn handle_error(error: &str) -> ExtendedCallResponse {
+    handle_revert(REVERT_CODE_CONTRACT, error)
+}
+
+/// Reverts with a typed error. The contract id and call depth are left empty since the VM
+/// fills them in when the frame unwinds.
+pub fn handle_revert(code: u32, error: &str) -> ExtendedCallResponse {
     let mut response = CallResponse::default();
-    let mut data: Vec<u8> = vec![0x08, 0xc3, 0x79, 0xa0];
-    data.extend(error.as_bytes());
-    response.data = data;
+    response.data = Revert::new(code, error, AlkaneId::default(), 0).encode();
     _abort();
     response.into()
 }
 
+/// The typed revert of a failed call, for a caller that would rather recover than abort.
+pub fn callee_revert(error: &anyhow::Error) -> Option<&Revert> {
+    error.downcast_ref::<Revert>()
+}
+
 pub fn prepare_response(response: ExtendedCallResponse) -> Vec<u8> {
     response.serialize()
 }
@@ -83,57 +100,109 @@ pub fn response_to_i32(response: ExtendedCallResponse) -> i32 {
     Box::leak(Box::new(response_bytes)).as_mut_ptr() as usize as i32 + 4
 }
 
+fn send_extcall(
+    __call: fn(i32, i32, i32, u64) -> i32,
+    cellpack: &Cellpack,
+    outgoing_alkanes: &AlkaneTransferParcel,
+    fuel: u64,
+) -> i32 {
+    let mut cellpack_buffer = to_arraybuffer_layout::<&[u8]>(&cellpack.serialize());
+    let mut outgoing_alkanes_buffer: Vec<u8> =
+        to_arraybuffer_layout::<&[u8]>(&outgoing_alkanes.serialize());
+    let mut storage_map_buffer = to_arraybuffer_layout::<&[u8]>(&get_cache().serialize());
+    __call(
+        to_passback_ptr(&mut cellpack_buffer),
+        to_passback_ptr(&mut outgoing_alkanes_buffer),
+        to_passback_ptr(&mut storage_map_buffer),
+        fuel,
+    )
+}
+
+fn load_returndata(call_result: i32) -> Result<CallResponse> {
+    let mut returndata = to_arraybuffer_layout(&vec![0; call_result.unsigned_abs() as usize]);
+    unsafe {
+        __returndatacopy(to_passback_ptr(&mut returndata));
+    }
+    if returndata.len() < 20 {
+        return Err(anyhow!(format!(
+            "Extcall {}, and returndatacopy len ({}) < AlkanesTransferParcel min size 20 ",
+            if call_result < 0 {
+                "failed"
+            } else {
+                "succeeded"
+            },
+            returndata.len()
+        )));
+    }
+    CallResponse::parse(&mut Cursor::new((&returndata[4..]).to_vec()))
+}
+
+/// What a `try_*` call came back with. A reverted call has had all of its state changes
+/// undone, so the alkanes that were sent with it are back with the caller.
+#[derive(Clone, Debug)]
+pub enum CallOutcome {
+    Success(CallResponse),
+    Revert {
+        revert: Revert,
+        refunded: AlkaneTransferParcel,
+    },
+}
+
+impl CallOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, CallOutcome::Success(_))
+    }
+    /// Turns a revert back into an error, for a caller that ends up not handling it.
+    pub fn into_result(self) -> Result<CallResponse> {
+        match self {
+            CallOutcome::Success(response) => Ok(response),
+            CallOutcome::Revert { revert, .. } => {
+                let error_message = format!("Extcall failed: {}", revert.message);
+                Err(anyhow::Error::new(revert).context(error_message))
+            }
+        }
+    }
+}
+
 pub trait Extcall {
     fn __call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32;
-    #[allow(static_mut_refs)]
+    fn __try_call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32;
     fn call(
         cellpack: &Cellpack,
         outgoing_alkanes: &AlkaneTransferParcel,
         fuel: u64,
     ) -> Result<CallResponse> {
-        let mut cellpack_buffer = to_arraybuffer_layout::<&[u8]>(&cellpack.serialize());
-        let mut outgoing_alkanes_buffer: Vec<u8> =
-            to_arraybuffer_layout::<&[u8]>(&outgoing_alkanes.serialize());
-        let mut storage_map_buffer = to_arraybuffer_layout::<&[u8]>(&get_cache().serialize());
-        let _call_result = Self::__call(
-            to_passback_ptr(&mut cellpack_buffer),
-            to_passback_ptr(&mut outgoing_alkanes_buffer),
-            to_passback_ptr(&mut storage_map_buffer),
-            fuel,
-        );
-        if _call_result < 0 {
-            let call_result = _call_result.abs() as usize;
-            let mut returndata = to_arraybuffer_layout(&vec![0; call_result]);
-            unsafe {
-                __returndatacopy(to_passback_ptr(&mut returndata));
-            }
-            if returndata.len() < 20 {
-                return Err(anyhow!(format!(
-                    "Extcall failed, and returndatacopy len ({}) < AlkanesTransferParcel min size 20 ",
-                    returndata.len()
-                )));
-            }
-            let response = CallResponse::parse(&mut Cursor::new((&returndata[4..]).to_vec()))?;
-            if response.data.len() <= 4 || &response.data[0..4] != &[0x08, 0xc3, 0x79, 0xa0] {
-                return Err(anyhow!("Extcall failed (no details available)"));
-            }
-            let error_message = String::from_utf8_lossy(&response.data[4..]).to_string();
-            return Err(anyhow!("Extcall failed: {}", error_message));
-        } else {
-            let call_result = _call_result as usize;
-            let mut returndata = to_arraybuffer_layout(&vec![0; call_result]);
-            unsafe {
-                __returndatacopy(to_passback_ptr(&mut returndata));
-            }
-            if returndata.len() < 20 {
-                return Err(anyhow!(format!(
-                    "Extcall succeeded, but returndatacopy len ({}) < AlkanesTransferParcel min size 20 ",
-                    returndata.len()
-                )));
-            }
-            let response = CallResponse::parse(&mut Cursor::new((&returndata[4..]).to_vec()))?;
-            Ok(response)
+        let call_result = send_extcall(Self::__call, cellpack, outgoing_alkanes, fuel);
+        let response = load_returndata(call_result)?;
+        if call_result < 0 {
+            return Err(match Revert::decode(&response.data) {
+                Some(revert) => {
+                    let error_message = format!("Extcall failed: {}", revert.message);
+                    anyhow::Error::new(revert).context(error_message)
+                }
+                None => anyhow!("Extcall failed (no details available)"),
+            });
         }
+        Ok(response)
+    }
+    /// Like `call`, but a revert in the callee is returned rather than aborting the caller.
+    fn try_call(
+        cellpack: &Cellpack,
+        outgoing_alkanes: &AlkaneTransferParcel,
+        fuel: u64,
+    ) -> Result<CallOutcome> {
+        let call_result = send_extcall(Self::__try_call, cellpack, outgoing_alkanes, fuel);
+        let response = load_returndata(call_result)?;
+        if call_result < 0 {
+            let revert = Revert::decode(&response.data).unwrap_or_else(|| {
+                Revert::new(REVERT_CODE_VM, "no details available", cellpack.target, 0)
+            });
+            return Ok(CallOutcome::Revert {
+                revert,
+                refunded: outgoing_alkanes.clone(),
+            });
+        }
+        Ok(CallOutcome::Success(response))
     }
 }
 
@@ -143,6 +212,9 @@ impl Extcall for Call {
     fn __call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
         unsafe { __call(cellpack, outgoing_alkanes, checkpoint, fuel) }
     }
+    fn __try_call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
+        unsafe { __try_call(cellpack, outgoing_alkanes, checkpoint, fuel) }
+    }
 }
 
 pub struct Delegatecall(());
@@ -151,6 +223,9 @@ impl Extcall for Delegatecall {
     fn __call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
         unsafe { __delegatecall(cellpack, outgoing_alkanes, checkpoint, fuel) }
     }
+    fn __try_call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
+        unsafe { __try_delegatecall(cellpack, outgoing_alkanes, checkpoint, fuel) }
+    }
 }
 
 pub struct Staticcall(());
@@ -159,6 +234,9 @@ impl Extcall for Staticcall {
     fn __call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
         unsafe { __staticcall(cellpack, outgoing_alkanes, checkpoint, fuel) }
     }
+    fn __try_call(cellpack: i32, outgoing_alkanes: i32, checkpoint: i32, fuel: u64) -> i32 {
+        unsafe { __try_staticcall(cellpack, outgoing_alkanes, checkpoint, fuel) }
+    }
 }
 
 pub trait AlkaneResponder: 'static {
@@ -205,6 +283,15 @@ pub trait AlkaneResponder: 'static {
             (&buffer[4..]).to_vec()
         }
     }
+    /// Returns the output spent by each input of the current transaction, in input order.
+    /// Entries are `None` for coinbase inputs and outputs the indexer has not seen.
+    fn prevouts(&self) -> Result<Prevouts> {
+        unsafe {
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; __request_prevouts() as usize]);
+            __load_prevouts(to_ptr(&mut buffer) + 4);
+            Prevouts::parse(&mut Cursor::new((&buffer[4..]).to_vec()))
+        }
+    }
     /*
     fn output(&self, v: &OutPoint) -> Result<Vec<u8>> {
         let mut buffer = to_arraybuffer_layout(consensus_encode(v)?);
@@ -244,6 +331,20 @@ pub trait AlkaneResponder: 'static {
             }
         }
     }
+    /// Reads `k` from another alkane's storage without calling into it. Only state
+    /// committed by completed calls is visible.
+    fn load_external(&self, id: &AlkaneId, k: Vec<u8>) -> Vec<u8> {
+        unsafe {
+            let mut id_bytes = to_arraybuffer_layout::<Vec<u8>>(id.clone().into());
+            let mut key_bytes = to_arraybuffer_layout(&k);
+            let id_ptr = to_passback_ptr(&mut id_bytes);
+            let key_ptr = to_passback_ptr(&mut key_bytes);
+            let buf_size = __request_external_storage(id_ptr, key_ptr) as usize;
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; buf_size]);
+            __load_external_storage(id_ptr, key_ptr, to_passback_ptr(&mut buffer));
+            (&buffer[4..]).to_vec()
+        }
+    }
     #[allow(static_mut_refs)]
     fn store(&self, k: Vec<u8>, v: Vec<u8>) {
         unsafe {
@@ -251,6 +352,64 @@ pub trait AlkaneResponder: 'static {
             _CACHE.as_mut().unwrap().set(&k, &v);
         }
     }
+    /// Removes `k` from this alkane's storage; reads of a deleted key return an empty value.
+    #[allow(static_mut_refs)]
+    fn delete(&self, k: Vec<u8>) {
+        unsafe {
+            initialize_cache();
+            _CACHE.as_mut().unwrap().delete(&k);
+        }
+    }
+    /// Lists keys in this alkane's storage beginning with `prefix`, scanning at most `limit`
+    /// entries of the key index starting at `offset`. Keys written during this call are
+    /// reported on the last page. Keys stored before the key index existed are only listed
+    /// once they are written again.
+    #[allow(static_mut_refs)]
+    fn storage_keys(&self, prefix: &[u8], offset: u32, limit: u32) -> Result<StorageKeyPage> {
+        let mut page = unsafe {
+            let mut prefix_bytes = to_arraybuffer_layout(prefix);
+            let prefix_ptr = to_passback_ptr(&mut prefix_bytes);
+            let buf_size = __request_storage_keys(prefix_ptr, offset as i32, limit as i32);
+            if buf_size < 0 {
+                return Err(anyhow!("failed to list storage keys"));
+            }
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; buf_size as usize]);
+            __load_storage_keys(
+                prefix_ptr,
+                offset as i32,
+                limit as i32,
+                to_passback_ptr(&mut buffer),
+            );
+            StorageKeyPage::parse(&mut Cursor::new((&buffer[4..]).to_vec()))?
+        };
+        unsafe {
+            initialize_cache();
+            let cache = _CACHE.as_ref().unwrap();
+            page.keys
+                .retain(|k| cache.get(k).map(|v| !v.is_empty()).unwrap_or(true));
+            if page.next.is_none() {
+                let mut pending = cache
+                    .keys_with_prefix(prefix)
+                    .filter(|k| {
+                        let mut key_bytes = to_arraybuffer_layout(k.as_slice());
+                        __request_storage(to_passback_ptr(&mut key_bytes)) == 0
+                    })
+                    .cloned()
+                    .collect::<Vec<Vec<u8>>>();
+                pending.sort();
+                page.keys.extend(pending);
+            }
+        }
+        Ok(page)
+    }
+    /// Records a structured event in the trace, indexed by this alkane and `topic`.
+    fn emit(&self, topic: &[u8], data: &[u8]) {
+        unsafe {
+            let mut topic_bytes: Vec<u8> = to_arraybuffer_layout(topic);
+            let mut data_bytes: Vec<u8> = to_arraybuffer_layout(data);
+            __emit(to_ptr(&mut topic_bytes) + 4, to_ptr(&mut data_bytes) + 4);
+        }
+    }
     fn balance(&self, who: &AlkaneId, what: &AlkaneId) -> u128 {
         unsafe {
             let mut who_bytes: Vec<u8> = to_arraybuffer_layout::<Vec<u8>>(who.clone().into());
@@ -283,6 +442,98 @@ pub trait AlkaneResponder: 'static {
             u64::from_le_bytes((&buffer[4..]).try_into().unwrap())
         }
     }
+    /// Returns the 80-byte header of the current block followed by its median-time-past.
+    fn header_data(&self) -> Vec<u8> {
+        unsafe {
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; 84]);
+            __header(to_ptr(&mut buffer) + 4);
+            (&buffer[4..]).to_vec()
+        }
+    }
+    fn header(&self) -> Header {
+        deserialize(&self.header_data()[0..80]).unwrap()
+    }
+    fn timestamp(&self) -> u32 {
+        self.header().time
+    }
+    fn block_hash(&self) -> BlockHash {
+        self.header().block_hash()
+    }
+    fn previous_block_hash(&self) -> BlockHash {
+        self.header().prev_blockhash
+    }
+    /// Median timestamp of the eleven blocks preceding the current one (BIP113).
+    fn median_time_past(&self) -> u32 {
+        u32::from_le_bytes((&self.header_data()[80..84]).try_into().unwrap())
+    }
+    /// Returns the sha256 of the decompressed WASM deployed at `id`, or `None` if
+    /// nothing is deployed there.
+    fn codehash(&self, id: &AlkaneId) -> Option<[u8; 32]> {
+        unsafe {
+            let mut id_bytes = to_arraybuffer_layout::<Vec<u8>>(id.clone().into());
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; 32]);
+            match __codehash(to_ptr(&mut id_bytes) + 4, to_ptr(&mut buffer) + 4) {
+                1 => Some((&buffer[4..]).try_into().unwrap()),
+                _ => None,
+            }
+        }
+    }
+    fn is_deployed(&self, id: &AlkaneId) -> bool {
+        unsafe {
+            let mut id_bytes = to_arraybuffer_layout::<Vec<u8>>(id.clone().into());
+            __is_deployed(to_ptr(&mut id_bytes) + 4) == 1
+        }
+    }
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        unsafe {
+            let mut data_bytes: Vec<u8> = to_arraybuffer_layout(data);
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; 32]);
+            __sha256(to_ptr(&mut data_bytes) + 4, to_ptr(&mut buffer) + 4);
+            (&buffer[4..]).try_into().unwrap()
+        }
+    }
+    fn hash160(&self, data: &[u8]) -> [u8; 20] {
+        unsafe {
+            let mut data_bytes: Vec<u8> = to_arraybuffer_layout(data);
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; 20]);
+            __hash160(to_ptr(&mut data_bytes) + 4, to_ptr(&mut buffer) + 4);
+            (&buffer[4..]).try_into().unwrap()
+        }
+    }
+    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
+        unsafe {
+            let mut data_bytes: Vec<u8> = to_arraybuffer_layout(data);
+            let mut buffer: Vec<u8> = to_arraybuffer_layout(vec![0; 32]);
+            __keccak256(to_ptr(&mut data_bytes) + 4, to_ptr(&mut buffer) + 4);
+            (&buffer[4..]).try_into().unwrap()
+        }
+    }
+    /// Verifies a BIP340 signature over a 32-byte message digest with an x-only public key.
+    fn verify_schnorr(&self, pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        unsafe {
+            let mut pubkey_bytes: Vec<u8> = to_arraybuffer_layout(pubkey);
+            let mut message_bytes: Vec<u8> = to_arraybuffer_layout(message);
+            let mut signature_bytes: Vec<u8> = to_arraybuffer_layout(signature);
+            __verify_schnorr(
+                to_ptr(&mut pubkey_bytes) + 4,
+                to_ptr(&mut message_bytes) + 4,
+                to_ptr(&mut signature_bytes) + 4,
+            ) == 1
+        }
+    }
+    /// Verifies a low-S ECDSA signature (compact or DER) over a 32-byte message digest.
+    fn verify_ecdsa(&self, pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        unsafe {
+            let mut pubkey_bytes: Vec<u8> = to_arraybuffer_layout(pubkey);
+            let mut message_bytes: Vec<u8> = to_arraybuffer_layout(message);
+            let mut signature_bytes: Vec<u8> = to_arraybuffer_layout(signature);
+            __verify_ecdsa(
+                to_ptr(&mut pubkey_bytes) + 4,
+                to_ptr(&mut message_bytes) + 4,
+                to_ptr(&mut signature_bytes) + 4,
+            ) == 1
+        }
+    }
     fn extcall<T: Extcall>(
         &self,
         cellpack: &Cellpack,
@@ -315,4 +566,36 @@ pub trait AlkaneResponder: 'static {
     ) -> Result<CallResponse> {
         self.extcall::<Staticcall>(cellpack, outgoing_alkanes, fuel)
     }
+    fn try_extcall<T: Extcall>(
+        &self,
+        cellpack: &Cellpack,
+        outgoing_alkanes: &AlkaneTransferParcel,
+        fuel: u64,
+    ) -> Result<CallOutcome> {
+        T::try_call(cellpack, outgoing_alkanes, fuel)
+    }
+    fn try_call(
+        &self,
+        cellpack: &Cellpack,
+        outgoing_alkanes: &AlkaneTransferParcel,
+        fuel: u64,
+    ) -> Result<CallOutcome> {
+        self.try_extcall::<Call>(cellpack, outgoing_alkanes, fuel)
+    }
+    fn try_delegatecall(
+        &self,
+        cellpack: &Cellpack,
+        outgoing_alkanes: &AlkaneTransferParcel,
+        fuel: u64,
+    ) -> Result<CallOutcome> {
+        self.try_extcall::<Delegatecall>(cellpack, outgoing_alkanes, fuel)
+    }
+    fn try_staticcall(
+        &self,
+        cellpack: &Cellpack,
+        outgoing_alkanes: &AlkaneTransferParcel,
+        fuel: u64,
+    ) -> Result<CallOutcome> {
+        self.try_extcall::<Staticcall>(cellpack, outgoing_alkanes, fuel)
+    }
 }
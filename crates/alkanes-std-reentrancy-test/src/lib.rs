@@ -0,0 +1,47 @@
+use alkanes_runtime::{declare_alkane, message::MessageDispatch, runtime::AlkaneResponder};
+#[allow(unused_imports)]
+use alkanes_runtime::{
+    println,
+    stdio::{stdout, Write},
+};
+use alkanes_support::{cellpack::Cellpack, id::AlkaneId, response::CallResponse};
+use anyhow::Result;
+use metashrew_support::compat::{to_arraybuffer_layout, to_passback_ptr};
+
+/// A non-reentrant alkane that calls out to another, used to exercise the VM's lock.
+#[derive(Default)]
+pub struct ReentrancyTest(());
+
+#[derive(MessageDispatch)]
+enum ReentrancyTestMessage {
+    #[opcode(0)]
+    CallOut { target: AlkaneId, inputs: Vec<u128> },
+
+    #[opcode(1)]
+    #[returns(Vec<u8>)]
+    Ping,
+}
+
+impl ReentrancyTest {
+    fn call_out(&self, target: AlkaneId, inputs: Vec<u128>) -> Result<CallResponse> {
+        let context = self.context()?;
+        let cellpack = Cellpack { target, inputs };
+        self.call(&cellpack, &context.incoming_alkanes, self.fuel())
+    }
+
+    fn ping(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+        response.data = vec![1];
+        Ok(response)
+    }
+}
+
+impl AlkaneResponder for ReentrancyTest {}
+
+declare_alkane! {
+    #[nonreentrant]
+    impl AlkaneResponder for ReentrancyTest {
+        type Message = ReentrancyTestMessage;
+    }
+}
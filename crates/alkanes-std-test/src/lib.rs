@@ -1,4 +1,8 @@
-use alkanes_runtime::{declare_alkane, message::MessageDispatch, runtime::AlkaneResponder};
+use alkanes_runtime::{
+    declare_alkane,
+    message::MessageDispatch,
+    runtime::{AlkaneResponder, CallOutcome},
+};
 use alkanes_support::{
     cellpack::Cellpack,
     id::AlkaneId,
@@ -6,6 +10,7 @@ use alkanes_support::{
     response::CallResponse,
 };
 use anyhow::{anyhow, Result};
+use bitcoin::hashes::Hash;
 use metashrew_support::compat::{to_arraybuffer_layout, to_passback_ptr};
 use sha2::{Digest, Sha256};
 #[allow(unused_imports)]
@@ -79,6 +84,73 @@ enum LoggerAlkaneMessage {
     #[opcode(78)]
     HashLoop,
 
+    #[opcode(79)]
+    #[returns(Vec<u8>)]
+    NativeHashes { data: Vec<u128> },
+
+    #[opcode(80)]
+    #[returns(Vec<u8>)]
+    TestVerifySchnorr {
+        pubkey: Vec<u128>,
+        message: Vec<u128>,
+        signature: Vec<u128>,
+    },
+
+    #[opcode(81)]
+    #[returns(Vec<u8>)]
+    TestVerifyEcdsa {
+        pubkey: Vec<u128>,
+        message: Vec<u128>,
+        signature: Vec<u128>,
+    },
+
+    #[opcode(82)]
+    TestEmit { data: Vec<u128> },
+
+    #[opcode(83)]
+    TestEmitRevert { data: Vec<u128> },
+
+    #[opcode(84)]
+    TestStoreKeys { keys: Vec<u128> },
+
+    #[opcode(85)]
+    TestDeleteKey { key: u128 },
+
+    #[opcode(86)]
+    #[returns(Vec<u8>)]
+    TestListKeys { offset: u128, limit: u128 },
+
+    #[opcode(87)]
+    #[returns(Vec<u8>)]
+    TestPrevouts,
+
+    #[opcode(88)]
+    #[returns(Vec<u8>)]
+    TestHeader,
+
+    #[opcode(89)]
+    #[returns(Vec<u8>)]
+    TestLoadExternal { target: AlkaneId, key: u128 },
+
+    #[opcode(90)]
+    #[returns(Vec<u8>)]
+    TestCodehash { target: AlkaneId },
+
+    #[opcode(91)]
+    #[returns(Vec<u8>)]
+    TestRecoverCall { target: AlkaneId, inputs: Vec<u128> },
+
+    #[opcode(92)]
+    TestTryCallFallback {
+        target: AlkaneId,
+        inputs: Vec<u128>,
+        fallback: AlkaneId,
+        fallback_inputs: Vec<u128>,
+    },
+
+    #[opcode(93)]
+    TestNestedGrow { pages: u128, depth: u128 },
+
     #[opcode(99)]
     #[returns(Vec<u8>)]
     ReturnDefaultData,
@@ -165,6 +237,200 @@ impl LoggerAlkane {
         Ok(response)
     }
 
+    fn native_hashes(&self, data: Vec<u128>) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let bytes = to_bytes(&data);
+        response.data = self.sha256(&bytes).to_vec();
+        response.data.extend(self.hash160(&bytes));
+        response.data.extend(self.keccak256(&bytes));
+
+        Ok(response)
+    }
+
+    fn test_verify_schnorr(
+        &self,
+        pubkey: Vec<u128>,
+        message: Vec<u128>,
+        signature: Vec<u128>,
+    ) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let valid = self.verify_schnorr(
+            &to_bytes(&pubkey),
+            &to_bytes(&message),
+            &to_bytes(&signature),
+        );
+        response.data = vec![valid as u8];
+
+        Ok(response)
+    }
+
+    fn test_verify_ecdsa(
+        &self,
+        pubkey: Vec<u128>,
+        message: Vec<u128>,
+        signature: Vec<u128>,
+    ) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        // compressed public keys are 33 bytes, zero-padded to a multiple of 16
+        let mut pubkey = to_bytes(&pubkey);
+        pubkey.truncate(33);
+        let valid = self.verify_ecdsa(&pubkey, &to_bytes(&message), &to_bytes(&signature));
+        response.data = vec![valid as u8];
+
+        Ok(response)
+    }
+
+    fn test_emit(&self, data: Vec<u128>) -> Result<CallResponse> {
+        let context = self.context()?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        self.emit(b"test", &to_bytes(&data));
+
+        Ok(response)
+    }
+
+    fn test_emit_revert(&self, data: Vec<u128>) -> Result<CallResponse> {
+        self.emit(b"test", &to_bytes(&data));
+        Err(anyhow!("Revert after emit"))
+    }
+
+    fn test_store_keys(&self, keys: Vec<u128>) -> Result<CallResponse> {
+        let context = self.context()?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        for key in keys {
+            self.store(storage_key(key), vec![1]);
+        }
+        // unrelated keys must not show up in a "/k/" listing
+        self.store(b"/other".to_vec(), vec![1]);
+
+        Ok(response)
+    }
+
+    fn test_delete_key(&self, key: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        self.delete(storage_key(key));
+
+        Ok(response)
+    }
+
+    fn test_list_keys(&self, offset: u128, limit: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let page = self.storage_keys(b"/k/", offset.try_into()?, limit.try_into()?)?;
+        response.data = page.serialize();
+
+        Ok(response)
+    }
+
+    fn test_prevouts(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        response.data = self.prevouts()?.serialize();
+
+        Ok(response)
+    }
+
+    fn test_header(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        response.data.extend(&self.timestamp().to_le_bytes());
+        response.data.extend(&self.median_time_past().to_le_bytes());
+        response.data.extend(self.block_hash().as_byte_array());
+        response
+            .data
+            .extend(self.previous_block_hash().as_byte_array());
+
+        Ok(response)
+    }
+
+    fn test_load_external(&self, target: AlkaneId, key: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        response.data = self.load_external(&target, storage_key(key));
+
+        Ok(response)
+    }
+
+    fn test_codehash(&self, target: AlkaneId) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        response.data = vec![self.is_deployed(&target) as u8];
+        if let Some(hash) = self.codehash(&target) {
+            response.data.extend(&hash);
+        }
+
+        Ok(response)
+    }
+
+    fn test_recover_call(&self, target: AlkaneId, inputs: Vec<u128>) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+        let cellpack = Cellpack { target, inputs };
+
+        // hand back the callee's revert instead of failing with it
+        match self.try_call(&cellpack, &AlkaneTransferParcel::default(), self.fuel())? {
+            CallOutcome::Success(v) => response.data = v.data,
+            CallOutcome::Revert { revert, .. } => response.data = revert.encode(),
+        }
+
+        Ok(response)
+    }
+
+    fn test_try_call_fallback(
+        &self,
+        target: AlkaneId,
+        inputs: Vec<u128>,
+        fallback: AlkaneId,
+        fallback_inputs: Vec<u128>,
+    ) -> Result<CallResponse> {
+        let context = self.context()?;
+        let cellpack = Cellpack { target, inputs };
+        let outcome = self.try_call(&cellpack, &context.incoming_alkanes, self.fuel())?;
+        match outcome {
+            CallOutcome::Success(response) => Ok(response),
+            CallOutcome::Revert { refunded, .. } => {
+                let cellpack = Cellpack {
+                    target: fallback,
+                    inputs: fallback_inputs,
+                };
+                self.call(&cellpack, &refunded, self.fuel())
+            }
+        }
+    }
+
+    fn test_nested_grow(&self, pages: u128, depth: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+
+        // grow this frame's memory, then have a nested frame do the same
+        #[cfg(target_arch = "wasm32")]
+        if core::arch::wasm32::memory_grow(0, pages as usize) == usize::MAX {
+            return Err(anyhow!("memory budget exceeded"));
+        }
+        if depth > 0 {
+            let cellpack = Cellpack {
+                target: context.myself.clone(),
+                inputs: vec![93, pages, depth - 1],
+            };
+            self.call(&cellpack, &AlkaneTransferParcel::default(), self.fuel())?;
+        }
+
+        Ok(CallResponse::forward(&context.incoming_alkanes))
+    }
+
     fn test_infinite_loop(&self) -> Result<CallResponse> {
         let context = self.context()?;
         let mut response = CallResponse::forward(&context.incoming_alkanes);
@@ -324,6 +590,16 @@ impl LoggerAlkane {
     }
 }
 
+fn to_bytes(values: &Vec<u128>) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn storage_key(key: u128) -> Vec<u8> {
+    let mut k = b"/k/".to_vec();
+    k.extend(key.to_le_bytes());
+    k
+}
+
 impl AlkaneResponder for LoggerAlkane {}
 
 // Use the new macro format
@@ -74,6 +74,13 @@ impl AlkaneId {
             None
         }
     }
+    pub fn precompile(&self) -> Option<u128> {
+        if self.block == 7 {
+            Some(self.tx)
+        } else {
+            None
+        }
+    }
     pub fn factory(&self) -> Option<AlkaneId> {
         if self.block == 5 {
             Some(AlkaneId {
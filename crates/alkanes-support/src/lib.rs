@@ -5,8 +5,10 @@ pub mod envelope;
 pub mod gz;
 pub mod id;
 pub mod parcel;
+pub mod prevout;
 pub mod proto;
 pub mod response;
+pub mod revert;
 pub mod storage;
 pub mod trace;
 pub mod utils;
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use bitcoin::{Amount, ScriptBuf, TxOut};
+use metashrew_support::utils::{consume_exact, consume_sized_int};
+use std::io::Cursor;
+
+/// The outputs spent by each input of a transaction, in input order. An entry is
+/// `None` when the spent output is not known to the indexer, e.g. a coinbase input.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Prevouts(pub Vec<Option<TxOut>>);
+
+impl Prevouts {
+    pub fn parse(cursor: &mut Cursor<Vec<u8>>) -> Result<Prevouts> {
+        let len = consume_sized_int::<u32>(cursor)?;
+        let mut prevouts = Vec::<Option<TxOut>>::new();
+        for _i in 0..len {
+            if consume_exact(cursor, 1)?[0] == 0 {
+                prevouts.push(None);
+                continue;
+            }
+            let value = consume_sized_int::<u64>(cursor)?;
+            let script_length: usize = consume_sized_int::<u32>(cursor)?.try_into()?;
+            let script = consume_exact(cursor, script_length)?;
+            prevouts.push(Some(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: ScriptBuf::from_bytes(script),
+            }));
+        }
+        Ok(Prevouts(prevouts))
+    }
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::<u8>::new();
+        buffer.extend(&(self.0.len() as u32).to_le_bytes());
+        for prevout in self.0.iter() {
+            match prevout {
+                Some(output) => {
+                    buffer.push(1);
+                    buffer.extend(&output.value.to_sat().to_le_bytes());
+                    buffer.extend(&(output.script_pubkey.len() as u32).to_le_bytes());
+                    buffer.extend(output.script_pubkey.as_bytes());
+                }
+                None => buffer.push(0),
+            }
+        }
+        buffer
+    }
+}
@@ -1344,6 +1344,8 @@ pub struct TraceContext {
     pub inner: ::protobuf::MessageField<Context>,
     // @@protoc_insertion_point(field:alkanes.TraceContext.fuel)
     pub fuel: u64,
+    // @@protoc_insertion_point(field:alkanes.TraceContext.requested_fuel)
+    pub requested_fuel: u64,
     // special fields
     // @@protoc_insertion_point(special_field:alkanes.TraceContext.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -1361,7 +1363,7 @@ impl TraceContext {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut fields = ::std::vec::Vec::with_capacity(3);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Context>(
             "inner",
@@ -1373,6 +1375,11 @@ impl TraceContext {
             |m: &TraceContext| { &m.fuel },
             |m: &mut TraceContext| { &mut m.fuel },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "requested_fuel",
+            |m: &TraceContext| { &m.requested_fuel },
+            |m: &mut TraceContext| { &mut m.requested_fuel },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<TraceContext>(
             "TraceContext",
             fields,
@@ -1397,6 +1404,9 @@ impl ::protobuf::Message for TraceContext {
                 16 => {
                     self.fuel = is.read_uint64()?;
                 },
+                24 => {
+                    self.requested_fuel = is.read_uint64()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -1416,6 +1426,9 @@ impl ::protobuf::Message for TraceContext {
         if self.fuel != 0 {
             my_size += ::protobuf::rt::uint64_size(2, self.fuel);
         }
+        if self.requested_fuel != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.requested_fuel);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -1428,6 +1441,9 @@ impl ::protobuf::Message for TraceContext {
         if self.fuel != 0 {
             os.write_uint64(2, self.fuel)?;
         }
+        if self.requested_fuel != 0 {
+            os.write_uint64(3, self.requested_fuel)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1447,6 +1463,7 @@ impl ::protobuf::Message for TraceContext {
     fn clear(&mut self) {
         self.inner.clear();
         self.fuel = 0;
+        self.requested_fuel = 0;
         self.special_fields.clear();
     }
 
@@ -1454,6 +1471,7 @@ impl ::protobuf::Message for TraceContext {
         static instance: TraceContext = TraceContext {
             inner: ::protobuf::MessageField::none(),
             fuel: 0,
+            requested_fuel: 0,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -1882,6 +1900,165 @@ impl ::protobuf::reflect::ProtobufValue for AlkanesCreate {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+// @@protoc_insertion_point(message:alkanes.AlkanesEmit)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct AlkanesEmit {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.AlkanesEmit.alkane)
+    pub alkane: ::protobuf::MessageField<AlkaneId>,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEmit.topic)
+    pub topic: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEmit.data)
+    pub data: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.AlkanesEmit.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a AlkanesEmit {
+    fn default() -> &'a AlkanesEmit {
+        <AlkanesEmit as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl AlkanesEmit {
+    pub fn new() -> AlkanesEmit {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, AlkaneId>(
+            "alkane",
+            |m: &AlkanesEmit| { &m.alkane },
+            |m: &mut AlkanesEmit| { &mut m.alkane },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "topic",
+            |m: &AlkanesEmit| { &m.topic },
+            |m: &mut AlkanesEmit| { &mut m.topic },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "data",
+            |m: &AlkanesEmit| { &m.data },
+            |m: &mut AlkanesEmit| { &mut m.data },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<AlkanesEmit>(
+            "AlkanesEmit",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for AlkanesEmit {
+    const NAME: &'static str = "AlkanesEmit";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.alkane)?;
+                },
+                18 => {
+                    self.topic = is.read_bytes()?;
+                },
+                26 => {
+                    self.data = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.alkane.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if !self.topic.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.topic);
+        }
+        if !self.data.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.data);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.alkane.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if !self.topic.is_empty() {
+            os.write_bytes(2, &self.topic)?;
+        }
+        if !self.data.is_empty() {
+            os.write_bytes(3, &self.data)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> AlkanesEmit {
+        AlkanesEmit::new()
+    }
+
+    fn clear(&mut self) {
+        self.alkane.clear();
+        self.topic.clear();
+        self.data.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static AlkanesEmit {
+        static instance: AlkanesEmit = AlkanesEmit {
+            alkane: ::protobuf::MessageField::none(),
+            topic: ::std::vec::Vec::new(),
+            data: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for AlkanesEmit {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("AlkanesEmit").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for AlkanesEmit {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for AlkanesEmit {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 // @@protoc_insertion_point(message:alkanes.AlkanesTraceEvent)
 #[derive(PartialEq,Clone,Default,Debug)]
 pub struct AlkanesTraceEvent {
@@ -2050,8 +2227,57 @@ impl AlkanesTraceEvent {
         }
     }
 
+    // .alkanes.AlkanesEmit emit = 4;
+
+    pub fn emit(&self) -> &AlkanesEmit {
+        match self.event {
+            ::std::option::Option::Some(alkanes_trace_event::Event::Emit(ref v)) => v,
+            _ => <AlkanesEmit as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_emit(&mut self) {
+        self.event = ::std::option::Option::None;
+    }
+
+    pub fn has_emit(&self) -> bool {
+        match self.event {
+            ::std::option::Option::Some(alkanes_trace_event::Event::Emit(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_emit(&mut self, v: AlkanesEmit) {
+        self.event = ::std::option::Option::Some(alkanes_trace_event::Event::Emit(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_emit(&mut self) -> &mut AlkanesEmit {
+        if let ::std::option::Option::Some(alkanes_trace_event::Event::Emit(_)) = self.event {
+        } else {
+            self.event = ::std::option::Option::Some(alkanes_trace_event::Event::Emit(AlkanesEmit::new()));
+        }
+        match self.event {
+            ::std::option::Option::Some(alkanes_trace_event::Event::Emit(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_emit(&mut self) -> AlkanesEmit {
+        if self.has_emit() {
+            match self.event.take() {
+                ::std::option::Option::Some(alkanes_trace_event::Event::Emit(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            AlkanesEmit::new()
+        }
+    }
+
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut fields = ::std::vec::Vec::with_capacity(4);
         let mut oneofs = ::std::vec::Vec::with_capacity(1);
         fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, AlkanesEnterContext>(
             "enter_context",
@@ -2074,6 +2300,13 @@ impl AlkanesTraceEvent {
             AlkanesTraceEvent::mut_create_alkane,
             AlkanesTraceEvent::set_create_alkane,
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, AlkanesEmit>(
+            "emit",
+            AlkanesTraceEvent::has_emit,
+            AlkanesTraceEvent::emit,
+            AlkanesTraceEvent::mut_emit,
+            AlkanesTraceEvent::set_emit,
+        ));
         oneofs.push(alkanes_trace_event::Event::generated_oneof_descriptor_data());
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<AlkanesTraceEvent>(
             "AlkanesTraceEvent",
@@ -2102,6 +2335,9 @@ impl ::protobuf::Message for AlkanesTraceEvent {
                 26 => {
                     self.event = ::std::option::Option::Some(alkanes_trace_event::Event::CreateAlkane(is.read_message()?));
                 },
+                34 => {
+                    self.event = ::std::option::Option::Some(alkanes_trace_event::Event::Emit(is.read_message()?));
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -2128,6 +2364,10 @@ impl ::protobuf::Message for AlkanesTraceEvent {
                     let len = v.compute_size();
                     my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
                 },
+                &alkanes_trace_event::Event::Emit(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
@@ -2147,6 +2387,9 @@ impl ::protobuf::Message for AlkanesTraceEvent {
                 &alkanes_trace_event::Event::CreateAlkane(ref v) => {
                     ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
                 },
+                &alkanes_trace_event::Event::Emit(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+                },
             };
         }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
@@ -2169,6 +2412,7 @@ impl ::protobuf::Message for AlkanesTraceEvent {
         self.event = ::std::option::Option::None;
         self.event = ::std::option::Option::None;
         self.event = ::std::option::Option::None;
+        self.event = ::std::option::Option::None;
         self.special_fields.clear();
     }
 
@@ -2211,6 +2455,8 @@ pub mod alkanes_trace_event {
         ExitContext(super::AlkanesExitContext),
         // @@protoc_insertion_point(oneof_field:alkanes.AlkanesTraceEvent.create_alkane)
         CreateAlkane(super::AlkanesCreate),
+        // @@protoc_insertion_point(oneof_field:alkanes.AlkanesTraceEvent.emit)
+        Emit(super::AlkanesEmit),
     }
 
     impl ::protobuf::Oneof for Event {
@@ -2636,60 +2882,88 @@ impl ::protobuf::reflect::ProtobufValue for AlkanesTrace {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:alkanes.SimulateResponse)
+// @@protoc_insertion_point(message:alkanes.CallFrameFuel)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct SimulateResponse {
+pub struct CallFrameFuel {
     // message fields
-    // @@protoc_insertion_point(field:alkanes.SimulateResponse.execution)
-    pub execution: ::protobuf::MessageField<ExtendedCallResponse>,
-    // @@protoc_insertion_point(field:alkanes.SimulateResponse.gas_used)
-    pub gas_used: u64,
-    // @@protoc_insertion_point(field:alkanes.SimulateResponse.error)
-    pub error: ::std::string::String,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.target)
+    pub target: ::protobuf::MessageField<AlkaneId>,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.opcode)
+    pub opcode: ::protobuf::MessageField<Uint128>,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.fuel_self)
+    pub fuel_self: u64,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.fuel_total)
+    pub fuel_total: u64,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.storage_bytes)
+    pub storage_bytes: u64,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.reverted)
+    pub reverted: bool,
+    // @@protoc_insertion_point(field:alkanes.CallFrameFuel.calls)
+    pub calls: ::std::vec::Vec<CallFrameFuel>,
     // special fields
-    // @@protoc_insertion_point(special_field:alkanes.SimulateResponse.special_fields)
+    // @@protoc_insertion_point(special_field:alkanes.CallFrameFuel.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
 }
 
-impl<'a> ::std::default::Default for &'a SimulateResponse {
-    fn default() -> &'a SimulateResponse {
-        <SimulateResponse as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a CallFrameFuel {
+    fn default() -> &'a CallFrameFuel {
+        <CallFrameFuel as ::protobuf::Message>::default_instance()
     }
 }
 
-impl SimulateResponse {
-    pub fn new() -> SimulateResponse {
+impl CallFrameFuel {
+    pub fn new() -> CallFrameFuel {
         ::std::default::Default::default()
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut fields = ::std::vec::Vec::with_capacity(7);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
-        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ExtendedCallResponse>(
-            "execution",
-            |m: &SimulateResponse| { &m.execution },
-            |m: &mut SimulateResponse| { &mut m.execution },
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, AlkaneId>(
+            "target",
+            |m: &CallFrameFuel| { &m.target },
+            |m: &mut CallFrameFuel| { &mut m.target },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Uint128>(
+            "opcode",
+            |m: &CallFrameFuel| { &m.opcode },
+            |m: &mut CallFrameFuel| { &mut m.opcode },
         ));
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
-            "gas_used",
-            |m: &SimulateResponse| { &m.gas_used },
-            |m: &mut SimulateResponse| { &mut m.gas_used },
+            "fuel_self",
+            |m: &CallFrameFuel| { &m.fuel_self },
+            |m: &mut CallFrameFuel| { &mut m.fuel_self },
         ));
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
-            "error",
-            |m: &SimulateResponse| { &m.error },
-            |m: &mut SimulateResponse| { &mut m.error },
+            "fuel_total",
+            |m: &CallFrameFuel| { &m.fuel_total },
+            |m: &mut CallFrameFuel| { &mut m.fuel_total },
         ));
-        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SimulateResponse>(
-            "SimulateResponse",
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "storage_bytes",
+            |m: &CallFrameFuel| { &m.storage_bytes },
+            |m: &mut CallFrameFuel| { &mut m.storage_bytes },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "reverted",
+            |m: &CallFrameFuel| { &m.reverted },
+            |m: &mut CallFrameFuel| { &mut m.reverted },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "calls",
+            |m: &CallFrameFuel| { &m.calls },
+            |m: &mut CallFrameFuel| { &mut m.calls },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<CallFrameFuel>(
+            "CallFrameFuel",
             fields,
             oneofs,
         )
     }
 }
 
-impl ::protobuf::Message for SimulateResponse {
-    const NAME: &'static str = "SimulateResponse";
+impl ::protobuf::Message for CallFrameFuel {
+    const NAME: &'static str = "CallFrameFuel";
 
     fn is_initialized(&self) -> bool {
         true
@@ -2699,13 +2973,25 @@ impl ::protobuf::Message for SimulateResponse {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.execution)?;
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.target)?;
                 },
-                16 => {
-                    self.gas_used = is.read_uint64()?;
+                18 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.opcode)?;
                 },
-                26 => {
-                    self.error = is.read_string()?;
+                24 => {
+                    self.fuel_self = is.read_uint64()?;
+                },
+                32 => {
+                    self.fuel_total = is.read_uint64()?;
+                },
+                40 => {
+                    self.storage_bytes = is.read_uint64()?;
+                },
+                48 => {
+                    self.reverted = is.read_bool()?;
+                },
+                58 => {
+                    self.calls.push(is.read_message()?);
                 },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -2719,31 +3005,57 @@ impl ::protobuf::Message for SimulateResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
-        if let Some(v) = self.execution.as_ref() {
+        if let Some(v) = self.target.as_ref() {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
         }
-        if self.gas_used != 0 {
-            my_size += ::protobuf::rt::uint64_size(2, self.gas_used);
+        if let Some(v) = self.opcode.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
         }
-        if !self.error.is_empty() {
-            my_size += ::protobuf::rt::string_size(3, &self.error);
+        if self.fuel_self != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.fuel_self);
         }
+        if self.fuel_total != 0 {
+            my_size += ::protobuf::rt::uint64_size(4, self.fuel_total);
+        }
+        if self.storage_bytes != 0 {
+            my_size += ::protobuf::rt::uint64_size(5, self.storage_bytes);
+        }
+        if self.reverted != false {
+            my_size += 1 + 1;
+        }
+        for value in &self.calls {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
-        if let Some(v) = self.execution.as_ref() {
+        if let Some(v) = self.target.as_ref() {
             ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
         }
-        if self.gas_used != 0 {
-            os.write_uint64(2, self.gas_used)?;
+        if let Some(v) = self.opcode.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
         }
-        if !self.error.is_empty() {
-            os.write_string(3, &self.error)?;
+        if self.fuel_self != 0 {
+            os.write_uint64(3, self.fuel_self)?;
         }
+        if self.fuel_total != 0 {
+            os.write_uint64(4, self.fuel_total)?;
+        }
+        if self.storage_bytes != 0 {
+            os.write_uint64(5, self.storage_bytes)?;
+        }
+        if self.reverted != false {
+            os.write_bool(6, self.reverted)?;
+        }
+        for v in &self.calls {
+            ::protobuf::rt::write_message_field_with_cached_size(7, v, os)?;
+        };
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -2756,51 +3068,237 @@ impl ::protobuf::Message for SimulateResponse {
         &mut self.special_fields
     }
 
-    fn new() -> SimulateResponse {
-        SimulateResponse::new()
+    fn new() -> CallFrameFuel {
+        CallFrameFuel::new()
     }
 
     fn clear(&mut self) {
-        self.execution.clear();
-        self.gas_used = 0;
-        self.error.clear();
+        self.target.clear();
+        self.opcode.clear();
+        self.fuel_self = 0;
+        self.fuel_total = 0;
+        self.storage_bytes = 0;
+        self.reverted = false;
+        self.calls.clear();
         self.special_fields.clear();
     }
 
-    fn default_instance() -> &'static SimulateResponse {
-        static instance: SimulateResponse = SimulateResponse {
-            execution: ::protobuf::MessageField::none(),
-            gas_used: 0,
-            error: ::std::string::String::new(),
+    fn default_instance() -> &'static CallFrameFuel {
+        static instance: CallFrameFuel = CallFrameFuel {
+            target: ::protobuf::MessageField::none(),
+            opcode: ::protobuf::MessageField::none(),
+            fuel_self: 0,
+            fuel_total: 0,
+            storage_bytes: 0,
+            reverted: false,
+            calls: ::std::vec::Vec::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
     }
 }
 
-impl ::protobuf::MessageFull for SimulateResponse {
+impl ::protobuf::MessageFull for CallFrameFuel {
     fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
-        descriptor.get(|| file_descriptor().message_by_package_relative_name("SimulateResponse").unwrap()).clone()
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("CallFrameFuel").unwrap()).clone()
     }
 }
 
-impl ::std::fmt::Display for SimulateResponse {
+impl ::std::fmt::Display for CallFrameFuel {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for SimulateResponse {
+impl ::protobuf::reflect::ProtobufValue for CallFrameFuel {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-// @@protoc_insertion_point(message:alkanes.MultiSimulateResponse)
+// @@protoc_insertion_point(message:alkanes.SimulateResponse)
 #[derive(PartialEq,Clone,Default,Debug)]
-pub struct MultiSimulateResponse {
+pub struct SimulateResponse {
     // message fields
-    // @@protoc_insertion_point(field:alkanes.MultiSimulateResponse.responses)
-    pub responses: ::std::vec::Vec<SimulateResponse>,
+    // @@protoc_insertion_point(field:alkanes.SimulateResponse.execution)
+    pub execution: ::protobuf::MessageField<ExtendedCallResponse>,
+    // @@protoc_insertion_point(field:alkanes.SimulateResponse.gas_used)
+    pub gas_used: u64,
+    // @@protoc_insertion_point(field:alkanes.SimulateResponse.error)
+    pub error: ::std::string::String,
+    // @@protoc_insertion_point(field:alkanes.SimulateResponse.call_tree)
+    pub call_tree: ::protobuf::MessageField<CallFrameFuel>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.SimulateResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SimulateResponse {
+    fn default() -> &'a SimulateResponse {
+        <SimulateResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SimulateResponse {
+    pub fn new() -> SimulateResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ExtendedCallResponse>(
+            "execution",
+            |m: &SimulateResponse| { &m.execution },
+            |m: &mut SimulateResponse| { &mut m.execution },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "gas_used",
+            |m: &SimulateResponse| { &m.gas_used },
+            |m: &mut SimulateResponse| { &mut m.gas_used },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "error",
+            |m: &SimulateResponse| { &m.error },
+            |m: &mut SimulateResponse| { &mut m.error },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, CallFrameFuel>(
+            "call_tree",
+            |m: &SimulateResponse| { &m.call_tree },
+            |m: &mut SimulateResponse| { &mut m.call_tree },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SimulateResponse>(
+            "SimulateResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SimulateResponse {
+    const NAME: &'static str = "SimulateResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.execution)?;
+                },
+                16 => {
+                    self.gas_used = is.read_uint64()?;
+                },
+                26 => {
+                    self.error = is.read_string()?;
+                },
+                34 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.call_tree)?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.execution.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if self.gas_used != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.gas_used);
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.error);
+        }
+        if let Some(v) = self.call_tree.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.execution.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if self.gas_used != 0 {
+            os.write_uint64(2, self.gas_used)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(3, &self.error)?;
+        }
+        if let Some(v) = self.call_tree.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SimulateResponse {
+        SimulateResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.execution.clear();
+        self.gas_used = 0;
+        self.error.clear();
+        self.call_tree.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SimulateResponse {
+        static instance: SimulateResponse = SimulateResponse {
+            execution: ::protobuf::MessageField::none(),
+            gas_used: 0,
+            error: ::std::string::String::new(),
+            call_tree: ::protobuf::MessageField::none(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SimulateResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SimulateResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SimulateResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SimulateResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.MultiSimulateResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct MultiSimulateResponse {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.MultiSimulateResponse.responses)
+    pub responses: ::std::vec::Vec<SimulateResponse>,
     // @@protoc_insertion_point(field:alkanes.MultiSimulateResponse.error)
     pub error: ::std::string::String,
     // special fields
@@ -4357,6 +4855,1177 @@ impl ::protobuf::reflect::ProtobufValue for BlockResponse {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+// @@protoc_insertion_point(message:alkanes.AlkanesEvent)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct AlkanesEvent {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.AlkanesEvent.outpoint)
+    pub outpoint: ::protobuf::MessageField<Outpoint>,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEvent.height)
+    pub height: u64,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEvent.alkane)
+    pub alkane: ::protobuf::MessageField<AlkaneId>,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEvent.topic)
+    pub topic: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:alkanes.AlkanesEvent.data)
+    pub data: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.AlkanesEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a AlkanesEvent {
+    fn default() -> &'a AlkanesEvent {
+        <AlkanesEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl AlkanesEvent {
+    pub fn new() -> AlkanesEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Outpoint>(
+            "outpoint",
+            |m: &AlkanesEvent| { &m.outpoint },
+            |m: &mut AlkanesEvent| { &mut m.outpoint },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "height",
+            |m: &AlkanesEvent| { &m.height },
+            |m: &mut AlkanesEvent| { &mut m.height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, AlkaneId>(
+            "alkane",
+            |m: &AlkanesEvent| { &m.alkane },
+            |m: &mut AlkanesEvent| { &mut m.alkane },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "topic",
+            |m: &AlkanesEvent| { &m.topic },
+            |m: &mut AlkanesEvent| { &mut m.topic },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "data",
+            |m: &AlkanesEvent| { &m.data },
+            |m: &mut AlkanesEvent| { &mut m.data },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<AlkanesEvent>(
+            "AlkanesEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for AlkanesEvent {
+    const NAME: &'static str = "AlkanesEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.outpoint)?;
+                },
+                16 => {
+                    self.height = is.read_uint64()?;
+                },
+                26 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.alkane)?;
+                },
+                34 => {
+                    self.topic = is.read_bytes()?;
+                },
+                42 => {
+                    self.data = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.outpoint.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if self.height != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.height);
+        }
+        if let Some(v) = self.alkane.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if !self.topic.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.topic);
+        }
+        if !self.data.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(5, &self.data);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.outpoint.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if self.height != 0 {
+            os.write_uint64(2, self.height)?;
+        }
+        if let Some(v) = self.alkane.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        }
+        if !self.topic.is_empty() {
+            os.write_bytes(4, &self.topic)?;
+        }
+        if !self.data.is_empty() {
+            os.write_bytes(5, &self.data)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> AlkanesEvent {
+        AlkanesEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.outpoint.clear();
+        self.height = 0;
+        self.alkane.clear();
+        self.topic.clear();
+        self.data.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static AlkanesEvent {
+        static instance: AlkanesEvent = AlkanesEvent {
+            outpoint: ::protobuf::MessageField::none(),
+            height: 0,
+            alkane: ::protobuf::MessageField::none(),
+            topic: ::std::vec::Vec::new(),
+            data: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for AlkanesEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("AlkanesEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for AlkanesEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for AlkanesEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.EventsRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct EventsRequest {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.id)
+    pub id: ::protobuf::MessageField<AlkaneId>,
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.topic)
+    pub topic: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.from_height)
+    pub from_height: u64,
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.to_height)
+    pub to_height: u64,
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.start)
+    pub start: u32,
+    // @@protoc_insertion_point(field:alkanes.EventsRequest.limit)
+    pub limit: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.EventsRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a EventsRequest {
+    fn default() -> &'a EventsRequest {
+        <EventsRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EventsRequest {
+    pub fn new() -> EventsRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, AlkaneId>(
+            "id",
+            |m: &EventsRequest| { &m.id },
+            |m: &mut EventsRequest| { &mut m.id },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "topic",
+            |m: &EventsRequest| { &m.topic },
+            |m: &mut EventsRequest| { &mut m.topic },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "from_height",
+            |m: &EventsRequest| { &m.from_height },
+            |m: &mut EventsRequest| { &mut m.from_height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "to_height",
+            |m: &EventsRequest| { &m.to_height },
+            |m: &mut EventsRequest| { &mut m.to_height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "start",
+            |m: &EventsRequest| { &m.start },
+            |m: &mut EventsRequest| { &mut m.start },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "limit",
+            |m: &EventsRequest| { &m.limit },
+            |m: &mut EventsRequest| { &mut m.limit },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<EventsRequest>(
+            "EventsRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for EventsRequest {
+    const NAME: &'static str = "EventsRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.id)?;
+                },
+                18 => {
+                    self.topic = is.read_bytes()?;
+                },
+                24 => {
+                    self.from_height = is.read_uint64()?;
+                },
+                32 => {
+                    self.to_height = is.read_uint64()?;
+                },
+                40 => {
+                    self.start = is.read_uint32()?;
+                },
+                48 => {
+                    self.limit = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.id.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if !self.topic.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.topic);
+        }
+        if self.from_height != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.from_height);
+        }
+        if self.to_height != 0 {
+            my_size += ::protobuf::rt::uint64_size(4, self.to_height);
+        }
+        if self.start != 0 {
+            my_size += ::protobuf::rt::uint32_size(5, self.start);
+        }
+        if self.limit != 0 {
+            my_size += ::protobuf::rt::uint32_size(6, self.limit);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.id.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if !self.topic.is_empty() {
+            os.write_bytes(2, &self.topic)?;
+        }
+        if self.from_height != 0 {
+            os.write_uint64(3, self.from_height)?;
+        }
+        if self.to_height != 0 {
+            os.write_uint64(4, self.to_height)?;
+        }
+        if self.start != 0 {
+            os.write_uint32(5, self.start)?;
+        }
+        if self.limit != 0 {
+            os.write_uint32(6, self.limit)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> EventsRequest {
+        EventsRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.id.clear();
+        self.topic.clear();
+        self.from_height = 0;
+        self.to_height = 0;
+        self.start = 0;
+        self.limit = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static EventsRequest {
+        static instance: EventsRequest = EventsRequest {
+            id: ::protobuf::MessageField::none(),
+            topic: ::std::vec::Vec::new(),
+            from_height: 0,
+            to_height: 0,
+            start: 0,
+            limit: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for EventsRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("EventsRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for EventsRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EventsRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.EventsResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct EventsResponse {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.EventsResponse.events)
+    pub events: ::std::vec::Vec<AlkanesEvent>,
+    // @@protoc_insertion_point(field:alkanes.EventsResponse.next)
+    pub next: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.EventsResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a EventsResponse {
+    fn default() -> &'a EventsResponse {
+        <EventsResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EventsResponse {
+    pub fn new() -> EventsResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "events",
+            |m: &EventsResponse| { &m.events },
+            |m: &mut EventsResponse| { &mut m.events },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "next",
+            |m: &EventsResponse| { &m.next },
+            |m: &mut EventsResponse| { &mut m.next },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<EventsResponse>(
+            "EventsResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for EventsResponse {
+    const NAME: &'static str = "EventsResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.events.push(is.read_message()?);
+                },
+                16 => {
+                    self.next = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.events {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        if self.next != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.next);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.events {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        };
+        if self.next != 0 {
+            os.write_uint32(2, self.next)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> EventsResponse {
+        EventsResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+        self.next = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static EventsResponse {
+        static instance: EventsResponse = EventsResponse {
+            events: ::std::vec::Vec::new(),
+            next: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for EventsResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("EventsResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for EventsResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EventsResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.MempoolSimulateRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct MempoolSimulateRequest {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.MempoolSimulateRequest.transactions)
+    pub transactions: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.MempoolSimulateRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MempoolSimulateRequest {
+    fn default() -> &'a MempoolSimulateRequest {
+        <MempoolSimulateRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MempoolSimulateRequest {
+    pub fn new() -> MempoolSimulateRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "transactions",
+            |m: &MempoolSimulateRequest| { &m.transactions },
+            |m: &mut MempoolSimulateRequest| { &mut m.transactions },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MempoolSimulateRequest>(
+            "MempoolSimulateRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MempoolSimulateRequest {
+    const NAME: &'static str = "MempoolSimulateRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.transactions.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.transactions {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.transactions {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MempoolSimulateRequest {
+        MempoolSimulateRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.transactions.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MempoolSimulateRequest {
+        static instance: MempoolSimulateRequest = MempoolSimulateRequest {
+            transactions: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MempoolSimulateRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MempoolSimulateRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MempoolSimulateRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MempoolSimulateRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.MempoolOutput)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct MempoolOutput {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.MempoolOutput.outpoint)
+    pub outpoint: ::protobuf::MessageField<Outpoint>,
+    // @@protoc_insertion_point(field:alkanes.MempoolOutput.runes)
+    pub runes: ::std::vec::Vec<AlkaneTransfer>,
+    // @@protoc_insertion_point(field:alkanes.MempoolOutput.alkanes)
+    pub alkanes: ::std::vec::Vec<AlkaneTransfer>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.MempoolOutput.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MempoolOutput {
+    fn default() -> &'a MempoolOutput {
+        <MempoolOutput as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MempoolOutput {
+    pub fn new() -> MempoolOutput {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Outpoint>(
+            "outpoint",
+            |m: &MempoolOutput| { &m.outpoint },
+            |m: &mut MempoolOutput| { &mut m.outpoint },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "runes",
+            |m: &MempoolOutput| { &m.runes },
+            |m: &mut MempoolOutput| { &mut m.runes },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "alkanes",
+            |m: &MempoolOutput| { &m.alkanes },
+            |m: &mut MempoolOutput| { &mut m.alkanes },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MempoolOutput>(
+            "MempoolOutput",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MempoolOutput {
+    const NAME: &'static str = "MempoolOutput";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.outpoint)?;
+                },
+                18 => {
+                    self.runes.push(is.read_message()?);
+                },
+                26 => {
+                    self.alkanes.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.outpoint.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        for value in &self.runes {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.alkanes {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.outpoint.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        for v in &self.runes {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        };
+        for v in &self.alkanes {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MempoolOutput {
+        MempoolOutput::new()
+    }
+
+    fn clear(&mut self) {
+        self.outpoint.clear();
+        self.runes.clear();
+        self.alkanes.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MempoolOutput {
+        static instance: MempoolOutput = MempoolOutput {
+            outpoint: ::protobuf::MessageField::none(),
+            runes: ::std::vec::Vec::new(),
+            alkanes: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MempoolOutput {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MempoolOutput").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MempoolOutput {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MempoolOutput {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.MempoolFailure)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct MempoolFailure {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.MempoolFailure.txindex)
+    pub txindex: u32,
+    // @@protoc_insertion_point(field:alkanes.MempoolFailure.outpoint)
+    pub outpoint: ::protobuf::MessageField<Outpoint>,
+    // @@protoc_insertion_point(field:alkanes.MempoolFailure.error)
+    pub error: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.MempoolFailure.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MempoolFailure {
+    fn default() -> &'a MempoolFailure {
+        <MempoolFailure as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MempoolFailure {
+    pub fn new() -> MempoolFailure {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "txindex",
+            |m: &MempoolFailure| { &m.txindex },
+            |m: &mut MempoolFailure| { &mut m.txindex },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Outpoint>(
+            "outpoint",
+            |m: &MempoolFailure| { &m.outpoint },
+            |m: &mut MempoolFailure| { &mut m.outpoint },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "error",
+            |m: &MempoolFailure| { &m.error },
+            |m: &mut MempoolFailure| { &mut m.error },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MempoolFailure>(
+            "MempoolFailure",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MempoolFailure {
+    const NAME: &'static str = "MempoolFailure";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.txindex = is.read_uint32()?;
+                },
+                18 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.outpoint)?;
+                },
+                26 => {
+                    self.error = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.txindex != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.txindex);
+        }
+        if let Some(v) = self.outpoint.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.error);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.txindex != 0 {
+            os.write_uint32(1, self.txindex)?;
+        }
+        if let Some(v) = self.outpoint.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(3, &self.error)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MempoolFailure {
+        MempoolFailure::new()
+    }
+
+    fn clear(&mut self) {
+        self.txindex = 0;
+        self.outpoint.clear();
+        self.error.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MempoolFailure {
+        static instance: MempoolFailure = MempoolFailure {
+            txindex: 0,
+            outpoint: ::protobuf::MessageField::none(),
+            error: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MempoolFailure {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MempoolFailure").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MempoolFailure {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MempoolFailure {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:alkanes.MempoolSimulateResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct MempoolSimulateResponse {
+    // message fields
+    // @@protoc_insertion_point(field:alkanes.MempoolSimulateResponse.height)
+    pub height: u64,
+    // @@protoc_insertion_point(field:alkanes.MempoolSimulateResponse.outputs)
+    pub outputs: ::std::vec::Vec<MempoolOutput>,
+    // @@protoc_insertion_point(field:alkanes.MempoolSimulateResponse.traces)
+    pub traces: ::std::vec::Vec<Trace>,
+    // @@protoc_insertion_point(field:alkanes.MempoolSimulateResponse.failures)
+    pub failures: ::std::vec::Vec<MempoolFailure>,
+    // special fields
+    // @@protoc_insertion_point(special_field:alkanes.MempoolSimulateResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MempoolSimulateResponse {
+    fn default() -> &'a MempoolSimulateResponse {
+        <MempoolSimulateResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MempoolSimulateResponse {
+    pub fn new() -> MempoolSimulateResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "height",
+            |m: &MempoolSimulateResponse| { &m.height },
+            |m: &mut MempoolSimulateResponse| { &mut m.height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "outputs",
+            |m: &MempoolSimulateResponse| { &m.outputs },
+            |m: &mut MempoolSimulateResponse| { &mut m.outputs },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "traces",
+            |m: &MempoolSimulateResponse| { &m.traces },
+            |m: &mut MempoolSimulateResponse| { &mut m.traces },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "failures",
+            |m: &MempoolSimulateResponse| { &m.failures },
+            |m: &mut MempoolSimulateResponse| { &mut m.failures },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MempoolSimulateResponse>(
+            "MempoolSimulateResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MempoolSimulateResponse {
+    const NAME: &'static str = "MempoolSimulateResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.height = is.read_uint64()?;
+                },
+                18 => {
+                    self.outputs.push(is.read_message()?);
+                },
+                26 => {
+                    self.traces.push(is.read_message()?);
+                },
+                34 => {
+                    self.failures.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.height != 0 {
+            my_size += ::protobuf::rt::uint64_size(1, self.height);
+        }
+        for value in &self.outputs {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.traces {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.failures {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.height != 0 {
+            os.write_uint64(1, self.height)?;
+        }
+        for v in &self.outputs {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        };
+        for v in &self.traces {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        };
+        for v in &self.failures {
+            ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MempoolSimulateResponse {
+        MempoolSimulateResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.height = 0;
+        self.outputs.clear();
+        self.traces.clear();
+        self.failures.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MempoolSimulateResponse {
+        static instance: MempoolSimulateResponse = MempoolSimulateResponse {
+            height: 0,
+            outputs: ::std::vec::Vec::new(),
+            traces: ::std::vec::Vec::new(),
+            failures: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MempoolSimulateResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MempoolSimulateResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MempoolSimulateResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MempoolSimulateResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 // @@protoc_insertion_point(enum:alkanes.AlkanesTraceCallType)
 pub enum AlkanesTraceCallType {
@@ -4517,52 +6186,88 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     aller\x18\x02\x20\x01(\x0b2\x11.alkanes.AlkaneIdR\x06caller\x12(\n\x06in\
     puts\x18\x03\x20\x03(\x0b2\x10.alkanes.uint128R\x06inputs\x12\x12\n\x04v\
     out\x18\x04\x20\x01(\rR\x04vout\x12B\n\x10incoming_alkanes\x18\x05\x20\
-    \x03(\x0b2\x17.alkanes.AlkaneTransferR\x0fincomingAlkanes\"J\n\x0cTraceC\
+    \x03(\x0b2\x17.alkanes.AlkaneTransferR\x0fincomingAlkanes\"q\n\x0cTraceC\
     ontext\x12&\n\x05inner\x18\x01\x20\x01(\x0b2\x10.alkanes.ContextR\x05inn\
-    er\x12\x12\n\x04fuel\x18\x02\x20\x01(\x04R\x04fuel\"\x82\x01\n\x13Alkane\
-    sEnterContext\x12:\n\tcall_type\x18\x01\x20\x01(\x0e2\x1d.alkanes.Alkane\
-    sTraceCallTypeR\x08callType\x12/\n\x07context\x18\x02\x20\x01(\x0b2\x15.\
-    alkanes.TraceContextR\x07context\"\x88\x01\n\x12AlkanesExitContext\x127\
-    \n\x06status\x18\x01\x20\x01(\x0e2\x1f.alkanes.AlkanesTraceStatusFlagR\
-    \x06status\x129\n\x08response\x18\x02\x20\x01(\x0b2\x1d.alkanes.Extended\
-    CallResponseR\x08response\"A\n\rAlkanesCreate\x120\n\nnew_alkane\x18\x01\
-    \x20\x01(\x0b2\x11.alkanes.AlkaneIdR\tnewAlkane\"\xe2\x01\n\x11AlkanesTr\
-    aceEvent\x12C\n\renter_context\x18\x01\x20\x01(\x0b2\x1c.alkanes.Alkanes\
-    EnterContextH\0R\x0centerContext\x12@\n\x0cexit_context\x18\x02\x20\x01(\
-    \x0b2\x1b.alkanes.AlkanesExitContextH\0R\x0bexitContext\x12=\n\rcreate_a\
-    lkane\x18\x03\x20\x01(\x0b2\x16.alkanes.AlkanesCreateH\0R\x0ccreateAlkan\
-    eB\x07\n\x05event\"\x8b\x01\n\x11AlkanesBlockEvent\x12-\n\x06traces\x18\
-    \x01\x20\x01(\x0b2\x15.alkanes.AlkanesTraceR\x06traces\x12-\n\x08outpoin\
-    t\x18\x02\x20\x01(\x0b2\x11.alkanes.OutpointR\x08outpoint\x12\x18\n\x07t\
-    xindex\x18\x03\x20\x01(\x04R\x07txindex\"L\n\x16AlkanesBlockTraceEvent\
-    \x122\n\x06events\x18\x01\x20\x03(\x0b2\x1a.alkanes.AlkanesBlockEventR\
-    \x06events\"B\n\x0cAlkanesTrace\x122\n\x06events\x18\x01\x20\x03(\x0b2\
-    \x1a.alkanes.AlkanesTraceEventR\x06events\"\x80\x01\n\x10SimulateRespons\
-    e\x12;\n\texecution\x18\x01\x20\x01(\x0b2\x1d.alkanes.ExtendedCallRespon\
-    seR\texecution\x12\x19\n\x08gas_used\x18\x02\x20\x01(\x04R\x07gasUsed\
-    \x12\x14\n\x05error\x18\x03\x20\x01(\tR\x05error\"f\n\x15MultiSimulateRe\
-    sponse\x127\n\tresponses\x18\x01\x20\x03(\x0b2\x19.alkanes.SimulateRespo\
-    nseR\tresponses\x12\x14\n\x05error\x18\x02\x20\x01(\tR\x05error\";\n\x16\
-    AlkaneInventoryRequest\x12!\n\x02id\x18\x01\x20\x01(\x0b2\x11.alkanes.Al\
-    kaneIdR\x02id\">\n\x19AlkaneIdToOutpointRequest\x12!\n\x02id\x18\x01\x20\
-    \x01(\x0b2\x11.alkanes.AlkaneIdR\x02id\"L\n\x17AlkaneInventoryResponse\
-    \x121\n\x07alkanes\x18\x01\x20\x03(\x0b2\x17.alkanes.AlkaneTransferR\x07\
-    alkanes\"D\n\x1aAlkaneIdToOutpointResponse\x12\x12\n\x04txid\x18\x01\x20\
-    \x01(\x0cR\x04txid\x12\x12\n\x04vout\x18\x02\x20\x01(\rR\x04vout\"2\n\
-    \x08Outpoint\x12\x12\n\x04txid\x18\x01\x20\x01(\x0cR\x04txid\x12\x12\n\
-    \x04vout\x18\x02\x20\x01(\rR\x04vout\"c\n\x05Trace\x12-\n\x08outpoint\
-    \x18\x01\x20\x01(\x0b2\x11.alkanes.OutpointR\x08outpoint\x12+\n\x05trace\
-    \x18\x02\x20\x01(\x0b2\x15.alkanes.AlkanesTraceR\x05trace\")\n\x11TraceB\
-    lockRequest\x12\x14\n\x05block\x18\x01\x20\x01(\x04R\x05block\"<\n\x12Tr\
-    aceBlockResponse\x12&\n\x06traces\x18\x01\x20\x03(\x0b2\x0e.alkanes.Trac\
-    eR\x06traces\"4\n\x0fBytecodeRequest\x12!\n\x02id\x18\x01\x20\x01(\x0b2\
-    \x11.alkanes.AlkaneIdR\x02id\"&\n\x0cBlockRequest\x12\x16\n\x06height\
-    \x18\x01\x20\x01(\rR\x06height\"=\n\rBlockResponse\x12\x14\n\x05block\
-    \x18\x01\x20\x01(\x0cR\x05block\x12\x16\n\x06height\x18\x02\x20\x01(\rR\
-    \x06height*L\n\x14AlkanesTraceCallType\x12\x08\n\x04NONE\x10\0\x12\x08\n\
-    \x04CALL\x10\x01\x12\x10\n\x0cDELEGATECALL\x10\x02\x12\x0e\n\nSTATICCALL\
-    \x10\x03*2\n\x16AlkanesTraceStatusFlag\x12\x0b\n\x07SUCCESS\x10\0\x12\
-    \x0b\n\x07FAILURE\x10\x01b\x06proto3\
+    er\x12\x12\n\x04fuel\x18\x02\x20\x01(\x04R\x04fuel\x12%\n\x0erequested_f\
+    uel\x18\x03\x20\x01(\x04R\rrequestedFuel\"\x82\x01\n\x13AlkanesEnterCont\
+    ext\x12:\n\tcall_type\x18\x01\x20\x01(\x0e2\x1d.alkanes.AlkanesTraceCall\
+    TypeR\x08callType\x12/\n\x07context\x18\x02\x20\x01(\x0b2\x15.alkanes.Tr\
+    aceContextR\x07context\"\x88\x01\n\x12AlkanesExitContext\x127\n\x06statu\
+    s\x18\x01\x20\x01(\x0e2\x1f.alkanes.AlkanesTraceStatusFlagR\x06status\
+    \x129\n\x08response\x18\x02\x20\x01(\x0b2\x1d.alkanes.ExtendedCallRespon\
+    seR\x08response\"A\n\rAlkanesCreate\x120\n\nnew_alkane\x18\x01\x20\x01(\
+    \x0b2\x11.alkanes.AlkaneIdR\tnewAlkane\"b\n\x0bAlkanesEmit\x12)\n\x06alk\
+    ane\x18\x01\x20\x01(\x0b2\x11.alkanes.AlkaneIdR\x06alkane\x12\x14\n\x05t\
+    opic\x18\x02\x20\x01(\x0cR\x05topic\x12\x12\n\x04data\x18\x03\x20\x01(\
+    \x0cR\x04data\"\x8e\x02\n\x11AlkanesTraceEvent\x12C\n\renter_context\x18\
+    \x01\x20\x01(\x0b2\x1c.alkanes.AlkanesEnterContextH\0R\x0centerContext\
+    \x12@\n\x0cexit_context\x18\x02\x20\x01(\x0b2\x1b.alkanes.AlkanesExitCon\
+    textH\0R\x0bexitContext\x12=\n\rcreate_alkane\x18\x03\x20\x01(\x0b2\x16.\
+    alkanes.AlkanesCreateH\0R\x0ccreateAlkane\x12*\n\x04emit\x18\x04\x20\x01\
+    (\x0b2\x14.alkanes.AlkanesEmitH\0R\x04emitB\x07\n\x05event\"\x8b\x01\n\
+    \x11AlkanesBlockEvent\x12-\n\x06traces\x18\x01\x20\x01(\x0b2\x15.alkanes\
+    .AlkanesTraceR\x06traces\x12-\n\x08outpoint\x18\x02\x20\x01(\x0b2\x11.al\
+    kanes.OutpointR\x08outpoint\x12\x18\n\x07txindex\x18\x03\x20\x01(\x04R\
+    \x07txindex\"L\n\x16AlkanesBlockTraceEvent\x122\n\x06events\x18\x01\x20\
+    \x03(\x0b2\x1a.alkanes.AlkanesBlockEventR\x06events\"B\n\x0cAlkanesTrace\
+    \x122\n\x06events\x18\x01\x20\x03(\x0b2\x1a.alkanes.AlkanesTraceEventR\
+    \x06events\"\x8f\x02\n\rCallFrameFuel\x12)\n\x06target\x18\x01\x20\x01(\
+    \x0b2\x11.alkanes.AlkaneIdR\x06target\x12(\n\x06opcode\x18\x02\x20\x01(\
+    \x0b2\x10.alkanes.uint128R\x06opcode\x12\x1b\n\tfuel_self\x18\x03\x20\
+    \x01(\x04R\x08fuelSelf\x12\x1d\n\nfuel_total\x18\x04\x20\x01(\x04R\tfuel\
+    Total\x12#\n\rstorage_bytes\x18\x05\x20\x01(\x04R\x0cstorageBytes\x12\
+    \x1a\n\x08reverted\x18\x06\x20\x01(\x08R\x08reverted\x12,\n\x05calls\x18\
+    \x07\x20\x03(\x0b2\x16.alkanes.CallFrameFuelR\x05calls\"\xb5\x01\n\x10Si\
+    mulateResponse\x12;\n\texecution\x18\x01\x20\x01(\x0b2\x1d.alkanes.Exten\
+    dedCallResponseR\texecution\x12\x19\n\x08gas_used\x18\x02\x20\x01(\x04R\
+    \x07gasUsed\x12\x14\n\x05error\x18\x03\x20\x01(\tR\x05error\x123\n\tcall\
+    _tree\x18\x04\x20\x01(\x0b2\x16.alkanes.CallFrameFuelR\x08callTree\"f\n\
+    \x15MultiSimulateResponse\x127\n\tresponses\x18\x01\x20\x03(\x0b2\x19.al\
+    kanes.SimulateResponseR\tresponses\x12\x14\n\x05error\x18\x02\x20\x01(\t\
+    R\x05error\";\n\x16AlkaneInventoryRequest\x12!\n\x02id\x18\x01\x20\x01(\
+    \x0b2\x11.alkanes.AlkaneIdR\x02id\">\n\x19AlkaneIdToOutpointRequest\x12!\
+    \n\x02id\x18\x01\x20\x01(\x0b2\x11.alkanes.AlkaneIdR\x02id\"L\n\x17Alkan\
+    eInventoryResponse\x121\n\x07alkanes\x18\x01\x20\x03(\x0b2\x17.alkanes.A\
+    lkaneTransferR\x07alkanes\"D\n\x1aAlkaneIdToOutpointResponse\x12\x12\n\
+    \x04txid\x18\x01\x20\x01(\x0cR\x04txid\x12\x12\n\x04vout\x18\x02\x20\x01\
+    (\rR\x04vout\"2\n\x08Outpoint\x12\x12\n\x04txid\x18\x01\x20\x01(\x0cR\
+    \x04txid\x12\x12\n\x04vout\x18\x02\x20\x01(\rR\x04vout\"c\n\x05Trace\x12\
+    -\n\x08outpoint\x18\x01\x20\x01(\x0b2\x11.alkanes.OutpointR\x08outpoint\
+    \x12+\n\x05trace\x18\x02\x20\x01(\x0b2\x15.alkanes.AlkanesTraceR\x05trac\
+    e\")\n\x11TraceBlockRequest\x12\x14\n\x05block\x18\x01\x20\x01(\x04R\x05\
+    block\"<\n\x12TraceBlockResponse\x12&\n\x06traces\x18\x01\x20\x03(\x0b2\
+    \x0e.alkanes.TraceR\x06traces\"4\n\x0fBytecodeRequest\x12!\n\x02id\x18\
+    \x01\x20\x01(\x0b2\x11.alkanes.AlkaneIdR\x02id\"&\n\x0cBlockRequest\x12\
+    \x16\n\x06height\x18\x01\x20\x01(\rR\x06height\"=\n\rBlockResponse\x12\
+    \x14\n\x05block\x18\x01\x20\x01(\x0cR\x05block\x12\x16\n\x06height\x18\
+    \x02\x20\x01(\rR\x06height\"\xaa\x01\n\x0cAlkanesEvent\x12-\n\x08outpoin\
+    t\x18\x01\x20\x01(\x0b2\x11.alkanes.OutpointR\x08outpoint\x12\x16\n\x06h\
+    eight\x18\x02\x20\x01(\x04R\x06height\x12)\n\x06alkane\x18\x03\x20\x01(\
+    \x0b2\x11.alkanes.AlkaneIdR\x06alkane\x12\x14\n\x05topic\x18\x04\x20\x01\
+    (\x0cR\x05topic\x12\x12\n\x04data\x18\x05\x20\x01(\x0cR\x04data\"\xb2\
+    \x01\n\rEventsRequest\x12!\n\x02id\x18\x01\x20\x01(\x0b2\x11.alkanes.Alk\
+    aneIdR\x02id\x12\x14\n\x05topic\x18\x02\x20\x01(\x0cR\x05topic\x12\x1f\n\
+    \x0bfrom_height\x18\x03\x20\x01(\x04R\nfromHeight\x12\x1b\n\tto_height\
+    \x18\x04\x20\x01(\x04R\x08toHeight\x12\x14\n\x05start\x18\x05\x20\x01(\r\
+    R\x05start\x12\x14\n\x05limit\x18\x06\x20\x01(\rR\x05limit\"S\n\x0eEvent\
+    sResponse\x12-\n\x06events\x18\x01\x20\x03(\x0b2\x15.alkanes.AlkanesEven\
+    tR\x06events\x12\x12\n\x04next\x18\x02\x20\x01(\rR\x04next\"<\n\x16Mempo\
+    olSimulateRequest\x12\"\n\x0ctransactions\x18\x01\x20\x03(\x0cR\x0ctrans\
+    actions\"\xa0\x01\n\rMempoolOutput\x12-\n\x08outpoint\x18\x01\x20\x01(\
+    \x0b2\x11.alkanes.OutpointR\x08outpoint\x12-\n\x05runes\x18\x02\x20\x03(\
+    \x0b2\x17.alkanes.AlkaneTransferR\x05runes\x121\n\x07alkanes\x18\x03\x20\
+    \x03(\x0b2\x17.alkanes.AlkaneTransferR\x07alkanes\"o\n\x0eMempoolFailure\
+    \x12\x18\n\x07txindex\x18\x01\x20\x01(\rR\x07txindex\x12-\n\x08outpoint\
+    \x18\x02\x20\x01(\x0b2\x11.alkanes.OutpointR\x08outpoint\x12\x14\n\x05er\
+    ror\x18\x03\x20\x01(\tR\x05error\"\xc0\x01\n\x17MempoolSimulateResponse\
+    \x12\x16\n\x06height\x18\x01\x20\x01(\x04R\x06height\x120\n\x07outputs\
+    \x18\x02\x20\x03(\x0b2\x16.alkanes.MempoolOutputR\x07outputs\x12&\n\x06t\
+    races\x18\x03\x20\x03(\x0b2\x0e.alkanes.TraceR\x06traces\x123\n\x08failu\
+    res\x18\x04\x20\x03(\x0b2\x17.alkanes.MempoolFailureR\x08failures*L\n\
+    \x14AlkanesTraceCallType\x12\x08\n\x04NONE\x10\0\x12\x08\n\x04CALL\x10\
+    \x01\x12\x10\n\x0cDELEGATECALL\x10\x02\x12\x0e\n\nSTATICCALL\x10\x03*2\n\
+    \x16AlkanesTraceStatusFlag\x12\x0b\n\x07SUCCESS\x10\0\x12\x0b\n\x07FAILU\
+    RE\x10\x01b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -4580,7 +6285,7 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(29);
+            let mut messages = ::std::vec::Vec::with_capacity(38);
             messages.push(Uint128::generated_message_descriptor_data());
             messages.push(AlkaneId::generated_message_descriptor_data());
             messages.push(AlkaneTransfer::generated_message_descriptor_data());
@@ -4593,10 +6298,12 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             messages.push(AlkanesEnterContext::generated_message_descriptor_data());
             messages.push(AlkanesExitContext::generated_message_descriptor_data());
             messages.push(AlkanesCreate::generated_message_descriptor_data());
+            messages.push(AlkanesEmit::generated_message_descriptor_data());
             messages.push(AlkanesTraceEvent::generated_message_descriptor_data());
             messages.push(AlkanesBlockEvent::generated_message_descriptor_data());
             messages.push(AlkanesBlockTraceEvent::generated_message_descriptor_data());
             messages.push(AlkanesTrace::generated_message_descriptor_data());
+            messages.push(CallFrameFuel::generated_message_descriptor_data());
             messages.push(SimulateResponse::generated_message_descriptor_data());
             messages.push(MultiSimulateResponse::generated_message_descriptor_data());
             messages.push(AlkaneInventoryRequest::generated_message_descriptor_data());
@@ -4610,6 +6317,13 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             messages.push(BytecodeRequest::generated_message_descriptor_data());
             messages.push(BlockRequest::generated_message_descriptor_data());
             messages.push(BlockResponse::generated_message_descriptor_data());
+            messages.push(AlkanesEvent::generated_message_descriptor_data());
+            messages.push(EventsRequest::generated_message_descriptor_data());
+            messages.push(EventsResponse::generated_message_descriptor_data());
+            messages.push(MempoolSimulateRequest::generated_message_descriptor_data());
+            messages.push(MempoolOutput::generated_message_descriptor_data());
+            messages.push(MempoolFailure::generated_message_descriptor_data());
+            messages.push(MempoolSimulateResponse::generated_message_descriptor_data());
             let mut enums = ::std::vec::Vec::with_capacity(2);
             enums.push(AlkanesTraceCallType::generated_enum_descriptor_data());
             enums.push(AlkanesTraceStatusFlag::generated_enum_descriptor_data());
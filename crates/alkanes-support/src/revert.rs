@@ -0,0 +1,136 @@
+use crate::id::AlkaneId;
+use anyhow::Result;
+use metashrew_support::utils::{consume_sized_int, consume_to_end};
+use std::fmt;
+use std::io::Cursor;
+
+/// Selector of the Solidity-style `Error(string)` revert, followed by the message bytes.
+/// Still accepted when decoding, so older contracts and traces keep working.
+pub const REVERT_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of a typed revert: a u32 code, the reverting alkane, the call depth as a u32 and
+/// then the message bytes, all integers little-endian.
+pub const TYPED_REVERT_SELECTOR: [u8; 4] = [0xa1, 0x4b, 0x5e, 0x7d];
+
+/// An error returned by the contract itself.
+pub const REVERT_CODE_CONTRACT: u32 = 0;
+/// The VM stopped the call: a trap, a host function error or running out of fuel.
+pub const REVERT_CODE_VM: u32 = 1;
+/// The message never reached a contract, e.g. a rejected deploy or a bad cellpack.
+pub const REVERT_CODE_MESSAGE: u32 = 2;
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Revert {
+    pub code: u32,
+    pub message: String,
+    pub contract: AlkaneId,
+    pub depth: u32,
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Revert {}
+
+impl Revert {
+    pub fn new(code: u32, message: impl Into<String>, contract: AlkaneId, depth: u32) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            contract,
+            depth,
+        }
+    }
+    /// Recovers the typed revert carried by `error`, or wraps its text in a new one raised
+    /// by `contract` at `depth`.
+    pub fn from_error(error: &anyhow::Error, code: u32, contract: &AlkaneId, depth: u32) -> Self {
+        match error.downcast_ref::<Revert>() {
+            Some(revert) => revert.clone(),
+            None => Self::new(code, error.to_string(), *contract, depth),
+        }
+    }
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = TYPED_REVERT_SELECTOR.to_vec();
+        data.extend(&self.code.to_le_bytes());
+        data.extend(self.contract.block.to_le_bytes());
+        data.extend(self.contract.tx.to_le_bytes());
+        data.extend(&self.depth.to_le_bytes());
+        data.extend(self.message.as_bytes());
+        data
+    }
+    /// Encodes the revert as a legacy `Error(string)`, which carries only the message.
+    pub fn encode_legacy(&self) -> Vec<u8> {
+        let mut data = REVERT_SELECTOR.to_vec();
+        data.extend(self.message.as_bytes());
+        data
+    }
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor::new(data.to_vec());
+        let code = consume_sized_int::<u32>(&mut cursor)?;
+        let contract = AlkaneId::parse(&mut cursor)?;
+        let depth = consume_sized_int::<u32>(&mut cursor)?;
+        let message = String::from_utf8_lossy(&consume_to_end(&mut cursor)?).to_string();
+        Ok(Self::new(code, message, contract, depth))
+    }
+    /// Decodes revert data in either encoding. A legacy `Error(string)` revert comes back as
+    /// a contract error with no contract id or depth.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let selector: [u8; 4] = data.get(0..4)?.try_into().ok()?;
+        match selector {
+            TYPED_REVERT_SELECTOR => Self::parse(&data[4..]).ok(),
+            REVERT_SELECTOR => Some(Self {
+                message: String::from_utf8_lossy(&data[4..]).to_string(),
+                ..Default::default()
+            }),
+            _ => None,
+        }
+    }
+    /// Decodes only a legacy `Error(string)` revert, reading a message that is not UTF-8 as
+    /// hex, as was done before typed reverts.
+    pub fn decode_legacy(data: &[u8]) -> Option<Self> {
+        if data.get(0..4)? != REVERT_SELECTOR {
+            return None;
+        }
+        Some(Self {
+            message: String::from_utf8(data[4..].to_vec())
+                .unwrap_or_else(|_| hex::encode(&data[4..])),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revert_roundtrip() {
+        let revert = Revert::new(REVERT_CODE_VM, "out of fuel", AlkaneId::new(2, 7), 3);
+        assert_eq!(Revert::decode(&revert.encode()), Some(revert));
+    }
+
+    #[test]
+    fn test_decode_legacy_revert() {
+        let mut data = REVERT_SELECTOR.to_vec();
+        data.extend(b"Error: failed");
+        let revert = Revert::decode(&data).unwrap();
+        assert_eq!(revert.code, REVERT_CODE_CONTRACT);
+        assert_eq!(revert.message, "Error: failed");
+        assert_eq!(Revert::decode(&[1, 2, 3, 4, 5]), None);
+    }
+
+    #[test]
+    fn test_legacy_encoding() {
+        let revert = Revert::new(REVERT_CODE_VM, "out of fuel", AlkaneId::new(2, 7), 3);
+        let data = revert.encode_legacy();
+        assert_eq!(data[..4], REVERT_SELECTOR);
+        assert_eq!(Revert::decode_legacy(&data).unwrap().message, "out of fuel");
+        assert_eq!(Revert::decode_legacy(&revert.encode()), None);
+        let mut data = REVERT_SELECTOR.to_vec();
+        data.push(0xff);
+        assert_eq!(Revert::decode_legacy(&data).unwrap().message, "ff");
+    }
+}
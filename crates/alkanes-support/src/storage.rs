@@ -36,6 +36,16 @@ impl StorageMap {
     pub fn set<KT: AsRef<[u8]>, VT: AsRef<[u8]>>(&mut self, k: KT, v: VT) {
         self.0.insert(k.as_ref().to_vec(), v.as_ref().to_vec());
     }
+    /// Deleting a key writes an empty value, which the indexer treats as absent.
+    pub fn delete<T: AsRef<[u8]>>(&mut self, k: T) {
+        self.0.insert(k.as_ref().to_vec(), vec![]);
+    }
+    pub fn keys_with_prefix<'a>(&'a self, prefix: &'a [u8]) -> impl Iterator<Item = &'a Vec<u8>> {
+        self.0
+            .iter()
+            .filter(move |(k, v)| !v.is_empty() && k.starts_with(prefix))
+            .map(|(k, _)| k)
+    }
     pub fn serialize(&self) -> Vec<u8> {
         let mut buffer = Vec::<u8>::new();
         let size = self.0.len() as u32;
@@ -51,3 +61,37 @@ impl StorageMap {
         buffer
     }
 }
+
+/// One page of keys from a contract's storage key index. `next` holds the
+/// offset to request the following page from, if there is one.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct StorageKeyPage {
+    pub keys: Vec<Vec<u8>>,
+    pub next: Option<u32>,
+}
+
+impl StorageKeyPage {
+    pub fn parse(cursor: &mut Cursor<Vec<u8>>) -> Result<StorageKeyPage> {
+        let next = match consume_sized_int::<u32>(cursor)? {
+            u32::MAX => None,
+            v => Some(v),
+        };
+        let len = consume_sized_int::<u32>(cursor)?;
+        let mut keys = Vec::<Vec<u8>>::new();
+        for _i in 0..len {
+            let key_length: usize = consume_sized_int::<u32>(cursor)?.try_into()?;
+            keys.push(consume_exact(cursor, key_length)?);
+        }
+        Ok(StorageKeyPage { keys, next })
+    }
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::<u8>::new();
+        buffer.extend(&self.next.unwrap_or(u32::MAX).to_le_bytes());
+        buffer.extend(&(self.keys.len() as u32).to_le_bytes());
+        for k in self.keys.iter() {
+            buffer.extend(&(k.len() as u32).to_le_bytes());
+            buffer.extend(k);
+        }
+        buffer
+    }
+}
@@ -0,0 +1,77 @@
+use crate::id::AlkaneId;
+use crate::proto;
+use crate::trace::TraceEvent;
+use protobuf::MessageField;
+
+/// Fuel accounting for one call frame, rebuilt from the trace of a message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuelFrame {
+    pub target: AlkaneId,
+    pub opcode: u128,
+    /// fuel consumed by this frame alone
+    pub fuel_self: u64,
+    /// fuel consumed by this frame and every call it made
+    pub fuel_total: u64,
+    pub storage_bytes: u64,
+    pub reverted: bool,
+    pub calls: Vec<FuelFrame>,
+}
+
+impl FuelFrame {
+    /// Builds the call tree from a trace, returning the outermost frame. Frames left open
+    /// at the end of the trace are closed with the fuel of their completed children.
+    pub fn from_trace(events: &[TraceEvent]) -> Option<FuelFrame> {
+        let mut stack: Vec<FuelFrame> = vec![];
+        let mut root: Option<FuelFrame> = None;
+        for event in events {
+            match event {
+                TraceEvent::EnterCall(context)
+                | TraceEvent::EnterStaticcall(context)
+                | TraceEvent::EnterDelegatecall(context) => stack.push(FuelFrame {
+                    target: context.inner.myself.clone(),
+                    opcode: context.inner.inputs.first().cloned().unwrap_or_default(),
+                    ..Default::default()
+                }),
+                TraceEvent::ReturnContext(response) | TraceEvent::RevertContext(response) => {
+                    let Some(mut frame) = stack.pop() else {
+                        continue;
+                    };
+                    frame.reverted = matches!(event, TraceEvent::RevertContext(_));
+                    frame.fuel_total = response.fuel_used;
+                    if !frame.reverted {
+                        frame.storage_bytes = response.inner.storage.serialize().len() as u64;
+                    }
+                    Self::close(frame, &mut stack, &mut root);
+                }
+                _ => {}
+            }
+        }
+        while let Some(mut frame) = stack.pop() {
+            frame.fuel_total = frame.calls.iter().map(|call| call.fuel_total).sum();
+            Self::close(frame, &mut stack, &mut root);
+        }
+        root
+    }
+    fn close(mut frame: FuelFrame, stack: &mut Vec<FuelFrame>, root: &mut Option<FuelFrame>) {
+        let children: u64 = frame.calls.iter().map(|call| call.fuel_total).sum();
+        frame.fuel_self = frame.fuel_total.saturating_sub(children);
+        match stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => *root = Some(frame),
+        }
+    }
+}
+
+impl Into<proto::alkanes::CallFrameFuel> for FuelFrame {
+    fn into(self) -> proto::alkanes::CallFrameFuel {
+        let mut result = proto::alkanes::CallFrameFuel::new();
+        result.target = MessageField::some(self.target.into());
+        result.opcode = MessageField::some(self.opcode.into());
+        result.fuel_self = self.fuel_self;
+        result.fuel_total = self.fuel_total;
+        result.storage_bytes = self.storage_bytes;
+        result.reverted = self.reverted;
+        result.calls = self.calls.into_iter().map(|call| call.into()).collect();
+        result
+    }
+}
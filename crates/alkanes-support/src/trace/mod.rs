@@ -2,3 +2,5 @@ pub mod types;
 pub use types::*;
 pub mod block;
 pub use block::*;
+pub mod fuel;
+pub use fuel::*;
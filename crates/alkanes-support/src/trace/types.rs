@@ -3,6 +3,7 @@ use crate::id::AlkaneId;
 use crate::parcel::{AlkaneTransfer, AlkaneTransferParcel};
 use crate::proto;
 use crate::response::ExtendedCallResponse;
+use crate::revert::Revert;
 use crate::utils::field_or_default;
 use protobuf::{Message, MessageField};
 use std::sync::{Arc, Mutex};
@@ -12,6 +13,8 @@ pub struct TraceContext {
     pub inner: Context,
     pub target: AlkaneId,
     pub fuel: u64,
+    /// fuel limit the sender asked for in the protostone, if any
+    pub requested_fuel: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -20,6 +23,26 @@ pub struct TraceResponse {
     pub fuel_used: u64,
 }
 
+impl TraceResponse {
+    pub fn from_revert(revert: &Revert, fuel_used: u64) -> Self {
+        let mut response = Self::default();
+        response.inner.data = revert.encode();
+        response.fuel_used = fuel_used;
+        response
+    }
+    /// The typed revert carried by a `RevertContext`, if the data holds one.
+    pub fn revert(&self) -> Option<Revert> {
+        Revert::decode(&self.inner.data)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TraceEmit {
+    pub alkane: AlkaneId,
+    pub topic: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
 #[derive(Debug, Clone)]
 pub enum TraceEvent {
     EnterDelegatecall(TraceContext),
@@ -28,6 +51,7 @@ pub enum TraceEvent {
     RevertContext(TraceResponse),
     ReturnContext(TraceResponse),
     CreateAlkane(AlkaneId),
+    Emit(TraceEmit),
 }
 
 impl Into<TraceResponse> for ExtendedCallResponse {
@@ -46,6 +70,7 @@ impl Into<TraceContext> for Context {
             inner: self,
             target,
             fuel: 0,
+            requested_fuel: None,
         }
     }
 }
@@ -84,6 +109,7 @@ impl Into<proto::alkanes::TraceContext> for TraceContext {
         let mut result = proto::alkanes::TraceContext::new();
         result.inner = MessageField::some(self.inner.into());
         result.fuel = self.fuel;
+        result.requested_fuel = self.requested_fuel.unwrap_or_default();
         result
     }
 }
@@ -96,6 +122,16 @@ impl Into<proto::alkanes::AlkanesEnterContext> for TraceContext {
     }
 }
 
+impl Into<proto::alkanes::AlkanesEmit> for TraceEmit {
+    fn into(self) -> proto::alkanes::AlkanesEmit {
+        let mut result = proto::alkanes::AlkanesEmit::new();
+        result.alkane = MessageField::some(self.alkane.into());
+        result.topic = self.topic;
+        result.data = self.data;
+        result
+    }
+}
+
 impl Into<proto::alkanes::AlkanesTraceEvent> for TraceEvent {
     fn into(self) -> proto::alkanes::AlkanesTraceEvent {
         let mut result = proto::alkanes::AlkanesTraceEvent::new();
@@ -130,6 +166,7 @@ impl Into<proto::alkanes::AlkanesTraceEvent> for TraceEvent {
                 creation.new_alkane = MessageField::some(v.into());
                 proto::alkanes::alkanes_trace_event::Event::CreateAlkane(creation)
             }
+            TraceEvent::Emit(v) => proto::alkanes::alkanes_trace_event::Event::Emit(v.into()),
         });
         result
     }
@@ -210,6 +247,10 @@ impl From<proto::alkanes::TraceContext> for TraceContext {
                 .unwrap_or_else(|_| Context::default()),
             fuel: v.fuel.into(),
             target: AlkaneId::default(),
+            requested_fuel: match v.requested_fuel {
+                0 => None,
+                requested => Some(requested),
+            },
         }
     }
 }
@@ -227,6 +268,16 @@ impl From<proto::alkanes::AlkanesEnterContext> for TraceContext {
     }
 }
 
+impl From<proto::alkanes::AlkanesEmit> for TraceEmit {
+    fn from(v: proto::alkanes::AlkanesEmit) -> TraceEmit {
+        TraceEmit {
+            alkane: field_or_default(v.alkane),
+            topic: v.topic,
+            data: v.data,
+        }
+    }
+}
+
 impl From<proto::alkanes::AlkanesTraceEvent> for TraceEvent {
     fn from(v: proto::alkanes::AlkanesTraceEvent) -> Self {
         if v.event.is_some() {
@@ -249,6 +300,7 @@ impl From<proto::alkanes::AlkanesTraceEvent> for TraceEvent {
                 proto::alkanes::alkanes_trace_event::Event::CreateAlkane(v) => {
                     TraceEvent::CreateAlkane(field_or_default(v.new_alkane))
                 }
+                proto::alkanes::alkanes_trace_event::Event::Emit(v) => TraceEvent::Emit(v.into()),
             }
         } else {
             TraceEvent::CreateAlkane(AlkaneId { block: 0, tx: 0 })
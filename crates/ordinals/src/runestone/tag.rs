@@ -29,6 +29,8 @@ pub enum Tag {
     Refund = 93,
     ProtoPointer = 91,
     From = 95,
+    FuelLimit = 97,
+    AllOrNothing = 99,
 }
 
 impl Tag {
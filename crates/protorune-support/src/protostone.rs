@@ -152,6 +152,10 @@ pub struct Protostone {
     pub refund: Option<u32>,
     pub pointer: Option<u32>,
     pub from: Option<u32>,
+    /// upper bound on the fuel a protocol message may spend, if the sender set one
+    pub fuel_limit: Option<u64>,
+    /// when set, every protocol message in the transaction reverts if any one of them fails
+    pub all_or_nothing: bool,
     pub protocol_tag: u128,
 }
 
@@ -234,6 +238,14 @@ impl Protostone {
             payload.push(Tag::From.into());
             payload.push((*from).into());
         }
+        if let Some(fuel_limit) = self.fuel_limit {
+            payload.push(Tag::FuelLimit.into());
+            payload.push(fuel_limit.into());
+        }
+        if self.all_or_nothing {
+            payload.push(Tag::AllOrNothing.into());
+            payload.push(1);
+        }
         if !self.message.is_empty() {
             for item in split_bytes(&self.message) {
                 payload.push(Tag::Message.into());
@@ -272,6 +284,13 @@ impl Protostone {
             pointer: map.get(&Tag::ProtoPointer.into()).map(|v| v[0] as u32),
             protocol_tag,
             from: map.get(&Tag::From.into()).map(|v| v[0] as u32),
+            fuel_limit: map
+                .get(&Tag::FuelLimit.into())
+                .map(|v| u64::try_from(v[0]).unwrap_or(u64::MAX)),
+            all_or_nothing: map
+                .get(&Tag::AllOrNothing.into())
+                .map(|v| v[0] != 0)
+                .unwrap_or(false),
             edicts: map
                 .get(&0u128)
                 .map(|list| -> Result<Vec<ProtostoneEdict>> {
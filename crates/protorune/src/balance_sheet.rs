@@ -1,3 +1,4 @@
+use crate::journal;
 use anyhow::{anyhow, Result};
 use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
 use metashrew_support::index_pointer::KeyValuePointer;
@@ -24,12 +25,13 @@ pub trait PersistentRecord: BalanceSheetOperations {
         for (rune, balance) in self.balances() {
             if *balance != 0u128 && !is_cenotaph {
                 let rune_bytes: Vec<u8> = (*rune).into();
+                journal::record_append(&runes_ptr);
                 runes_ptr.append(rune_bytes.clone().into());
 
+                journal::record_append(&balances_ptr);
                 balances_ptr.append_value::<u128>(*balance);
 
-                runes_to_balances_ptr
-                    .select(&rune_bytes)
+                journal::written(runes_to_balances_ptr.select(&rune_bytes))
                     .set_value::<u128>(*balance);
             }
         }
@@ -49,11 +51,11 @@ pub trait PersistentRecord: BalanceSheetOperations {
             .ok_or(anyhow!("no balance found"))?;
         if *balance != 0u128 && !is_cenotaph {
             let rune_bytes: Vec<u8> = (*rune).into();
+            journal::record_append(&runes_ptr);
             runes_ptr.append(rune_bytes.clone().into());
+            journal::record_append(&balances_ptr);
             balances_ptr.append_value::<u128>(*balance);
-            runes_to_balances_ptr
-                .select(&rune_bytes)
-                .set_value::<u128>(*balance);
+            journal::written(runes_to_balances_ptr.select(&rune_bytes)).set_value::<u128>(*balance);
         }
 
         Ok(())
@@ -188,9 +190,9 @@ pub fn clear_balances<T: KeyValuePointer>(ptr: &T) {
     let runes_to_balances_ptr = ptr.keyword("/id_to_balance");
 
     for i in 0..length {
-        balances_ptr.select_index(i).set_value::<u128>(0);
+        journal::written(balances_ptr.select_index(i)).set_value::<u128>(0);
         let rune = balances_ptr.select_index(i).get();
-        runes_to_balances_ptr.select(&rune).set_value::<u128>(0);
+        journal::written(runes_to_balances_ptr.select(&rune)).set_value::<u128>(0);
     }
 }
 
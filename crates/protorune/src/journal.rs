@@ -0,0 +1,88 @@
+use metashrew_core::index_pointer::IndexPointer;
+use metashrew_support::index_pointer::KeyValuePointer;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/*
+The write journal keeps, for every key written while it is open, the value the key held
+before its first write. Every write to the index goes through `record` (or `record_append`
+for lists) just before it is made, so the journal of a block is exactly what undoing the
+block must put back, found without reading anything the block did not write.
+
+Values are read from the committed index, so a key written through an AtomicPointer is
+recorded with the value it had before the block even if an earlier, rolled back checkpoint
+wrote it too.
+*/
+
+static JOURNAL: Lazy<Mutex<Option<BTreeMap<Vec<u8>, Vec<u8>>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts recording, discarding anything recorded before.
+pub fn open() {
+    *JOURNAL.lock().unwrap() = Some(BTreeMap::new());
+}
+
+/// Stops recording and returns the prior value of every key written since `open`.
+pub fn close() -> BTreeMap<Vec<u8>, Vec<u8>> {
+    JOURNAL.lock().unwrap().take().unwrap_or_default()
+}
+
+/// Records the value `pointer` holds before it is written, unless the journal is closed or
+/// already has it.
+pub fn record<T: KeyValuePointer>(pointer: &T) {
+    if let Some(journal) = JOURNAL.lock().unwrap().as_mut() {
+        let key = pointer.unwrap().as_ref().clone();
+        if !journal.contains_key(&key) {
+            let prior = IndexPointer::wrap(&key).get().as_ref().clone();
+            journal.insert(key, prior);
+        }
+    }
+}
+
+/// Records the length of the list at `pointer` and the slot the next append fills.
+pub fn record_append<T: KeyValuePointer>(pointer: &T) {
+    record(&pointer.length_key());
+    record(&pointer.select_index(pointer.length()));
+}
+
+/// Records the keys `append_ll` writes: those of `record_append` and the link from the
+/// current tail to the new slot.
+pub fn record_append_ll<T: KeyValuePointer>(pointer: &T) {
+    let length = pointer.length();
+    if length > 0 {
+        record(&pointer.next_key(length - 1));
+    }
+    record_append(pointer);
+}
+
+/// Records the keys `delete_value(index)` may write: the slot, the head, and the links walked
+/// from the head to the slot, one of which is relinked past it.
+pub fn record_delete_ll<T: KeyValuePointer>(pointer: &T, index: u32) {
+    record(&pointer.select_index(index));
+    record(&pointer.next_key(index));
+    let head_key = pointer.head_key();
+    record(&head_key);
+    let mut node = head_key.get_value::<u32>();
+    for _ in 0..pointer.length() {
+        let next_key = pointer.next_key(node);
+        record(&next_key);
+        let next = next_key.get_value::<u32>();
+        if next == index || next == 0 {
+            break;
+        }
+        node = next;
+    }
+}
+
+/// Records `pointer` as `record` does and hands it back, for a pointer written right away.
+pub fn written<T: KeyValuePointer>(pointer: T) -> T {
+    record(&pointer);
+    pointer
+}
+
+/// Records the list at `pointer` as `record_append` does and hands it back, for a list
+/// appended to right away.
+pub fn appended<T: KeyValuePointer>(pointer: T) -> T {
+    record_append(&pointer);
+    pointer
+}
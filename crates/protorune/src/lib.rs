@@ -29,6 +29,7 @@ use protorune_support::proto;
 use protorune_support::{
     balance_sheet::{BalanceSheet, ProtoruneRuneId},
     protostone::{into_protostone_edicts, Protostone, ProtostoneEdict},
+    rune_transfer::refund_to_refund_pointer,
     utils::{consensus_encode, field_to_name, outpoint_encode, tx_hex_to_txid},
 };
 use std::collections::{HashMap, HashSet};
@@ -40,6 +41,7 @@ const BLACKLISTED_TX_HASHES: [&str; 1] =
     ["5cbb0c466dd08d7af9223d45105fbbf0fdc9fb7cda4831c183d6b0cb5ba60fb0"];
 
 pub mod balance_sheet;
+pub mod journal;
 pub mod message;
 pub mod protoburn;
 pub mod protorune_init;
@@ -393,9 +395,7 @@ impl Protorune {
                 && (offset_start == 0 || height >= offset_start + etching_height)
                 && (offset_end == 0 || height < etching_height + offset_end)
             {
-                tables::RUNES
-                    .MINTS_REMAINING
-                    .select(&name)
+                journal::written(tables::RUNES.MINTS_REMAINING.select(&name))
                     .set_value(remaining.sub(1));
                 balance_sheet.increase(
                     &(ProtoruneRuneId {
@@ -452,24 +452,21 @@ impl Protorune {
             return Ok(());
         }
         let rune_id = ProtoruneRuneId::new(height.into(), index.into());
-        atomic
-            .derive(&tables::RUNES.RUNE_ID_TO_ETCHING.select(&rune_id.into()))
+        journal::written(atomic.derive(&tables::RUNES.RUNE_ID_TO_ETCHING.select(&rune_id.into())))
             .set(Arc::new(indexer_rune_name.clone()));
-        atomic
-            .derive(&tables::RUNES.ETCHING_TO_RUNE_ID.select(&indexer_rune_name))
-            .set(rune_id.into());
-        atomic
-            .derive(&tables::RUNES.RUNE_ID_TO_HEIGHT.select(&rune_id.into()))
+        journal::written(
+            atomic.derive(&tables::RUNES.ETCHING_TO_RUNE_ID.select(&indexer_rune_name)),
+        )
+        .set(rune_id.into());
+        journal::written(atomic.derive(&tables::RUNES.RUNE_ID_TO_HEIGHT.select(&rune_id.into())))
             .set_value(height);
 
         if let Some(divisibility) = etching.divisibility {
-            atomic
-                .derive(&tables::RUNES.DIVISIBILITY.select(&indexer_rune_name))
+            journal::written(atomic.derive(&tables::RUNES.DIVISIBILITY.select(&indexer_rune_name)))
                 .set_value(divisibility);
         }
         if let Some(premine) = etching.premine {
-            atomic
-                .derive(&tables::RUNES.PREMINE.select(&indexer_rune_name))
+            journal::written(atomic.derive(&tables::RUNES.PREMINE.select(&indexer_rune_name)))
                 .set_value(premine);
             let rune = ProtoruneRuneId {
                 block: u128::from(height),
@@ -481,55 +478,54 @@ impl Protorune {
         }
         if let Some(terms) = etching.terms {
             if let Some(amount) = terms.amount {
-                atomic
-                    .derive(&tables::RUNES.AMOUNT.select(&indexer_rune_name))
+                journal::written(atomic.derive(&tables::RUNES.AMOUNT.select(&indexer_rune_name)))
                     .set_value(amount);
             }
             if let Some(cap) = terms.cap {
-                atomic
-                    .derive(&tables::RUNES.CAP.select(&indexer_rune_name))
-                    .set_value(cap);
-                atomic
-                    .derive(&tables::RUNES.MINTS_REMAINING.select(&indexer_rune_name))
+                journal::written(atomic.derive(&tables::RUNES.CAP.select(&indexer_rune_name)))
                     .set_value(cap);
+                journal::written(
+                    atomic.derive(&tables::RUNES.MINTS_REMAINING.select(&indexer_rune_name)),
+                )
+                .set_value(cap);
             }
             if let (Some(height_start), Some(height_end)) = (terms.height.0, terms.height.1) {
-                atomic
-                    .derive(&tables::RUNES.HEIGHTSTART.select(&indexer_rune_name))
-                    .set_value(height_start);
-
-                atomic
-                    .derive(&tables::RUNES.HEIGHTEND.select(&indexer_rune_name))
-                    .set_value(height_end);
+                journal::written(
+                    atomic.derive(&tables::RUNES.HEIGHTSTART.select(&indexer_rune_name)),
+                )
+                .set_value(height_start);
+
+                journal::written(
+                    atomic.derive(&tables::RUNES.HEIGHTEND.select(&indexer_rune_name)),
+                )
+                .set_value(height_end);
             }
             if let (Some(offset_start), Some(offset_end)) = (terms.offset.0, terms.offset.1) {
-                atomic
-                    .derive(&tables::RUNES.OFFSETSTART.select(&indexer_rune_name))
-                    .set_value(offset_start);
-                atomic
-                    .derive(&tables::RUNES.OFFSETEND.select(&indexer_rune_name))
-                    .set_value(offset_end);
+                journal::written(
+                    atomic.derive(&tables::RUNES.OFFSETSTART.select(&indexer_rune_name)),
+                )
+                .set_value(offset_start);
+                journal::written(
+                    atomic.derive(&tables::RUNES.OFFSETEND.select(&indexer_rune_name)),
+                )
+                .set_value(offset_end);
             }
         }
 
         // runes spec states this is the default symbol if symbol is omitted
         let symbol = etching.symbol.unwrap_or('¤');
-        atomic
-            .derive(&tables::RUNES.SYMBOL.select(&indexer_rune_name))
+        journal::written(atomic.derive(&tables::RUNES.SYMBOL.select(&indexer_rune_name)))
             .set_value(symbol as u32);
 
         if let Some(spacers) = etching.spacers {
-            atomic
-                .derive(&tables::RUNES.SPACERS.select(&indexer_rune_name))
+            journal::written(atomic.derive(&tables::RUNES.SPACERS.select(&indexer_rune_name)))
                 .set_value(spacers);
         }
 
-        atomic
-            .derive(&tables::RUNES.ETCHINGS.select(&indexer_rune_name))
+        journal::appended(atomic.derive(&tables::RUNES.ETCHINGS.select(&indexer_rune_name)))
             .append(Arc::new(indexer_rune_name.clone()));
 
-        atomic
-            .derive(&tables::HEIGHT_TO_RUNES.select_value(height))
+        journal::appended(atomic.derive(&tables::HEIGHT_TO_RUNES.select_value(height)))
             .append(Arc::new(indexer_rune_name.clone()));
 
         Ok(())
@@ -576,35 +572,50 @@ impl Protorune {
 
     pub fn index_unspendables<T: MessageContext>(block: &Block, height: u64) -> Result<()> {
         for (index, tx) in block.txdata.iter().enumerate() {
-            if let Some(Artifact::Runestone(ref runestone)) = Runestone::decipher(tx) {
-                let mut atomic = AtomicPointer::default();
-                let runestone_output_index: u32 = Self::get_runestone_output_index(tx)?;
-                match Self::index_runestone::<T>(
-                    &mut atomic,
-                    tx,
-                    runestone,
-                    height,
-                    index as u32,
-                    block,
-                    runestone_output_index,
-                ) {
-                    Err(e) => {
-                        println!("err: {:?}", e);
-                        atomic.rollback();
-                    }
-                    _ => {
-                        atomic.commit();
-                    }
-                };
-            }
-            for input in &tx.input {
-                //all inputs must be used up, even in cenotaphs
-                let key = consensus_encode(&input.previous_output)?;
-                clear_balances(&mut tables::RUNES.OUTPOINT_TO_RUNES.select(&key));
+            if let Some(e) = Self::index_unspendable::<T>(block, height, index as u32, tx)? {
+                println!("err: {:?}", e);
             }
         }
         Ok(())
     }
+    /// Indexes the runestone of the transaction at `index` in `block`, rolling back its
+    /// writes if it fails, and clears the balances of the outpoints it spends. The error of
+    /// a failed runestone is returned rather than raised, as it does not fail the block.
+    pub fn index_unspendable<T: MessageContext>(
+        block: &Block,
+        height: u64,
+        index: u32,
+        tx: &Transaction,
+    ) -> Result<Option<anyhow::Error>> {
+        let mut failure = None;
+        if let Some(Artifact::Runestone(ref runestone)) = Runestone::decipher(tx) {
+            let mut atomic = AtomicPointer::default();
+            let runestone_output_index: u32 = Self::get_runestone_output_index(tx)?;
+            match Self::index_runestone::<T>(
+                &mut atomic,
+                tx,
+                runestone,
+                height,
+                index,
+                block,
+                runestone_output_index,
+            ) {
+                Err(e) => {
+                    atomic.rollback();
+                    failure = Some(e);
+                }
+                _ => {
+                    atomic.commit();
+                }
+            };
+        }
+        for input in &tx.input {
+            //all inputs must be used up, even in cenotaphs
+            let key = consensus_encode(&input.previous_output)?;
+            clear_balances(&mut tables::RUNES.OUTPOINT_TO_RUNES.select(&key));
+        }
+        Ok(failure)
+    }
     pub fn index_spendables(txdata: &Vec<Transaction>) -> Result<HashSet<Vec<u8>>> {
         // Track unique addresses that have their spendable outpoints updated
         #[cfg(feature = "cache")]
@@ -615,14 +626,18 @@ impl Protorune {
 
         for (txindex, transaction) in txdata.iter().enumerate() {
             let tx_id = transaction.compute_txid();
-            tables::RUNES
-                .TXID_TO_TXINDEX
-                .select(&tx_id.as_byte_array().to_vec())
-                .set_value(txindex as u32);
+            journal::written(
+                tables::RUNES
+                    .TXID_TO_TXINDEX
+                    .select(&tx_id.as_byte_array().to_vec()),
+            )
+            .set_value(txindex as u32);
             for (_index, input) in transaction.input.iter().enumerate() {
-                tables::OUTPOINT_SPENDABLE_BY
-                    .select(&consensus_encode(&input.previous_output)?)
-                    .nullify();
+                journal::written(
+                    tables::OUTPOINT_SPENDABLE_BY
+                        .select(&consensus_encode(&input.previous_output)?),
+                )
+                .nullify();
             }
             for (index, output) in transaction.output.iter().enumerate() {
                 let outpoint = OutPoint {
@@ -639,11 +654,9 @@ impl Protorune {
                     #[cfg(feature = "cache")]
                     updated_addresses.insert(address.to_vec());
 
-                    tables::OUTPOINTS_FOR_ADDRESS
-                        .select(&address.clone())
+                    journal::appended(tables::OUTPOINTS_FOR_ADDRESS.select(&address.clone()))
                         .append(Arc::new(outpoint_bytes.clone()));
-                    tables::OUTPOINT_SPENDABLE_BY
-                        .select(&outpoint_bytes.clone())
+                    journal::written(tables::OUTPOINT_SPENDABLE_BY.select(&outpoint_bytes.clone()))
                         .set(Arc::new(address.clone()))
                 }
             }
@@ -662,10 +675,12 @@ impl Protorune {
 
         for (txindex, transaction) in txdata.iter().enumerate() {
             let tx_id = transaction.compute_txid();
-            tables::RUNES
-                .TXID_TO_TXINDEX
-                .select(&tx_id.as_byte_array().to_vec())
-                .set_value(txindex as u32);
+            journal::written(
+                tables::RUNES
+                    .TXID_TO_TXINDEX
+                    .select(&tx_id.as_byte_array().to_vec()),
+            )
+            .set_value(txindex as u32);
             for (index, output) in transaction.output.iter().enumerate() {
                 let outpoint = OutPoint {
                     txid: tx_id.clone(),
@@ -683,23 +698,22 @@ impl Protorune {
                         updated_addresses.insert(address.to_vec());
                     }
 
-                    tables::OUTPOINTS_FOR_ADDRESS
-                        .select(&address.clone())
+                    journal::appended(tables::OUTPOINTS_FOR_ADDRESS.select(&address.clone()))
                         .append(Arc::new(outpoint_bytes.clone()));
                     if address.len() > 0 {
-                        tables::OUTPOINT_SPENDABLE_BY_ADDRESS
-                            .select(&address.clone())
-                            .append_ll(Arc::new(outpoint_bytes.clone()));
+                        let list = tables::OUTPOINT_SPENDABLE_BY_ADDRESS.select(&address.clone());
+                        journal::record_append_ll(&list);
+                        list.append_ll(Arc::new(outpoint_bytes.clone()));
                         let pos = tables::OUTPOINT_SPENDABLE_BY_ADDRESS
                             .select(&address.clone())
                             .length()
                             - 1;
-                        tables::OUTPOINT_SPENDABLE_BY_ADDRESS
-                            .select(&outpoint_bytes.clone())
-                            .set_value(pos);
+                        journal::written(
+                            tables::OUTPOINT_SPENDABLE_BY_ADDRESS.select(&outpoint_bytes.clone()),
+                        )
+                        .set_value(pos);
                     }
-                    tables::OUTPOINT_SPENDABLE_BY
-                        .select(&outpoint_bytes.clone())
+                    journal::written(tables::OUTPOINT_SPENDABLE_BY.select(&outpoint_bytes.clone()))
                         .set(Arc::new(address.clone()))
                 }
             }
@@ -714,13 +728,14 @@ impl Protorune {
                     #[cfg(feature = "cache")]
                     updated_addresses.insert(address.as_ref().to_vec());
 
-                    tables::OUTPOINT_SPENDABLE_BY_ADDRESS
-                        .select(&address)
-                        .delete_value(pos);
+                    let list = tables::OUTPOINT_SPENDABLE_BY_ADDRESS.select(&address);
+                    journal::record_delete_ll(&list, pos);
+                    list.delete_value(pos);
                     if pos > 0 {
-                        tables::OUTPOINT_SPENDABLE_BY_ADDRESS
-                            .select(&outpoint_bytes)
-                            .nullify();
+                        journal::written(
+                            tables::OUTPOINT_SPENDABLE_BY_ADDRESS.select(&outpoint_bytes),
+                        )
+                        .nullify();
                     }
                 }
             }
@@ -735,6 +750,7 @@ impl Protorune {
             .HEIGHT_TO_TRANSACTION_IDS
             .select_value::<u64>(height);
         for tx in &block.txdata {
+            journal::record_append(&ptr);
             ptr.append(Arc::new(tx.compute_txid().as_byte_array().to_vec()));
         }
         Ok(())
@@ -749,19 +765,21 @@ impl Protorune {
                         vout: i as u32,
                     }),
                 )?;
-                atomic
-                    .derive(&tables::RUNES.OUTPOINT_TO_HEIGHT.select(&outpoint_bytes))
-                    .set_value(height);
-                atomic
-                    .derive(&tables::OUTPOINT_TO_OUTPUT.select(&outpoint_bytes))
-                    .set(Arc::new(
-                        (proto::protorune::Output {
-                            script: tx.output[i].clone().script_pubkey.into_bytes(),
-                            value: tx.output[i].clone().value.to_sat(),
-                            special_fields: SpecialFields::new(),
-                        })
-                        .write_to_bytes()?,
-                    ));
+                journal::written(
+                    atomic.derive(&tables::RUNES.OUTPOINT_TO_HEIGHT.select(&outpoint_bytes)),
+                )
+                .set_value(height);
+                journal::written(
+                    atomic.derive(&tables::OUTPOINT_TO_OUTPUT.select(&outpoint_bytes)),
+                )
+                .set(Arc::new(
+                    (proto::protorune::Output {
+                        script: tx.output[i].clone().script_pubkey.into_bytes(),
+                        value: tx.output[i].clone().value.to_sat(),
+                        special_fields: SpecialFields::new(),
+                    })
+                    .write_to_bytes()?,
+                ));
             }
         }
         atomic.commit();
@@ -890,6 +908,31 @@ impl Protorune {
             )?;
 
             let num_protostones = protostones.len();
+            // an all-or-nothing transaction runs every protostone under one checkpoint, so a
+            // failing message undoes the ones before it and the set is refunded as a whole
+            let all_or_nothing = T::all_or_nothing_active(height)
+                && protostones
+                    .iter()
+                    .any(|stone| stone.protocol_tag == T::protocol_tag() && stone.all_or_nothing);
+            let message_vouts: Vec<u32> = protostones
+                .iter()
+                .enumerate()
+                .filter(|(_, stone)| stone.protocol_tag == T::protocol_tag() && stone.is_message())
+                .map(|(i, _)| (i as u32) + (tx.output.len() as u32) + 1)
+                .collect();
+            let refunds: Vec<(u32, u32)> = protostones
+                .iter()
+                .enumerate()
+                .map(|(i, stone)| {
+                    (
+                        (i as u32) + (tx.output.len() as u32) + 1,
+                        stone
+                            .refund
+                            .or(stone.pointer)
+                            .unwrap_or_else(|| default_output(tx)),
+                    )
+                })
+                .collect();
             let protostones_iter = protostones.into_iter();
             // by default, all protorunes that come in as input will be given to the
             // first protostone with a matching protocol_tag
@@ -903,9 +946,20 @@ impl Protorune {
                     (tx.output.len() as u32) + 1 + position as u32,
                 )?;
             }
-            protostones_iter
+            let snapshot = match all_or_nothing {
+                true => Some(proto_balances_by_output.clone()),
+                false => None,
+            };
+            let mut failed = false;
+            if all_or_nothing {
+                atomic.checkpoint();
+            }
+            let result = protostones_iter
                 .enumerate()
                 .map(|(i, stone)| {
+                    if failed {
+                        return Ok(());
+                    }
                     let shadow_vout = (i as u32) + (tx.output.len() as u32) + 1;
                     if !proto_balances_by_output.contains_key(&shadow_vout) {
                         proto_balances_by_output.insert(shadow_vout, BalanceSheet::default());
@@ -932,6 +986,10 @@ impl Protorune {
                             num_protostones,
                         )?;
                         did_message_fail_and_refund = !success;
+                        if all_or_nothing && !success {
+                            failed = true;
+                            return Ok(());
+                        }
                         if success {
                             // Get the post-message balance to use for edicts
                             prior_balance_sheet =
@@ -967,7 +1025,24 @@ impl Protorune {
 
                     Ok(())
                 })
-                .collect::<Result<()>>()?;
+                .collect::<Result<()>>();
+            if let Some(snapshot) = snapshot {
+                if failed || result.is_err() {
+                    atomic.rollback();
+                    T::rollback_messages(tx, height, &message_vouts)?;
+                    proto_balances_by_output = snapshot;
+                    for (shadow_vout, refund_pointer) in refunds {
+                        refund_to_refund_pointer(
+                            &mut proto_balances_by_output,
+                            shadow_vout,
+                            refund_pointer,
+                        )?;
+                    }
+                } else {
+                    atomic.commit();
+                }
+            }
+            result?;
             Self::save_balances::<T>(
                 height,
                 &mut atomic.derive(&IndexPointer::default()),
@@ -990,14 +1065,18 @@ impl Protorune {
             add_to_indexable_protocols(T::protocol_tag()).map_err(|e| anyhow!(e.to_string()));
         init_result?;
         add_result?;
-        tables::RUNES
-            .HEIGHT_TO_BLOCKHASH
-            .select_value::<u64>(height)
-            .set(Arc::new(consensus_encode(&block.block_hash())?));
-        tables::RUNES
-            .BLOCKHASH_TO_HEIGHT
-            .select(&consensus_encode(&block.block_hash())?)
-            .set_value::<u64>(height);
+        journal::written(
+            tables::RUNES
+                .HEIGHT_TO_BLOCKHASH
+                .select_value::<u64>(height),
+        )
+        .set(Arc::new(consensus_encode(&block.block_hash())?));
+        journal::written(
+            tables::RUNES
+                .BLOCKHASH_TO_HEIGHT
+                .select(&consensus_encode(&block.block_hash())?),
+        )
+        .set_value::<u64>(height);
         Self::index_transaction_ids(&block, height)?;
         Self::index_outpoints(&block, height)?;
 
@@ -13,6 +13,15 @@ pub trait MessageContext {
         parcel: &MessageContextParcel,
     ) -> Result<(Vec<RuneTransfer>, BalanceSheet<AtomicPointer>)>;
     fn protocol_tag() -> u128;
+    /// Whether the all-or-nothing flag of a protostone is honoured at `height`.
+    fn all_or_nothing_active(_height: u64) -> bool {
+        true
+    }
+    /// Called once an all-or-nothing transaction is rolled back, with the vouts of the
+    /// messages it ran, so that records kept outside the atomic can be marked reverted.
+    fn rollback_messages(_tx: &Transaction, _height: u64, _vouts: &[u32]) -> Result<()> {
+        Ok(())
+    }
     fn asset_protoburned_in_protocol(id: ProtoruneRuneId) -> bool {
         let table = RuneTable::for_protocol(Self::protocol_tag());
         if table.RUNE_ID_TO_ETCHING.select(&id.into()).get().len() > 0 {
@@ -36,6 +45,7 @@ pub struct MessageContextParcel {
     pub txindex: u32,
     pub vout: u32,
     pub runtime_balances: Box<BalanceSheet<AtomicPointer>>,
+    pub fuel_limit: Option<u64>,
 }
 
 pub trait ToBytes {
@@ -64,6 +74,7 @@ impl Default for MessageContextParcel {
             txindex: 0,
             runtime_balances: Box::new(BalanceSheet::default()),
             sheets: Box::new(BalanceSheet::default()),
+            fuel_limit: None,
         }
     }
 }
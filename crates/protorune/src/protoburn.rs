@@ -1,3 +1,4 @@
+use crate::journal;
 use crate::tables::{RuneTable, RUNES};
 use anyhow::{anyhow, Result};
 use bitcoin::{OutPoint, Txid};
@@ -38,22 +39,16 @@ impl Protoburn {
         for (rune, _balance) in balance_sheet.balances().into_iter() {
             let runeid: Arc<Vec<u8>> = (*rune).into();
             let name = RUNES.RUNE_ID_TO_ETCHING.select(&runeid).get();
-            atomic
-                .derive(&table.RUNE_ID_TO_ETCHING.select(&runeid))
+            journal::written(atomic.derive(&table.RUNE_ID_TO_ETCHING.select(&runeid)))
                 .set(name.clone());
-            atomic
-                .derive(&table.ETCHING_TO_RUNE_ID.select(&name))
-                .set(runeid);
-            atomic
-                .derive(&table.SPACERS.select(&name))
+            journal::written(atomic.derive(&table.ETCHING_TO_RUNE_ID.select(&name))).set(runeid);
+            journal::written(atomic.derive(&table.SPACERS.select(&name)))
                 .set(RUNES.SPACERS.select(&name).get());
-            atomic
-                .derive(&table.DIVISIBILITY.select(&name))
+            journal::written(atomic.derive(&table.DIVISIBILITY.select(&name)))
                 .set(RUNES.DIVISIBILITY.select(&name).get());
-            atomic
-                .derive(&table.SYMBOL.select(&name))
+            journal::written(atomic.derive(&table.SYMBOL.select(&name)))
                 .set(RUNES.SYMBOL.select(&name).get());
-            atomic.derive(&table.ETCHINGS).append(name);
+            journal::appended(atomic.derive(&table.ETCHINGS)).append(name);
         }
         if !proto_balances_by_output.contains_key(&outpoint.vout) {
             proto_balances_by_output.insert(outpoint.vout, BalanceSheet::default());
@@ -1,3 +1,4 @@
+use crate::journal;
 use crate::message::MessageContext;
 use crate::tables::RuneTable;
 use metashrew_core::index_pointer::AtomicPointer;
@@ -23,8 +24,8 @@ pub fn index_unique_protorunes<T: MessageContext>(
         .map(|v| -> Vec<u8> { v.into() })
         .for_each(|v| {
             if seen_table.select(&v).get().as_ref().len() == 0 {
-                seen_table.select(&v).set(Arc::new(vec![0x01]));
-                table.select_value::<u64>(height).append(Arc::new(v));
+                journal::written(seen_table.select(&v)).set(Arc::new(vec![0x01]));
+                journal::appended(table.select_value::<u64>(height)).append(Arc::new(v));
             }
         });
 }
@@ -150,6 +150,7 @@ impl MessageProcessor for Protostone {
                     .unwrap_or_else(|| BalanceSheet::default()),
             ),
             sheets: Box::new(BalanceSheet::default()),
+            fuel_limit: self.fuel_limit,
         };
 
         match T::handle(&parcel) {
@@ -316,6 +317,8 @@ mod tests {
             pointer: Some(3),
             refund: None,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             protocol_tag: 13, // must be 13 when protoburn
             message: vec![],
         }];
@@ -342,6 +345,8 @@ mod tests {
             pointer: Some(3),
             refund: None,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             protocol_tag: 1,
             message: vec![],
         }];
@@ -361,6 +366,8 @@ mod tests {
             pointer: Some(3),
             refund: None,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             protocol_tag: 1,
             message: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0], // what we pass in should be well defined by the subprotocol
         }];
@@ -381,6 +388,8 @@ mod tests {
                 pointer: Some(3),
                 refund: None,
                 from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
                 protocol_tag: 13,
                 message: vec![],
             },
@@ -390,6 +399,8 @@ mod tests {
                 pointer: Some(2),
                 refund: None,
                 from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
                 protocol_tag: 3,
                 message: vec![100, 11, 112, 113, 114, 115, 116, 117, 118, 0, 0, 0, 0, 0, 0],
             },
@@ -401,4 +412,59 @@ mod tests {
 
         assert_eq!(protostones, protostone_decipered);
     }
+
+    #[test]
+    fn test_protostone_encipher_fuel_limit() {
+        let protostones = vec![Protostone {
+            burn: None,
+            edicts: vec![],
+            pointer: Some(0),
+            refund: Some(0),
+            from: None,
+            fuel_limit: Some(1_000_000),
+            all_or_nothing: false,
+            protocol_tag: 1,
+            message: vec![2, 1, 77],
+        }];
+
+        let protostone_enciphered = protostones.encipher().unwrap();
+
+        let protostone_decipered = Protostone::decipher(&protostone_enciphered).unwrap();
+
+        assert_eq!(protostones, protostone_decipered);
+    }
+
+    #[test]
+    fn test_protostone_encipher_all_or_nothing() {
+        let protostones = vec![
+            Protostone {
+                burn: None,
+                edicts: vec![],
+                pointer: Some(0),
+                refund: Some(0),
+                from: None,
+                fuel_limit: None,
+                all_or_nothing: true,
+                protocol_tag: 1,
+                message: vec![1, 0, 50],
+            },
+            Protostone {
+                burn: None,
+                edicts: vec![],
+                pointer: Some(0),
+                refund: Some(0),
+                from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
+                protocol_tag: 1,
+                message: vec![2, 1, 100],
+            },
+        ];
+
+        let protostone_enciphered = protostones.encipher().unwrap();
+
+        let protostone_decipered = Protostone::decipher(&protostone_enciphered).unwrap();
+
+        assert_eq!(protostones, protostone_decipered);
+    }
 }
@@ -573,6 +573,8 @@ pub fn create_transaction_with_middle_op_return(
             pointer: Some(0), // Point to output 0 (before OP_RETURN)
             refund: None,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             protocol_tag: protocol_id,
             message: vec![],
         }]
@@ -794,6 +796,8 @@ pub fn create_multi_protoburn_transaction(
                 pointer: Some(i as u32),
                 refund: None,
                 from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
                 protocol_tag: 13,
                 message: vec![],
             })
@@ -905,6 +909,8 @@ pub fn create_protostone_transaction(
             pointer: Some(output_protostone_pointer),
             refund: None,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             protocol_tag: protocol_tag,
             message: vec![],
         }]
@@ -982,6 +988,8 @@ pub fn create_multiple_protomessage_from_edict_tx(
             refund: Some(1),
             edicts,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             burn: None,
             protocol_tag: protocol_id[i] as u128,
         })
@@ -1070,6 +1078,8 @@ pub fn create_protomessage_from_edict_tx(
             refund: Some(1),
             edicts: protostone_edicts,
             from: None,
+            fuel_limit: None,
+            all_or_nothing: false,
             burn: None,
             protocol_tag: protocol_id as u128,
         }]
@@ -284,6 +284,8 @@ mod tests {
                     pointer: Some(4), // output 0 is the spendable outputs, output 1 is the op_return, output 2 is reserved, output 3 is the protoburn, so output 4 is the protomessage
                     refund: None,
                     from: None,
+                    fuel_limit: None,
+                    all_or_nothing: false,
                     protocol_tag: 13, // this value must be 13 if protoburn
                     message: vec![],
                 },
@@ -294,6 +296,8 @@ mod tests {
                     refund: Some(0),
                     edicts: vec![],
                     from: None,
+                    fuel_limit: None,
+                    all_or_nothing: false,
                     burn: None,
                     protocol_tag: protocol_id as u128,
                 },
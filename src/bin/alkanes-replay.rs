@@ -0,0 +1,107 @@
+use alkanes::indexer::configure_network;
+use alkanes::query::{call_view, VIEWS};
+use alkanes::replay::{read_block_dir, replay};
+use alkanes::snapshot::{decode_snapshot, export_snapshot, import_snapshot};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: alkanes-replay <block-dir> \
+[--load-snapshot <file> --snapshot-root <hex content root>] [--save-snapshot <file>] [--traces] \
+[--view <name> <hex input>]...";
+
+#[derive(Default)]
+struct Args {
+    blocks: PathBuf,
+    load_snapshot: Option<PathBuf>,
+    snapshot_root: Option<Vec<u8>>,
+    save_snapshot: Option<PathBuf>,
+    traces: bool,
+    views: Vec<(String, Vec<u8>)>,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args::default();
+    let mut blocks = None;
+    let mut iter = std::env::args().skip(1);
+    let value = |iter: &mut dyn Iterator<Item = String>, flag: &str| {
+        iter.next()
+            .ok_or_else(|| anyhow!("{} needs a value\n{}", flag, USAGE))
+    };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--load-snapshot" => {
+                args.load_snapshot = Some(value(&mut iter, &arg)?.into());
+            }
+            "--snapshot-root" => {
+                let root = hex::decode(value(&mut iter, &arg)?.trim_start_matches("0x"))?;
+                args.snapshot_root = Some(root);
+            }
+            "--save-snapshot" => {
+                args.save_snapshot = Some(value(&mut iter, &arg)?.into());
+            }
+            "--traces" => args.traces = true,
+            "--view" => {
+                let name = value(&mut iter, &arg)?;
+                if !VIEWS.contains(&name.as_str()) {
+                    return Err(anyhow!(
+                        "unknown view {}, expected one of {:?}",
+                        name,
+                        VIEWS
+                    ));
+                }
+                let input = hex::decode(value(&mut iter, &arg)?.trim_start_matches("0x"))?;
+                args.views.push((name, input));
+            }
+            _ if blocks.is_none() && !arg.starts_with("--") => blocks = Some(arg.into()),
+            _ => return Err(anyhow!("unexpected argument {}\n{}", arg, USAGE)),
+        }
+    }
+    args.blocks = blocks.ok_or_else(|| anyhow!(USAGE))?;
+    if args.load_snapshot.is_some() != args.snapshot_root.is_some() {
+        return Err(anyhow!(
+            "--load-snapshot and --snapshot-root go together\n{}",
+            USAGE
+        ));
+    }
+    Ok(args)
+}
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    configure_network();
+
+    let mut tip = None;
+    if let (Some(path), Some(root)) = (&args.load_snapshot, &args.snapshot_root) {
+        let header = import_snapshot(&fs::read(path)?, root)?;
+        eprintln!("loaded snapshot at block {}", header.height);
+        tip = Some(header.height);
+    }
+
+    let blocks = read_block_dir(&args.blocks)?
+        .into_iter()
+        .filter(|(height, _)| tip.map_or(true, |tip| u64::from(*height) > tip))
+        .collect::<Vec<_>>();
+    replay(&blocks)?;
+    if let Some((height, _)) = blocks.last() {
+        eprintln!("indexed {} blocks up to {}", blocks.len(), height);
+        tip = Some((*height).into());
+    }
+
+    for (name, input) in &args.views {
+        println!("{}", hex::encode(call_view(name, input)?));
+    }
+
+    if let Some(path) = &args.save_snapshot {
+        let height = tip.ok_or_else(|| anyhow!("no blocks were indexed to snapshot"))?;
+        let snapshot = export_snapshot(height, args.traces)?;
+        let (header, _) = decode_snapshot(&snapshot)?;
+        fs::write(path, snapshot)?;
+        eprintln!(
+            "saved snapshot at block {} with content root {}",
+            height,
+            hex::encode(header.content_root)
+        );
+    }
+    Ok(())
+}
@@ -223,3 +223,15 @@ impl AuxpowHeader {
         Ok(result)
     }
 }
+
+/// Splits the input metashrew hands the indexer, a u32 height followed by the serialized
+/// block, decoding AuxPoW blocks on the networks that use them.
+pub fn decode_block_input(data: &[u8]) -> Result<(u32, Block)> {
+    let mut cursor = Cursor::new(data.to_vec());
+    let height = consume_sized_int::<u32>(&mut cursor)?;
+    #[cfg(any(feature = "dogecoin", feature = "luckycoin", feature = "bellscoin"))]
+    let block: Block = metashrew_support::block::AuxpowBlock::parse(&mut cursor)?.to_consensus();
+    #[cfg(not(any(feature = "dogecoin", feature = "luckycoin", feature = "bellscoin")))]
+    let block: Block = consensus_decode::<Block>(&mut cursor)?;
+    Ok((height, block))
+}
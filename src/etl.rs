@@ -5,13 +5,14 @@ use bitcoin::consensus::encode::Decodable;
 use metashrew_core::index_pointer::IndexPointer;
 use metashrew_support::index_pointer::KeyValuePointer;
 use once_cell::sync::Lazy;
+use protorune::journal;
 use std::io::Cursor;
 use std::sync::Arc;
 
 pub static BLOCKS: Lazy<IndexPointer> = Lazy::new(|| IndexPointer::from_keyword("/blockdata/"));
 
 pub fn index_extensions(height: u32, v: &bitcoin::Block) {
-    BLOCKS.select_value(height).set(Arc::new(serialize(v)))
+    journal::written(BLOCKS.select_value(height)).set(Arc::new(serialize(v)))
 }
 
 pub fn get_block(height: u32) -> Result<bitcoin::Block> {
@@ -1,5 +1,7 @@
 use crate::message::AlkaneMessageContext;
 use crate::network::{genesis, is_genesis};
+use crate::stateroot::{commit_state_root, reset as reset_touched};
+use crate::tables::BLOCK_TIMES;
 use crate::vm::fuel::FuelTank;
 use anyhow::Result;
 use bitcoin::blockdata::block::Block;
@@ -8,6 +10,7 @@ use metashrew_core::{
     println,
     stdio::{stdout, Write},
 };
+use protorune::journal;
 use protorune::Protorune;
 #[allow(unused_imports)]
 use metashrew_support::index_pointer::KeyValuePointer;
@@ -82,15 +85,21 @@ use std::sync::Arc;
 
 pub fn index_block(block: &Block, height: u32) -> Result<()> {
     configure_network();
+    reset_touched();
+    #[cfg(any(test, feature = "undo-log"))]
+    crate::undo::capture();
     let really_is_genesis = is_genesis(height.into());
     if really_is_genesis {
         genesis(&block).unwrap();
     }
-    FuelTank::initialize(&block);
+    FuelTank::initialize(&block, height.into());
+    journal::written(BLOCK_TIMES.select_value::<u64>(height.into()))
+        .set_value::<u32>(block.header.time);
 
     // Get the set of updated addresses from the indexing process
     let _updated_addresses =
       Protorune::index_block::<AlkaneMessageContext>(block.clone(), height.into())?;
+    commit_state_root(&block, height.into())?;
 
     #[cfg(feature = "cache")]
     {
@@ -113,8 +122,7 @@ pub fn index_block(block: &Block, height: u32) -> Result<()> {
             match protorunes_by_address(&request.write_to_bytes()?) {
                 Ok(full_response) => {
                     // Cache the serialized full WalletResponse
-                    CACHED_WALLET_RESPONSE
-                        .select(&address)
+                    journal::written(CACHED_WALLET_RESPONSE.select(&address))
                         .set(Arc::new(full_response.write_to_bytes()?));
 
                     // Create a filtered version with only outpoints that have runes
@@ -139,8 +147,7 @@ pub fn index_block(block: &Block, height: u32) -> Result<()> {
                         .collect::<Vec<protorune_support::proto::protorune::OutpointResponse>>();
 
                     // Cache the serialized filtered WalletResponse
-                    CACHED_FILTERED_WALLET_RESPONSE
-                        .select(&address)
+                    journal::written(CACHED_FILTERED_WALLET_RESPONSE.select(&address))
                         .set(Arc::new(filtered_response.write_to_bytes()?));
                 }
                 Err(e) => {
@@ -150,5 +157,11 @@ pub fn index_block(block: &Block, height: u32) -> Result<()> {
         }
     }
 
+    #[cfg(any(test, feature = "undo-log"))]
+    crate::undo::record_undo(height.into());
+
+    #[cfg(feature = "debug-log")]
+    crate::vm::cache::log_cache_stats();
+
     Ok(())
 }
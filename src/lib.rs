@@ -1,31 +1,28 @@
 use crate::indexer::configure_network;
-use crate::view::{meta_safe, multi_simulate_safe, parcel_from_protobuf, simulate_safe};
-use alkanes_support::proto;
-use bitcoin::{Block, OutPoint};
 #[allow(unused_imports)]
 use metashrew_core::{
     flush, input, println,
     stdio::{stdout, Write},
 };
-#[allow(unused_imports)]
-use metashrew_support::block::AuxpowBlock;
 use metashrew_support::compat::export_bytes;
 #[allow(unused_imports)]
 use metashrew_support::index_pointer::KeyValuePointer;
-use metashrew_support::utils::{consensus_decode, consume_sized_int, consume_to_end};
-use protobuf::{Message, MessageField};
-use std::io::Cursor;
-use view::parcels_from_protobuf;
 pub mod block;
 pub mod etl;
 pub mod indexer;
+pub mod mempool;
 pub mod message;
 pub mod network;
 pub mod precompiled;
+pub mod query;
+pub mod replay;
+pub mod snapshot;
+pub mod stateroot;
 pub mod tables;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod tests;
 pub mod trace;
+pub mod undo;
 pub mod utils;
 pub mod view;
 pub mod vm;
@@ -56,109 +53,49 @@ Thus, going to add not(test) to all these functions
 #[no_mangle]
 pub fn multisimluate() -> i32 {
     configure_network();
-    let data = input();
-    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
-    let reader = &data[4..];
-    let mut result: proto::alkanes::MultiSimulateResponse =
-        proto::alkanes::MultiSimulateResponse::new();
-    let responses = multi_simulate_safe(
-        &parcels_from_protobuf(
-            proto::alkanes::MultiSimulateRequest::parse_from_bytes(reader).unwrap(),
-        ),
-        u64::MAX,
-    );
-
-    for response in responses {
-        let mut res = proto::alkanes::SimulateResponse::new();
-        match response {
-            Ok((response, gas_used)) => {
-                res.execution = MessageField::some(response.into());
-                res.gas_used = gas_used;
-            }
-            Err(e) => {
-                result.error = e.to_string();
-            }
-        }
-        result.responses.push(res);
-    }
-
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::multisimluate(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn simulate() -> i32 {
     configure_network();
-    let data = input();
-    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
-    let reader = &data[4..];
-    let mut result: proto::alkanes::SimulateResponse = proto::alkanes::SimulateResponse::new();
-    match simulate_safe(
-        &parcel_from_protobuf(
-            proto::alkanes::MessageContextParcel::parse_from_bytes(reader).unwrap(),
-        ),
-        u64::MAX,
-    ) {
-        Ok((response, gas_used)) => {
-            result.execution = MessageField::some(response.into());
-            result.gas_used = gas_used;
-        }
-        Err(e) => {
-            result.error = e.to_string();
-        }
-    }
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::simulate(&input()))
+}
+
+#[cfg(not(test))]
+#[no_mangle]
+pub fn mempoolsimulate() -> i32 {
+    configure_network();
+    export_bytes(query::mempoolsimulate(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn meta() -> i32 {
     configure_network();
-    let data = input();
-    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
-    let reader = &data[4..];
-    match meta_safe(&parcel_from_protobuf(
-        proto::alkanes::MessageContextParcel::parse_from_bytes(reader).unwrap(),
-    )) {
-        Ok(response) => export_bytes(response),
-        Err(_) => export_bytes(vec![]),
-    }
+    export_bytes(query::meta(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn runesbyaddress() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::WalletResponse =
-        protorune::view::runes_by_address(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::runesbyaddress(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn runesbyoutpoint() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::OutpointResponse =
-        protorune::view::runes_by_outpoint(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::OutpointResponse::new());
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::runesbyoutpoint(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn spendablesbyaddress() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::WalletResponse =
-        view::protorunes_by_address(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::spendablesbyaddress(&input()))
 }
 
 // #[cfg(not(test))]
@@ -177,34 +114,7 @@ pub fn spendablesbyaddress() -> i32 {
 #[no_mangle]
 pub fn protorunesbyaddress() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let input_data = consume_to_end(&mut data).unwrap();
-    //  let _request = protorune_support::proto::protorune::ProtorunesWalletRequest::parse_from_bytes(&input_data).unwrap();
-
-    let mut result: protorune_support::proto::protorune::WalletResponse =
-        view::protorunes_by_address(&input_data)
-            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
-
-    result.outpoints = result
-        .outpoints
-        .into_iter()
-        .filter_map(|v| {
-            if v.clone()
-                .balances
-                .unwrap_or_else(|| protorune_support::proto::protorune::BalanceSheet::new())
-                .entries
-                .len()
-                == 0
-            {
-                None
-            } else {
-                Some(v)
-            }
-        })
-        .collect::<Vec<protorune_support::proto::protorune::OutpointResponse>>();
-
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::protorunesbyaddress(&input()))
 }
 
 // #[cfg(not(test))]
@@ -267,86 +177,63 @@ pub fn protorunesbyaddress() -> i32 {
 #[no_mangle]
 pub fn protorunesbyheight() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::RunesResponse =
-        view::protorunes_by_height(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::RunesResponse::new());
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::protorunesbyheight(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn alkanes_id_to_outpoint() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    // first 4 bytes come in as height, not used
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let data_vec = consume_to_end(&mut data).unwrap();
-    let result: alkanes_support::proto::alkanes::AlkaneIdToOutpointResponse =
-        view::alkanes_id_to_outpoint(&data_vec).unwrap_or_else(|err| {
-            eprintln!("Error in alkanes_id_to_outpoint: {:?}", err);
-            alkanes_support::proto::alkanes::AlkaneIdToOutpointResponse::new()
-        });
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::alkanes_id_to_outpoint(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn traceblock() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let height = consume_sized_int::<u32>(&mut data).unwrap();
-    export_bytes(view::traceblock(height).unwrap())
+    export_bytes(query::traceblock(&input()))
+}
+
+#[cfg(not(test))]
+#[no_mangle]
+pub fn staterootbyheight() -> i32 {
+    configure_network();
+    export_bytes(query::staterootbyheight(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn trace() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let outpoint: OutPoint = protorune_support::proto::protorune::Outpoint::parse_from_bytes(
-        &consume_to_end(&mut data).unwrap(),
-    )
-    .unwrap()
-    .try_into()
-    .unwrap();
-    export_bytes(view::trace(&outpoint).unwrap())
+    export_bytes(query::trace(&input()))
+}
+
+#[cfg(not(test))]
+#[no_mangle]
+pub fn events() -> i32 {
+    configure_network();
+    export_bytes(query::events(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn getbytecode() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    export_bytes(view::getbytecode(&consume_to_end(&mut data).unwrap()).unwrap_or_default())
+    export_bytes(query::getbytecode(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn protorunesbyoutpoint() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::OutpointResponse =
-        view::protorunes_by_outpoint(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::OutpointResponse::new());
-
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::protorunesbyoutpoint(&input()))
 }
 
 #[cfg(not(test))]
 #[no_mangle]
 pub fn runesbyheight() -> i32 {
     configure_network();
-    let mut data: Cursor<Vec<u8>> = Cursor::new(input());
-    let _height = consume_sized_int::<u32>(&mut data).unwrap();
-    let result: protorune_support::proto::protorune::RunesResponse =
-        protorune::view::runes_by_height(&consume_to_end(&mut data).unwrap())
-            .unwrap_or_else(|_| protorune_support::proto::protorune::RunesResponse::new());
-    export_bytes(result.write_to_bytes().unwrap())
+    export_bytes(query::runesbyheight(&input()))
 }
 
 // #[no_mangle]
@@ -368,17 +255,7 @@ pub fn runesbyheight() -> i32 {
 #[cfg(all(target_arch = "wasm32", not(test)))]
 #[no_mangle]
 pub fn _start() {
-    let data = input();
-    let height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
-    let reader = &data[4..];
-    #[cfg(any(feature = "dogecoin", feature = "luckycoin", feature = "bellscoin"))]
-    let block: Block = AuxpowBlock::parse(&mut Cursor::<Vec<u8>>::new(reader.to_vec()))
-        .unwrap()
-        .to_consensus();
-    #[cfg(not(any(feature = "dogecoin", feature = "luckycoin", feature = "bellscoin")))]
-    let block: Block =
-        consensus_decode::<Block>(&mut Cursor::<Vec<u8>>::new(reader.to_vec())).unwrap();
-
+    let (height, block) = block::decode_block_input(&input()).unwrap();
     index_block(&block, height).unwrap();
     etl::index_extensions(height, &block);
     flush();
@@ -388,11 +265,14 @@ pub fn _start() {
 mod unit_tests {
     use super::*;
     use crate::message::AlkaneMessageContext;
+    use bitcoin::{Block, OutPoint};
+    use metashrew_support::utils::consensus_decode;
     use protobuf::{Message, SpecialFields};
     use protorune::view::{rune_outpoint_to_outpoint_response, runes_by_address, runes_by_height};
     use protorune::Protorune;
     use protorune_support::proto::protorune::{RunesByHeightRequest, Uint128, WalletRequest};
     use std::fs;
+    use std::io::Cursor;
     use std::path::PathBuf;
 
     #[test]
@@ -0,0 +1,201 @@
+use crate::message::AlkaneMessageContext;
+use crate::network::set_view_mode;
+use crate::stateroot;
+use crate::tables::{BLOCK_TIMES, TRACES, TRACES_BY_HEIGHT};
+use crate::vm::fuel::FuelTank;
+use crate::vm::schedule::protocol_rules;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::parcel::AlkaneTransfer;
+use alkanes_support::proto;
+use anyhow::Result;
+use bitcoin::block::{Header, Version};
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, BlockHash, CompactTarget, OutPoint, Transaction, TxMerkleNode};
+use metashrew_core::index_pointer::IndexPointer;
+use metashrew_support::index_pointer::KeyValuePointer;
+use metashrew_support::utils::{consensus_decode, consensus_encode};
+use protobuf::{Message, MessageField};
+use protorune::balance_sheet::load_sheet;
+use protorune::message::MessageContext;
+use protorune::tables::{RuneTable, RUNES};
+use protorune::Protorune;
+use protorune_support::balance_sheet::BalanceSheetOperations;
+use std::io::Cursor;
+
+/*
+Mempool simulation runs unconfirmed transactions through Protorune::index_unspendable, the
+per-transaction step of Protorune::index_block, as if they were mined in order in the block
+after the tip. A view call is never flushed, so nothing it writes is persisted; when the
+index is held in memory, as under test, the writes are undone before returning.
+*/
+
+fn outpoint_proto(outpoint: &OutPoint) -> proto::alkanes::Outpoint {
+    proto::alkanes::Outpoint {
+        txid: outpoint.txid.as_byte_array().to_vec(),
+        vout: outpoint.vout,
+        ..Default::default()
+    }
+}
+
+fn sheet_transfers(ptr: &IndexPointer) -> Vec<proto::alkanes::AlkaneTransfer> {
+    let sheet = load_sheet(ptr);
+    let mut transfers = sheet
+        .balances()
+        .iter()
+        .map(|(id, value)| AlkaneTransfer {
+            id: AlkaneId::from(id.clone()),
+            value: *value,
+        })
+        .collect::<Vec<AlkaneTransfer>>();
+    transfers.sort_by(|a, b| a.id.cmp(&b.id));
+    transfers.into_iter().map(|v| v.into()).collect()
+}
+
+fn failure(
+    txindex: u32,
+    outpoint: Option<&OutPoint>,
+    error: String,
+) -> proto::alkanes::MempoolFailure {
+    proto::alkanes::MempoolFailure {
+        txindex,
+        outpoint: MessageField::from_option(outpoint.map(outpoint_proto)),
+        error,
+        ..Default::default()
+    }
+}
+
+// the revert message of a trace whose outermost call failed, decoded as the rules at `height`
+// encode reverts
+fn revert_message(trace: &proto::alkanes::AlkanesTrace, height: u64) -> Option<String> {
+    let last = trace.events.last()?;
+    if !last.has_exit_context() {
+        return None;
+    }
+    let exit = last.exit_context();
+    if exit.status.enum_value_or_default() != proto::alkanes::AlkanesTraceStatusFlag::FAILURE {
+        return None;
+    }
+    let data = &exit.response.data;
+    Some(match protocol_rules(height).decode_revert(data) {
+        Some(revert) => revert.message,
+        None => String::from_utf8_lossy(data).to_string(),
+    })
+}
+
+// a header following the block at `height`, for contracts that read the block
+fn next_header(height: u64) -> Result<Header> {
+    let prev = RUNES.HEIGHT_TO_BLOCKHASH.select_value::<u64>(height).get();
+    Ok(Header {
+        version: Version::from_consensus(1),
+        prev_blockhash: if prev.is_empty() {
+            BlockHash::all_zeros()
+        } else {
+            consensus_decode::<BlockHash>(&mut Cursor::new(prev.as_ref().clone()))?
+        },
+        merkle_root: TxMerkleNode::all_zeros(),
+        time: BLOCK_TIMES.select_value::<u64>(height).get_value::<u32>(),
+        bits: CompactTarget::from_consensus(0),
+        nonce: 0,
+    })
+}
+
+fn simulate(
+    height: u64,
+    transactions: &[Vec<u8>],
+) -> Result<proto::alkanes::MempoolSimulateResponse> {
+    let mut response = proto::alkanes::MempoolSimulateResponse::new();
+    response.height = height + 1;
+    let mut txindexes = vec![];
+    let mut txdata = vec![];
+    for (txindex, raw) in transactions.iter().enumerate() {
+        match consensus_decode::<Transaction>(&mut Cursor::new(raw.clone())) {
+            Ok(tx) => {
+                txindexes.push(txindex as u32);
+                txdata.push(tx);
+            }
+            Err(e) => response.failures.push(failure(
+                txindex as u32,
+                None,
+                format!("invalid transaction: {}", e),
+            )),
+        }
+    }
+    let block = Block {
+        header: next_header(height)?,
+        txdata,
+    };
+    FuelTank::initialize(&block, response.height);
+    // as when the block is indexed, so a transaction can read the outputs of an earlier one
+    // in the batch through __request_prevouts
+    Protorune::index_transaction_ids(&block, response.height)?;
+    Protorune::index_outpoints(&block, response.height)?;
+
+    let protocol = RuneTable::for_protocol(AlkaneMessageContext::protocol_tag());
+    for (index, (tx, txindex)) in block.txdata.iter().zip(txindexes).enumerate() {
+        if let Some(e) = Protorune::index_unspendable::<AlkaneMessageContext>(
+            &block,
+            response.height,
+            index as u32,
+            tx,
+        )? {
+            response
+                .failures
+                .push(failure(txindex, None, e.to_string()));
+        }
+        let txid = tx.compute_txid();
+        for vout in 0..tx.output.len() as u32 {
+            let outpoint = OutPoint { txid, vout };
+            let key = consensus_encode(&outpoint)?;
+            response.outputs.push(proto::alkanes::MempoolOutput {
+                outpoint: MessageField::some(outpoint_proto(&outpoint)),
+                runes: sheet_transfers(&RUNES.OUTPOINT_TO_RUNES.select(&key)),
+                alkanes: sheet_transfers(&protocol.OUTPOINT_TO_RUNES.select(&key)),
+                ..Default::default()
+            });
+        }
+        for key in TRACES_BY_HEIGHT.select_value(response.height).get_list() {
+            let outpoint = consensus_decode::<OutPoint>(&mut Cursor::new(key.as_ref().clone()))?;
+            if outpoint.txid != txid {
+                continue;
+            }
+            let trace = proto::alkanes::AlkanesTrace::parse_from_bytes(&TRACES.select(&key).get())?;
+            if let Some(error) = revert_message(&trace, response.height) {
+                response
+                    .failures
+                    .push(failure(txindex, Some(&outpoint), error));
+            }
+            response.traces.push(proto::alkanes::Trace {
+                outpoint: MessageField::some(outpoint_proto(&outpoint)),
+                trace: MessageField::some(trace),
+                ..Default::default()
+            });
+        }
+    }
+    response.failures.sort_by_key(|failure| failure.txindex);
+    Ok(response)
+}
+
+/// Executes the raw `transactions` in order on top of the state at `height`, returning the
+/// balance sheets of their outputs, the trace of every protomessage and every failure.
+/// Transactions that fail to decode are reported as failures and left out.
+pub fn simulate_mempool(
+    height: u64,
+    transactions: &[Vec<u8>],
+) -> Result<proto::alkanes::MempoolSimulateResponse> {
+    set_view_mode();
+    #[cfg(any(test, feature = "undo-log"))]
+    crate::undo::capture();
+    let result = simulate(height, transactions);
+    #[cfg(any(test, feature = "undo-log"))]
+    crate::undo::restore();
+    stateroot::reset();
+    result
+}
+
+pub fn mempool_simulate(
+    height: u64,
+    input: &Vec<u8>,
+) -> Result<proto::alkanes::MempoolSimulateResponse> {
+    let request = proto::alkanes::MempoolSimulateRequest::parse_from_bytes(input)?;
+    simulate_mempool(height, &request.transactions)
+}
@@ -1,18 +1,19 @@
 use crate::network::{genesis::GENESIS_BLOCK, is_active};
-use crate::trace::save_trace;
+use crate::trace::{revert_trace, save_trace};
 use crate::utils::{credit_balances, debit_balances, pipe_storagemap_to};
 use crate::vm::{
     fuel::{FuelTank, VirtualFuelBytes},
     runtime::AlkanesRuntimeContext,
+    schedule::protocol_rules,
     utils::{prepare_context, run_after_special, run_special_cellpacks},
 };
 use alkanes_support::{
     cellpack::Cellpack,
-    response::ExtendedCallResponse,
+    revert::{Revert, REVERT_CODE_MESSAGE, REVERT_CODE_VM},
     trace::{TraceContext, TraceEvent, TraceResponse},
 };
 use anyhow::{anyhow, Result};
-use bitcoin::OutPoint;
+use bitcoin::{OutPoint, Transaction};
 use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
 #[allow(unused_imports)]
 use metashrew_core::{
@@ -72,7 +73,26 @@ pub fn handle_message(
         parcel, &cellpack,
     )));
     let mut atomic = parcel.atomic.derive(&IndexPointer::default());
-    let (caller, myself, binary) = run_special_cellpacks(context.clone(), &cellpack)?;
+    let (caller, myself, binary) = match run_special_cellpacks(context.clone(), &cellpack) {
+        Ok(v) => v,
+        Err(e) => {
+            // deploys rejected before any code runs still leave a revert in the trace
+            let revert = Revert::from_error(&e, REVERT_CODE_MESSAGE, &target, 0);
+            let trace = context.lock().unwrap().trace.clone();
+            trace.clock(TraceEvent::RevertContext(
+                protocol_rules(parcel.height).revert_response(&revert, 0),
+            ));
+            save_trace(
+                &OutPoint {
+                    txid: parcel.transaction.compute_txid(),
+                    vout: parcel.vout,
+                },
+                parcel.height,
+                trace,
+            )?;
+            return Err(e);
+        }
+    };
 
     #[cfg(feature = "debug-log")]
     {
@@ -87,14 +107,23 @@ pub fn handle_message(
 
     credit_balances(&mut atomic, &myself, &parcel.runes)?;
     prepare_context(context.clone(), &caller, &myself, false);
-    let txsize = parcel.transaction.vfsize() as u64;
+    let txsize = parcel
+        .transaction
+        .vfsize_with(protocol_rules(parcel.height).vfsize);
     if FuelTank::is_top() {
         FuelTank::fuel_transaction(txsize, parcel.txindex);
     } else if FuelTank::should_advance(parcel.txindex) {
         FuelTank::refuel_block();
         FuelTank::fuel_transaction(txsize, parcel.txindex);
     }
-    let fuel = FuelTank::start_fuel();
+    // fuel limits are ignored before they were introduced, as the tag was unknown then
+    let fuel_limit = parcel
+        .fuel_limit
+        .filter(|_| protocol_rules(parcel.height).fuel_limits);
+    let fuel = match fuel_limit {
+        Some(requested) => FuelTank::request_fuel(requested),
+        None => FuelTank::start_fuel(),
+    };
     // NOTE: we  want to keep unwrap for cases where we lock a mutex guard,
     // it's better if it panics, so then metashrew will retry that block again
     // whereas if we do .map_err(|e| anyhow!("Mutex lock poisoned: {}", e))?
@@ -106,6 +135,7 @@ pub fn handle_message(
         inner,
         target,
         fuel,
+        requested_fuel: fuel_limit,
     }));
     run_after_special(context.clone(), binary, fuel)
         .and_then(|(response, gas_used)| {
@@ -115,6 +145,7 @@ pub fn handle_message(
                 &mut atomic.derive(
                     &IndexPointer::from_keyword("/alkanes/").select(&myself.clone().into()),
                 ),
+                parcel.height,
             );
             let mut combined = parcel.runtime_balances.as_ref().clone();
             <BalanceSheet<AtomicPointer> as TryFrom<Vec<RuneTransfer>>>::try_from(
@@ -176,16 +207,16 @@ pub fn handle_message(
                 println!("========================");
             }
 
-            FuelTank::drain_fuel();
-            let mut response = ExtendedCallResponse::default();
-
-            response.data = vec![0x08, 0xc3, 0x79, 0xa0];
-            response.data.extend(e.to_string().as_bytes());
+            match fuel_limit {
+                Some(_) => FuelTank::burn_fuel(fuel),
+                None => FuelTank::drain_fuel(),
+            }
+            let depth = context.lock().unwrap().depth;
+            let revert = Revert::from_error(&e, REVERT_CODE_VM, &myself, depth);
             let cloned = context.clone().lock().unwrap().trace.clone();
-            cloned.clock(TraceEvent::RevertContext(TraceResponse {
-                inner: response,
-                fuel_used: u64::MAX,
-            }));
+            cloned.clock(TraceEvent::RevertContext(
+                protocol_rules(parcel.height).revert_response(&revert, u64::MAX),
+            ));
             save_trace(
                 &OutPoint {
                     txid: parcel.transaction.compute_txid(),
@@ -202,6 +233,21 @@ impl MessageContext for AlkaneMessageContext {
     fn protocol_tag() -> u128 {
         1
     }
+    fn all_or_nothing_active(height: u64) -> bool {
+        protocol_rules(height).all_or_nothing
+    }
+    // traces are kept outside the atomic, so those of messages that returned before the
+    // transaction failed would otherwise still show them succeeding
+    fn rollback_messages(tx: &Transaction, _height: u64, vouts: &[u32]) -> Result<()> {
+        let txid = tx.compute_txid();
+        for vout in vouts {
+            revert_trace(
+                &OutPoint { txid, vout: *vout },
+                "rolled back by a failing message in an all-or-nothing transaction",
+            )?;
+        }
+        Ok(())
+    }
     fn handle(
         _parcel: &MessageContextParcel,
     ) -> Result<(Vec<RuneTransfer>, BalanceSheet<AtomicPointer>)> {
@@ -5,8 +5,10 @@ use crate::precompiled::{
     alkanes_std_genesis_alkane_luckycoin_build, alkanes_std_genesis_alkane_mainnet_build,
     alkanes_std_genesis_alkane_regtest_build,
 };
+use crate::stateroot::written;
 use crate::utils::pipe_storagemap_to;
 use crate::view::simulate_parcel;
+use crate::vm::schedule::protocol_schedule;
 use crate::vm::utils::sequence_pointer;
 use alkanes_support::cellpack::Cellpack;
 use alkanes_support::gz::compress;
@@ -18,6 +20,7 @@ use bitcoin::{Block, OutPoint, Transaction, Txid};
 use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
 use metashrew_support::index_pointer::KeyValuePointer;
 use protorune::balance_sheet::PersistentRecord;
+use protorune::journal;
 use protorune::message::{MessageContext, MessageContextParcel};
 #[allow(unused_imports)]
 use protorune::tables::{RuneTable, RUNES};
@@ -124,7 +127,7 @@ pub mod genesis {
 }
 
 pub fn is_active(height: u64) -> bool {
-    height >= genesis::GENESIS_BLOCK
+    protocol_schedule().is_active(height)
 }
 
 static mut _VIEW: bool = false;
@@ -149,15 +152,16 @@ pub fn is_genesis(height: u64) -> bool {
         false
     };
     if is_genesis {
+        journal::record(&init_ptr);
         init_ptr.set_value::<u8>(0x01);
     }
     is_genesis
 }
 
 pub fn genesis(block: &Block) -> Result<()> {
-    IndexPointer::from_keyword("/alkanes/")
-        .select(&(AlkaneId { block: 2, tx: 0 }).into())
-        .set(Arc::new(compress(genesis_alkane_bytes())?));
+    let genesis_binary =
+        IndexPointer::from_keyword("/alkanes/").select(&(AlkaneId { block: 2, tx: 0 }).into());
+    written(genesis_binary).set(Arc::new(compress(genesis_alkane_bytes())?));
     let mut atomic: AtomicPointer = AtomicPointer::default();
     sequence_pointer(&atomic).set_value::<u128>(1);
     let myself = AlkaneId { block: 2, tx: 0 };
@@ -183,6 +187,7 @@ pub fn genesis(block: &Block) -> Result<()> {
         txindex: 0,
         vout: 0,
         runtime_balances: Box::<BalanceSheet<AtomicPointer>>::new(BalanceSheet::default()),
+        fuel_limit: None,
     };
     let (response, _gas_used) = (match simulate_parcel(&parcel, u64::MAX) {
         Ok((a, b)) => Ok((a, b)),
@@ -209,24 +214,25 @@ pub fn genesis(block: &Block) -> Result<()> {
     pipe_storagemap_to(
         &response.storage,
         &mut atomic.derive(&IndexPointer::from_keyword("/alkanes/").select(&myself.clone().into())),
+        genesis::GENESIS_BLOCK,
     );
 
-    atomic
-        .derive(&RUNES.OUTPOINT_TO_HEIGHT.select(&outpoint_bytes))
+    journal::written(atomic.derive(&RUNES.OUTPOINT_TO_HEIGHT.select(&outpoint_bytes)))
         .set_value(genesis::GENESIS_OUTPOINT_BLOCK_HEIGHT);
-    atomic
-        .derive(
+    journal::appended(
+        atomic.derive(
             &RUNES
                 .HEIGHT_TO_TRANSACTION_IDS
                 .select_value::<u64>(genesis::GENESIS_OUTPOINT_BLOCK_HEIGHT),
-        )
-        .append(Arc::new(
-            hex::decode(genesis::GENESIS_OUTPOINT)?
-                .iter()
-                .cloned()
-                .rev()
-                .collect::<Vec<u8>>(),
-        ));
+        ),
+    )
+    .append(Arc::new(
+        hex::decode(genesis::GENESIS_OUTPOINT)?
+            .iter()
+            .cloned()
+            .rev()
+            .collect::<Vec<u8>>(),
+    ));
     atomic.commit();
     Ok(())
 }
@@ -0,0 +1,276 @@
+use crate::mempool;
+use crate::stateroot;
+use crate::view::{
+    self, meta_safe, multi_simulate_safe, parcel_from_protobuf, parcels_from_protobuf,
+    simulate_safe,
+};
+use alkanes_support::proto;
+use anyhow::{anyhow, Result};
+use bitcoin::OutPoint;
+use metashrew_support::utils::{consume_sized_int, consume_to_end};
+use protobuf::{Message, MessageField};
+use std::io::Cursor;
+
+/*
+The view functions exported by the indexer, as plain functions of the bytes a metashrew
+view call receives: a u32 height followed by the request. The WASM exports in lib.rs wrap
+these, and native tools such as the replay binary call them through call_view.
+*/
+
+/// Names of the views `call_view` accepts, the same as their WASM export names.
+pub const VIEWS: &[&str] = &[
+    "multisimluate",
+    "simulate",
+    "mempoolsimulate",
+    "meta",
+    "runesbyaddress",
+    "runesbyoutpoint",
+    "spendablesbyaddress",
+    "protorunesbyaddress",
+    "protorunesbyheight",
+    "alkanes_id_to_outpoint",
+    "traceblock",
+    "staterootbyheight",
+    "trace",
+    "events",
+    "getbytecode",
+    "protorunesbyoutpoint",
+    "runesbyheight",
+];
+
+pub fn call_view(name: &str, input: &[u8]) -> Result<Vec<u8>> {
+    Ok(match name {
+        "multisimluate" => multisimluate(input),
+        "simulate" => simulate(input),
+        "mempoolsimulate" => mempoolsimulate(input),
+        "meta" => meta(input),
+        "runesbyaddress" => runesbyaddress(input),
+        "runesbyoutpoint" => runesbyoutpoint(input),
+        "spendablesbyaddress" => spendablesbyaddress(input),
+        "protorunesbyaddress" => protorunesbyaddress(input),
+        "protorunesbyheight" => protorunesbyheight(input),
+        "alkanes_id_to_outpoint" => alkanes_id_to_outpoint(input),
+        "traceblock" => traceblock(input),
+        "staterootbyheight" => staterootbyheight(input),
+        "trace" => trace(input),
+        "events" => events(input),
+        "getbytecode" => getbytecode(input),
+        "protorunesbyoutpoint" => protorunesbyoutpoint(input),
+        "runesbyheight" => runesbyheight(input),
+        _ => return Err(anyhow!("unknown view {}", name)),
+    })
+}
+
+pub fn multisimluate(data: &[u8]) -> Vec<u8> {
+    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
+    let reader = &data[4..];
+    let mut result: proto::alkanes::MultiSimulateResponse =
+        proto::alkanes::MultiSimulateResponse::new();
+    let responses = multi_simulate_safe(
+        &parcels_from_protobuf(
+            proto::alkanes::MultiSimulateRequest::parse_from_bytes(reader).unwrap(),
+        ),
+        u64::MAX,
+    );
+
+    for (response, call_tree) in responses {
+        let mut res = proto::alkanes::SimulateResponse::new();
+        res.call_tree = MessageField::from_option(call_tree.map(|v| v.into()));
+        match response {
+            Ok((response, gas_used)) => {
+                res.execution = MessageField::some(response.into());
+                res.gas_used = gas_used;
+            }
+            Err(e) => {
+                result.error = e.to_string();
+            }
+        }
+        result.responses.push(res);
+    }
+
+    result.write_to_bytes().unwrap()
+}
+
+pub fn simulate(data: &[u8]) -> Vec<u8> {
+    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
+    let reader = &data[4..];
+    let mut result: proto::alkanes::SimulateResponse = proto::alkanes::SimulateResponse::new();
+    let (response, call_tree) = simulate_safe(
+        &parcel_from_protobuf(
+            proto::alkanes::MessageContextParcel::parse_from_bytes(reader).unwrap(),
+        ),
+        u64::MAX,
+    );
+    result.call_tree = MessageField::from_option(call_tree.map(|v| v.into()));
+    match response {
+        Ok((response, gas_used)) => {
+            result.execution = MessageField::some(response.into());
+            result.gas_used = gas_used;
+        }
+        Err(e) => {
+            result.error = e.to_string();
+        }
+    }
+    result.write_to_bytes().unwrap()
+}
+
+pub fn mempoolsimulate(data: &[u8]) -> Vec<u8> {
+    let height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
+    let reader = &data[4..];
+    let result = mempool::mempool_simulate(height.into(), &reader.to_vec()).unwrap_or_else(|e| {
+        let mut response = proto::alkanes::MempoolSimulateResponse::new();
+        response.failures.push(proto::alkanes::MempoolFailure {
+            error: e.to_string(),
+            ..Default::default()
+        });
+        response
+    });
+    result.write_to_bytes().unwrap()
+}
+
+pub fn meta(data: &[u8]) -> Vec<u8> {
+    let _height = u32::from_le_bytes((&data[0..4]).try_into().unwrap());
+    let reader = &data[4..];
+    match meta_safe(&parcel_from_protobuf(
+        proto::alkanes::MessageContextParcel::parse_from_bytes(reader).unwrap(),
+    )) {
+        Ok(response) => response,
+        Err(_) => vec![],
+    }
+}
+
+pub fn runesbyaddress(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::WalletResponse =
+        protorune::view::runes_by_address(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
+    result.write_to_bytes().unwrap()
+}
+
+pub fn runesbyoutpoint(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::OutpointResponse =
+        protorune::view::runes_by_outpoint(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::OutpointResponse::new());
+    result.write_to_bytes().unwrap()
+}
+
+pub fn spendablesbyaddress(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::WalletResponse =
+        view::protorunes_by_address(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
+    result.write_to_bytes().unwrap()
+}
+
+pub fn protorunesbyaddress(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let input_data = consume_to_end(&mut data).unwrap();
+
+    let mut result: protorune_support::proto::protorune::WalletResponse =
+        view::protorunes_by_address(&input_data)
+            .unwrap_or_else(|_| protorune_support::proto::protorune::WalletResponse::new());
+
+    result.outpoints = result
+        .outpoints
+        .into_iter()
+        .filter_map(|v| {
+            if v.clone()
+                .balances
+                .unwrap_or_else(|| protorune_support::proto::protorune::BalanceSheet::new())
+                .entries
+                .len()
+                == 0
+            {
+                None
+            } else {
+                Some(v)
+            }
+        })
+        .collect::<Vec<protorune_support::proto::protorune::OutpointResponse>>();
+
+    result.write_to_bytes().unwrap()
+}
+
+pub fn protorunesbyheight(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::RunesResponse =
+        view::protorunes_by_height(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::RunesResponse::new());
+    result.write_to_bytes().unwrap()
+}
+
+pub fn alkanes_id_to_outpoint(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    // first 4 bytes come in as height, not used
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let data_vec = consume_to_end(&mut data).unwrap();
+    let result: alkanes_support::proto::alkanes::AlkaneIdToOutpointResponse =
+        view::alkanes_id_to_outpoint(&data_vec).unwrap_or_else(|err| {
+            eprintln!("Error in alkanes_id_to_outpoint: {:?}", err);
+            alkanes_support::proto::alkanes::AlkaneIdToOutpointResponse::new()
+        });
+    result.write_to_bytes().unwrap()
+}
+
+pub fn traceblock(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let height = consume_sized_int::<u32>(&mut data).unwrap();
+    view::traceblock(height).unwrap()
+}
+
+pub fn staterootbyheight(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let height = consume_sized_int::<u32>(&mut data).unwrap();
+    stateroot::state_root(height.into())
+}
+
+pub fn trace(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let outpoint: OutPoint = protorune_support::proto::protorune::Outpoint::parse_from_bytes(
+        &consume_to_end(&mut data).unwrap(),
+    )
+    .unwrap()
+    .try_into()
+    .unwrap();
+    view::trace(&outpoint).unwrap()
+}
+
+pub fn events(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: proto::alkanes::EventsResponse = view::events(&consume_to_end(&mut data).unwrap())
+        .unwrap_or_else(|_| proto::alkanes::EventsResponse::new());
+    result.write_to_bytes().unwrap()
+}
+
+pub fn getbytecode(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    view::getbytecode(&consume_to_end(&mut data).unwrap()).unwrap_or_default()
+}
+
+pub fn protorunesbyoutpoint(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::OutpointResponse =
+        view::protorunes_by_outpoint(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::OutpointResponse::new());
+
+    result.write_to_bytes().unwrap()
+}
+
+pub fn runesbyheight(input: &[u8]) -> Vec<u8> {
+    let mut data: Cursor<Vec<u8>> = Cursor::new(input.to_vec());
+    let _height = consume_sized_int::<u32>(&mut data).unwrap();
+    let result: protorune_support::proto::protorune::RunesResponse =
+        protorune::view::runes_by_height(&consume_to_end(&mut data).unwrap())
+            .unwrap_or_else(|_| protorune_support::proto::protorune::RunesResponse::new());
+    result.write_to_bytes().unwrap()
+}
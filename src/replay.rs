@@ -0,0 +1,63 @@
+use crate::block::decode_block_input;
+use crate::etl;
+use crate::indexer::index_block;
+use anyhow::{anyhow, Result};
+use bitcoin::Block;
+use std::fs;
+use std::path::Path;
+
+/*
+Replays blocks through the indexer outside of metashrew. A block file holds what _start()
+receives, a u32 height followed by the serialized block, either as raw bytes like
+src/tests/static/849236.txt or as a hex dump of the same bytes.
+*/
+
+fn is_hex_dump(data: &[u8]) -> bool {
+    !data.is_empty()
+        && data
+            .iter()
+            .all(|b| b.is_ascii_hexdigit() || b.is_ascii_whitespace())
+}
+
+pub fn parse_block_file(data: &[u8]) -> Result<(u32, Block)> {
+    if is_hex_dump(data) {
+        let text = data
+            .iter()
+            .filter(|b| !b.is_ascii_whitespace())
+            .cloned()
+            .collect::<Vec<u8>>();
+        decode_block_input(&hex::decode(text)?)
+    } else {
+        decode_block_input(data)
+    }
+}
+
+/// Reads every file in `dir` as a block file, ordered by height.
+pub fn read_block_dir(dir: &Path) -> Result<Vec<(u32, Block)>> {
+    let mut blocks = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let block = parse_block_file(&fs::read(&path)?)
+            .map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+        blocks.push(block);
+    }
+    blocks.sort_by_key(|(height, _)| *height);
+    for pair in blocks.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            return Err(anyhow!("two block files for height {}", pair[0].0));
+        }
+    }
+    Ok(blocks)
+}
+
+/// Indexes the blocks in order, exactly as _start() would for each of them.
+pub fn replay(blocks: &[(u32, Block)]) -> Result<()> {
+    for (height, block) in blocks {
+        index_block(block, *height)?;
+        etl::index_extensions(*height, block);
+    }
+    Ok(())
+}
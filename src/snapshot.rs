@@ -0,0 +1,253 @@
+use crate::stateroot::{leaf, merkle_root, state_root};
+use crate::tables::STATE_ROOTS;
+use anyhow::{anyhow, Result};
+use bitcoin::hashes::{sha256, Hash};
+use metashrew_core::get_cache;
+use metashrew_core::index_pointer::IndexPointer;
+use metashrew_support::index_pointer::KeyValuePointer;
+use metashrew_support::utils::{consume_exact, consume_sized_int, is_empty};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+/*
+Snapshot layout, all integers little-endian:
+
+  header  magic "ALKSNAP\0" | version u32 | height u64 | state root [32] | content root [32]
+          | flags u32 | chunk count u32
+  chunk   entry count u32 | payload length u32 | sha256(payload) [32] | payload
+  payload (key length u32 | key | value length u32 | value)*, keys in ascending order
+  footer  sha256 over the header and every chunk hash
+
+The state root is the chained per-block root of the last block, which commits only to what
+each block wrote. The content root is the merkle root over every entry of the snapshot, so it
+is what an importer checks the snapshot against, having obtained it from a node it trusts.
+*/
+
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"ALKSNAP\0";
+pub const SNAPSHOT_VERSION: u32 = 2;
+/// Payload size at which a chunk is closed and a new one started.
+pub const SNAPSHOT_CHUNK_SIZE: usize = 1 << 20;
+
+const FLAG_TRACES: u32 = 1;
+
+/// Key prefixes of the protorune tables and of the alkanes index, including the sequence
+/// pointer under `/alkanes/sequence` and the state roots needed to keep the chain going.
+pub const SNAPSHOT_PREFIXES: &[&str] = &[
+    "/blockhash/byheight/",
+    "/height/",
+    "/runes",
+    "/runeid/byetching/",
+    "/etching/byruneid/",
+    "/txids/byheight",
+    "/txindex/byid",
+    "/output/byoutpoint/",
+    "/outpoint/",
+    "/alkanes",
+    "/seen-genesis",
+    "/blocktime/",
+    "/stateroot/byheight/",
+];
+
+/// Traces and events are only needed to serve the trace views, so they can be left out.
+pub const TRACE_PREFIXES: &[&str] = &["/trace/", "/events/"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub version: u32,
+    pub height: u64,
+    pub state_root: Vec<u8>,
+    pub content_root: Vec<u8>,
+    pub traces: bool,
+    pub chunks: u32,
+}
+
+impl SnapshotHeader {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = SNAPSHOT_MAGIC.to_vec();
+        data.extend(self.version.to_le_bytes());
+        data.extend(self.height.to_le_bytes());
+        data.extend(&self.state_root);
+        data.extend(&self.content_root);
+        data.extend((if self.traces { FLAG_TRACES } else { 0 }).to_le_bytes());
+        data.extend(self.chunks.to_le_bytes());
+        data
+    }
+    fn parse(cursor: &mut Cursor<Vec<u8>>) -> Result<Self> {
+        if consume_exact(cursor, SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+            return Err(anyhow!("not an alkanes snapshot"));
+        }
+        let version = consume_sized_int::<u32>(cursor)?;
+        if version != SNAPSHOT_VERSION {
+            return Err(anyhow!("unsupported snapshot version {}", version));
+        }
+        Ok(Self {
+            version,
+            height: consume_sized_int::<u64>(cursor)?,
+            state_root: consume_exact(cursor, 32)?,
+            content_root: consume_exact(cursor, 32)?,
+            traces: consume_sized_int::<u32>(cursor)? & FLAG_TRACES != 0,
+            chunks: consume_sized_int::<u32>(cursor)?,
+        })
+    }
+}
+
+pub fn is_snapshot_key(key: &[u8], traces: bool) -> bool {
+    let matches = |prefixes: &[&str]| prefixes.iter().any(|p| key.starts_with(p.as_bytes()));
+    matches(SNAPSHOT_PREFIXES) || (traces && matches(TRACE_PREFIXES))
+}
+
+/// Merkle root over the leaves of `entries`, which must be in ascending key order.
+pub fn content_root<'a>(entries: impl IntoIterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>) -> Vec<u8> {
+    merkle_root(
+        entries
+            .into_iter()
+            .map(|(key, value)| leaf(key, value))
+            .collect(),
+    )
+    .to_vec()
+}
+
+fn encode_chunk(entries: &[(&Vec<u8>, &Vec<u8>)]) -> (Vec<u8>, [u8; 32]) {
+    let mut payload = vec![];
+    for (key, value) in entries {
+        payload.extend((key.len() as u32).to_le_bytes());
+        payload.extend(key.iter());
+        payload.extend((value.len() as u32).to_le_bytes());
+        payload.extend(value.iter());
+    }
+    let hash = sha256::Hash::hash(&payload).to_byte_array();
+    let mut chunk = (entries.len() as u32).to_le_bytes().to_vec();
+    chunk.extend((payload.len() as u32).to_le_bytes());
+    chunk.extend(hash);
+    chunk.extend(payload);
+    (chunk, hash)
+}
+
+/// Serializes `state`, the snapshot keys and their values, as the state at `height`.
+pub fn encode_snapshot(
+    height: u64,
+    state_root: Vec<u8>,
+    traces: bool,
+    state: &BTreeMap<Vec<u8>, Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let mut chunks: Vec<Vec<(&Vec<u8>, &Vec<u8>)>> = vec![];
+    let mut size = SNAPSHOT_CHUNK_SIZE;
+    for (key, value) in state {
+        if size >= SNAPSHOT_CHUNK_SIZE {
+            chunks.push(vec![]);
+            size = 0;
+        }
+        chunks.last_mut().unwrap().push((key, value));
+        size += 8 + key.len() + value.len();
+    }
+    let header = SnapshotHeader {
+        version: SNAPSHOT_VERSION,
+        height,
+        state_root,
+        content_root: content_root(state),
+        traces,
+        chunks: chunks.len() as u32,
+    }
+    .serialize();
+    let mut checksum = header.clone();
+    let mut data = header;
+    for entries in chunks {
+        let (chunk, hash) = encode_chunk(&entries);
+        checksum.extend(hash);
+        data.extend(chunk);
+    }
+    data.extend(sha256::Hash::hash(&checksum).to_byte_array());
+    Ok(data)
+}
+
+/// Exports the index as of `height`, which must be the last block indexed.
+///
+/// The state is read from the key-value cache of this process, which holds the whole index
+/// only when every block was indexed here, as `alkanes-replay` and the tests do. Inside the
+/// metashrew host the cache holds just the keys touched since the program was loaded, so an
+/// export from there is not a complete snapshot.
+pub fn export_snapshot(height: u64, traces: bool) -> Result<Vec<u8>> {
+    if STATE_ROOTS.select_value::<u64>(height).get().is_empty() {
+        return Err(anyhow!("block {} has not been indexed", height));
+    }
+    if !STATE_ROOTS.select_value::<u64>(height + 1).get().is_empty() {
+        return Err(anyhow!("block {} is not the last block indexed", height));
+    }
+    let state = get_cache()
+        .iter()
+        .filter(|(key, _)| is_snapshot_key(key, traces))
+        .map(|(key, value)| (key.to_vec(), value.to_vec()))
+        .collect::<BTreeMap<Vec<u8>, Vec<u8>>>();
+    encode_snapshot(height, state_root(height), traces, &state)
+}
+
+/// Parses a snapshot, checking every chunk against its hash and the footer against the
+/// header and chunk hashes.
+pub fn decode_snapshot(data: &[u8]) -> Result<(SnapshotHeader, Vec<(Vec<u8>, Vec<u8>)>)> {
+    let mut cursor = Cursor::new(data.to_vec());
+    let header = SnapshotHeader::parse(&mut cursor)?;
+    let mut checksum = header.serialize();
+    let mut entries = vec![];
+    for index in 0..header.chunks {
+        let count = consume_sized_int::<u32>(&mut cursor)?;
+        let length = consume_sized_int::<u32>(&mut cursor)?;
+        let hash = consume_exact(&mut cursor, 32)?;
+        let payload = consume_exact(&mut cursor, length as usize)?;
+        if sha256::Hash::hash(&payload).to_byte_array().to_vec() != hash {
+            return Err(anyhow!("snapshot chunk {} does not match its hash", index));
+        }
+        checksum.extend(&hash);
+        let mut payload = Cursor::new(payload);
+        for _ in 0..count {
+            let key_length = consume_sized_int::<u32>(&mut payload)?;
+            let key = consume_exact(&mut payload, key_length as usize)?;
+            let value_length = consume_sized_int::<u32>(&mut payload)?;
+            let value = consume_exact(&mut payload, value_length as usize)?;
+            entries.push((key, value));
+        }
+        if !is_empty(&mut payload) {
+            return Err(anyhow!("snapshot chunk {} has trailing data", index));
+        }
+    }
+    if consume_exact(&mut cursor, 32)? != sha256::Hash::hash(&checksum).to_byte_array() {
+        return Err(anyhow!("snapshot checksum mismatch"));
+    }
+    if !is_empty(&mut cursor) {
+        return Err(anyhow!("snapshot has trailing data"));
+    }
+    Ok((header, entries))
+}
+
+/// Verifies every entry of a snapshot against `content_root`, as obtained from a source the
+/// importer trusts, then writes the entries to the index, which should be empty. The root is
+/// not taken from the snapshot itself, so a snapshot cannot vouch for its own contents, and
+/// nothing is written unless the whole snapshot verifies.
+pub fn import_snapshot(data: &[u8], content_root: &[u8]) -> Result<SnapshotHeader> {
+    let (header, entries) = decode_snapshot(data)?;
+    let root = self::content_root(entries.iter().map(|(key, value)| (key, value)));
+    if root != content_root {
+        return Err(anyhow!(
+            "snapshot content root for block {} is {}, expected {}",
+            header.height,
+            hex::encode(&root),
+            hex::encode(content_root)
+        ));
+    }
+    // indexing continues by chaining from the root stored for the snapshot's block
+    let root_key = STATE_ROOTS.select_value::<u64>(header.height).unwrap();
+    let stored_root = entries
+        .iter()
+        .find(|(key, _)| key == root_key.as_ref())
+        .map(|(_, value)| value.as_slice());
+    if stored_root != Some(header.state_root.as_slice()) {
+        return Err(anyhow!(
+            "snapshot does not store the expected state root for block {}",
+            header.height
+        ));
+    }
+    for (key, value) in entries {
+        IndexPointer::wrap(&key).set(Arc::new(value));
+    }
+    Ok(header)
+}
@@ -0,0 +1,154 @@
+use crate::message::AlkaneMessageContext;
+use crate::tables::STATE_ROOTS;
+use anyhow::Result;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::{Block, OutPoint};
+use metashrew_core::index_pointer::IndexPointer;
+use metashrew_support::index_pointer::KeyValuePointer;
+use once_cell::sync::Lazy;
+use protorune::journal;
+use protorune::message::MessageContext;
+use protorune::tables::{RuneTable, RUNES};
+use protorune_support::utils::outpoint_encode;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+// Keys under /alkanes written while indexing the current block. Their values are read back
+// once the block is done, so writes that were later rolled back commit to the old value.
+static TOUCHED: Lazy<Mutex<BTreeSet<Vec<u8>>>> = Lazy::new(|| Mutex::new(BTreeSet::new()));
+
+fn touch<T: KeyValuePointer>(pointer: &T) {
+    TOUCHED
+        .lock()
+        .unwrap()
+        .insert(pointer.unwrap().as_ref().clone());
+}
+
+/// Records `pointer` in the write journal and the block's state, and hands it back, for a
+/// pointer written right away.
+pub fn written<T: KeyValuePointer>(pointer: T) -> T {
+    touch(&pointer);
+    journal::written(pointer)
+}
+
+/// Records the list at `pointer` as `written` does for the length and the slot the next
+/// append fills, and hands it back, for a list appended to right away.
+pub fn appended<T: KeyValuePointer>(pointer: T) -> T {
+    touch(&pointer.length_key());
+    touch(&pointer.select_index(pointer.length()));
+    journal::appended(pointer)
+}
+
+pub fn reset() {
+    TOUCHED.lock().unwrap().clear();
+}
+
+// balances in the order they are stored, as (rune id, amount) pairs
+fn sheet_bytes(ptr: &IndexPointer) -> Vec<u8> {
+    let runes_ptr = ptr.keyword("/runes");
+    let balances_ptr = ptr.keyword("/balances");
+    let mut data = vec![];
+    for i in 0..runes_ptr.length() {
+        data.extend(runes_ptr.select_index(i).get().as_ref());
+        data.extend(balances_ptr.select_index(i).get().as_ref());
+    }
+    data
+}
+
+/// Every key/value pair the block may have written: alkanes storage, balances and binaries,
+/// and the balance sheets, in the runes and alkanes tables, of the outpoints the block
+/// created and of those it spent, which indexing cleared.
+pub fn block_state(block: &Block) -> Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+    let mut state = BTreeMap::new();
+    let mut keys = std::mem::take(&mut *TOUCHED.lock().unwrap());
+    keys.insert(
+        IndexPointer::from_keyword("/alkanes/sequence")
+            .unwrap()
+            .as_ref()
+            .clone(),
+    );
+    for key in keys {
+        let value = IndexPointer::wrap(&key).get().as_ref().clone();
+        state.insert(key, value);
+    }
+    let tables = [
+        RUNES.OUTPOINT_TO_RUNES.clone(),
+        RuneTable::for_protocol(AlkaneMessageContext::protocol_tag()).OUTPOINT_TO_RUNES,
+    ];
+    for tx in &block.txdata {
+        let txid = tx.compute_txid();
+        let created = (0..tx.output.len() as u32).map(|vout| OutPoint { txid, vout });
+        let spent = tx
+            .input
+            .iter()
+            .map(|input| input.previous_output)
+            .filter(|outpoint| !outpoint.is_null());
+        for outpoint in created.chain(spent) {
+            let outpoint = outpoint_encode(&outpoint)?;
+            for table in &tables {
+                let ptr = table.select(&outpoint);
+                state.insert(ptr.unwrap().as_ref().clone(), sheet_bytes(&ptr));
+            }
+        }
+    }
+    Ok(state)
+}
+
+pub fn leaf(key: &[u8], value: &[u8]) -> [u8; 32] {
+    let mut data = vec![0u8];
+    data.extend((key.len() as u32).to_le_bytes());
+    data.extend(key);
+    data.extend(value);
+    sha256::Hash::hash(&data).to_byte_array()
+}
+
+/// Binary merkle root over the leaves, promoting an odd node to the next level. An empty
+/// tree has the all-zero root.
+pub fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut data = vec![1u8];
+                    data.extend(left);
+                    data.extend(right);
+                    sha256::Hash::hash(&data).to_byte_array()
+                }
+                _ => pair[0],
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// The root stored for `height`, or all zeros if that block was not indexed.
+pub fn state_root(height: u64) -> Vec<u8> {
+    let root = STATE_ROOTS.select_value::<u64>(height).get();
+    if root.is_empty() {
+        vec![0u8; 32]
+    } else {
+        root.as_ref().clone()
+    }
+}
+
+/// Commits to the state the block wrote, chained with the previous block's root, and
+/// stores the result for `height`.
+pub fn commit_state_root(block: &Block, height: u64) -> Result<Vec<u8>> {
+    let leaves = block_state(block)?
+        .iter()
+        .map(|(key, value)| leaf(key, value))
+        .collect::<Vec<_>>();
+    let mut data = match height {
+        0 => vec![0u8; 32],
+        _ => state_root(height - 1),
+    };
+    data.extend(height.to_le_bytes());
+    data.extend(merkle_root(leaves));
+    let root = sha256::Hash::hash(&data).to_byte_array().to_vec();
+    journal::written(STATE_ROOTS.select_value::<u64>(height)).set(Arc::new(root.clone()));
+    Ok(root)
+}
@@ -6,3 +6,14 @@ pub static TRACES: Lazy<IndexPointer> = Lazy::new(|| IndexPointer::from_keyword(
 
 pub static TRACES_BY_HEIGHT: Lazy<IndexPointer> =
     Lazy::new(|| IndexPointer::from_keyword("/trace/"));
+
+pub static BLOCK_TIMES: Lazy<IndexPointer> =
+    Lazy::new(|| IndexPointer::from_keyword("/blocktime/"));
+
+pub static EVENTS: Lazy<IndexPointer> = Lazy::new(|| IndexPointer::from_keyword("/events/"));
+
+pub static STATE_ROOTS: Lazy<IndexPointer> =
+    Lazy::new(|| IndexPointer::from_keyword("/stateroot/byheight/"));
+
+pub static UNDO_LOGS: Lazy<IndexPointer> =
+    Lazy::new(|| IndexPointer::from_keyword("/undo/byheight/"));
@@ -0,0 +1,116 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::envelope::RawEnvelope;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use ordinals::Runestone;
+use protorune::protostone::Protostones;
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use protorune_support::protostone::Protostone;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// deploys the test alkane in the first protostone and reverts in the second
+fn deploy_then_revert_block(all_or_nothing: bool) -> Block {
+    let cellpacks: Vec<Cellpack> = vec![
+        Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        },
+        Cellpack {
+            target: AlkaneId { block: 2, tx: 1 },
+            inputs: vec![100],
+        },
+    ];
+    let mut tx = alkane_helpers::create_multiple_cellpack_with_witness(
+        RawEnvelope::from(alkanes_std_test_build::get_bytes()).to_gzipped_witness(),
+        cellpacks.clone(),
+        false,
+    );
+    tx.output[1].script_pubkey = (Runestone {
+        etching: None,
+        pointer: Some(0),
+        edicts: vec![],
+        mint: None,
+        protocol: cellpacks
+            .into_iter()
+            .map(|cellpack| Protostone {
+                message: cellpack.encipher(),
+                pointer: Some(0),
+                refund: Some(0),
+                edicts: vec![],
+                from: None,
+                fuel_limit: None,
+                all_or_nothing,
+                burn: None,
+                protocol_tag: 1,
+            })
+            .collect::<Vec<Protostone>>()
+            .encipher()
+            .ok(),
+    })
+    .encipher();
+    let mut test_block = create_block_with_coinbase_tx(840_000);
+    test_block.txdata.push(tx);
+    test_block
+}
+
+#[wasm_bindgen_test]
+fn test_all_or_nothing_reverts_every_message() -> Result<()> {
+    clear();
+    let block = deploy_then_revert_block(true);
+    index_block(&block, 840_000)?;
+    alkane_helpers::assert_token_id_has_no_deployment(AlkaneId { block: 2, tx: 1 })?;
+
+    // the deploy returned before the second message failed, and its trace now says so
+    let tx = block.txdata.last().unwrap();
+    let outpoint = OutPoint {
+        txid: tx.compute_txid(),
+        vout: tx.output.len() as u32 + 1,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let revert = trace_response.revert().unwrap();
+            assert!(revert.message.contains("all-or-nothing"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_all_or_nothing_is_ignored_before_v2() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    index_block(&deploy_then_revert_block(true), 840_000)?;
+    alkane_helpers::assert_binary_deployed_to_id(
+        AlkaneId { block: 2, tx: 1 },
+        alkanes_std_test_build::get_bytes(),
+    )?;
+    clear();
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_messages_are_independent_by_default() -> Result<()> {
+    clear();
+    index_block(&deploy_then_revert_block(false), 840_000)?;
+    alkane_helpers::assert_binary_deployed_to_id(
+        AlkaneId { block: 2, tx: 1 },
+        alkanes_std_test_build::get_bytes(),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::tests::std::{alkanes_std_owned_token_build, alkanes_std_test_build};
+use crate::vm::cache::BinaryCache;
+use alkanes_support::gz::compress;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use std::sync::Arc;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_binary_cache_reuses_and_invalidates() -> Result<()> {
+    let mut cache = BinaryCache::new();
+    let id = AlkaneId { block: 2, tx: 1 };
+    let compressed = Arc::new(compress(alkanes_std_test_build::get_bytes())?);
+
+    let binary = cache.binary(&id, compressed.clone())?;
+    assert_eq!(binary.as_ref(), &alkanes_std_test_build::get_bytes());
+    // an equal payload read again from the index hits the cache
+    let again = cache.binary(&id, Arc::new(compressed.as_ref().clone()))?;
+    assert!(Arc::ptr_eq(&binary, &again));
+
+    let (_, module) = cache.module(&binary)?;
+    let (_, cached) = cache.module(&binary)?;
+    assert!(Arc::ptr_eq(&module, &cached));
+
+    // a different binary under the same id replaces the entry
+    let redeployed = Arc::new(compress(alkanes_std_owned_token_build::get_bytes())?);
+    let replaced = cache.binary(&id, redeployed)?;
+    assert!(!Arc::ptr_eq(&binary, &replaced));
+    assert_eq!(
+        replaced.as_ref(),
+        &alkanes_std_owned_token_build::get_bytes()
+    );
+
+    let stats = cache.stats();
+    assert_eq!((stats.binary_hits, stats.binary_misses), (1, 2));
+    assert_eq!((stats.module_hits, stats.module_misses), (1, 1));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_deploy_module_is_reused_by_calls() -> Result<()> {
+    let mut cache = BinaryCache::new();
+    let id = AlkaneId { block: 2, tx: 1 };
+    let binary = cache.binary(
+        &id,
+        Arc::new(compress(alkanes_std_test_build::get_bytes())?),
+    )?;
+
+    // the module compiled to validate a deploy is the one its calls run
+    let (_, validated) = cache.deploy_module(&binary)?;
+    let (_, called) = cache.module(&binary)?;
+    assert!(Arc::ptr_eq(&validated, &called));
+    let stats = cache.stats();
+    assert_eq!((stats.module_hits, stats.module_misses), (1, 1));
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view::{plain_parcel_from_cellpack, simulate_parcel_with_call_tree};
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const FUEL: u64 = 1_000_000;
+
+// deploys the test alkane at [2, 1]
+fn deploy_test_alkane() -> Result<()> {
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_call_tree_charges_reverted_frames() -> Result<()> {
+    clear();
+    deploy_test_alkane()?;
+
+    // the alkane recovers from a nested call that reverts
+    let (result, call_tree) = simulate_parcel_with_call_tree(
+        &plain_parcel_from_cellpack(Cellpack {
+            target: AlkaneId { block: 2, tx: 1 },
+            inputs: vec![91, 2, 1, 1, 100],
+        }),
+        FUEL,
+    );
+    let (_, fuel_used) = result?;
+    let root = call_tree.unwrap();
+    assert_eq!(root.target, AlkaneId { block: 2, tx: 1 });
+    assert_eq!(root.opcode, 91);
+    assert!(!root.reverted);
+    assert_eq!(root.fuel_total, fuel_used);
+    assert_eq!(root.calls.len(), 1);
+
+    let reverted = &root.calls[0];
+    assert_eq!(reverted.opcode, 100);
+    assert!(reverted.reverted);
+    assert!(reverted.fuel_total > 0);
+    assert_eq!(reverted.fuel_self, reverted.fuel_total);
+    assert!(root.fuel_total > reverted.fuel_total);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_call_tree_of_failed_simulation() -> Result<()> {
+    clear();
+    deploy_test_alkane()?;
+
+    let (result, call_tree) = simulate_parcel_with_call_tree(
+        &plain_parcel_from_cellpack(Cellpack {
+            target: AlkaneId { block: 2, tx: 1 },
+            inputs: vec![20],
+        }),
+        FUEL,
+    );
+    assert!(result.is_err());
+    let root = call_tree.unwrap();
+    assert!(root.reverted);
+    assert_eq!(root.fuel_total, FUEL);
+    assert!(root.calls.is_empty());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn returned_data(block: &Block, i: usize) -> Result<Vec<u8>> {
+    let outpoint = OutPoint {
+        txid: block.txdata[i].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => Ok(trace_response.inner.data),
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_codehash_and_is_deployed() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![90, 2, 1],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![90, 2, 99],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let mut expected = vec![1u8];
+    expected.extend(sha256::Hash::hash(&alkanes_std_test_build::get_bytes()).to_byte_array());
+    assert_eq!(returned_data(&test_block, 1)?, expected);
+    assert_eq!(returned_data(&test_block, 2)?, vec![0u8]);
+
+    Ok(())
+}
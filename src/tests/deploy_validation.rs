@@ -0,0 +1,126 @@
+use crate::tests::std::alkanes_std_test_build;
+use crate::vm::validation::validate_binary;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::OutPoint;
+use std::sync::Arc;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// a module that only exports one page of memory
+fn memory_only_module() -> Vec<u8> {
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    module.extend([0x05, 0x03, 0x01, 0x00, 0x01]);
+    module.extend([0x07, 0x0a, 0x01, 0x06]);
+    module.extend(b"memory");
+    module.extend([0x02, 0x00]);
+    module
+}
+
+#[wasm_bindgen_test]
+fn test_validate_binary() -> Result<()> {
+    validate_binary(&Arc::new(alkanes_std_test_build::get_bytes()))?;
+    let err = validate_binary(&Arc::new(memory_only_module())).unwrap_err();
+    assert!(err.to_string().contains("__execute"));
+    assert!(validate_binary(&Arc::new(b"not wasm".to_vec())).is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_rejected_deploy_is_traced_and_keeps_sequence() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [memory_only_module(), alkanes_std_test_build::get_bytes()].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![0],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![90, 2, 1],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[1].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let message = String::from_utf8_lossy(&trace_response.inner.data[4..]).to_string();
+            assert!(message.contains("does not export function __execute"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+
+    // the valid deploy that follows still receives {2, 1}
+    let outpoint = OutPoint {
+        txid: test_block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data[0], 1);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_deploys_are_not_validated_before_v2() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [memory_only_module()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![0],
+        }]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    // the binary is stored and the call into it fails, as it did before validation
+    let outpoint = OutPoint {
+        txid: test_block.txdata[1].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let message = String::from_utf8_lossy(&trace_response.inner.data[4..]).to_string();
+            assert!(!message.contains("does not export function __execute"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    clear();
+    Ok(())
+}
@@ -69,6 +69,8 @@ fn test_edict_to_protomessage() -> Result<()> {
                                 message: vec![1, 0, 4],
                                 protocol_tag: 1,
                                 from: None,
+                                fuel_limit: None,
+                                all_or_nothing: false,
                                 burn: None,
                                 pointer: Some(6),
                                 refund: Some(6),
@@ -78,6 +80,8 @@ fn test_edict_to_protomessage() -> Result<()> {
                                 message: vec![1, 0, 4],
                                 protocol_tag: 1,
                                 from: None,
+                                fuel_limit: None,
+                                all_or_nothing: false,
                                 burn: None,
                                 refund: Some(6),
                                 pointer: Some(6),
@@ -88,6 +92,8 @@ fn test_edict_to_protomessage() -> Result<()> {
                                 protocol_tag: 1,
                                 burn: None,
                                 from: None,
+                                fuel_limit: None,
+                                all_or_nothing: false,
                                 refund: Some(7),
                                 pointer: Some(7),
                                 edicts: vec![ProtostoneEdict {
@@ -100,6 +106,8 @@ fn test_edict_to_protomessage() -> Result<()> {
                                 message: vec![2, 1, 3],
                                 protocol_tag: 1,
                                 from: None,
+                                fuel_limit: None,
+                                all_or_nothing: false,
                                 pointer: Some(1),
                                 burn: None,
                                 refund: Some(1),
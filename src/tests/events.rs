@@ -0,0 +1,147 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::proto;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::Hash;
+use bitcoin::OutPoint;
+use protobuf::{Message, MessageField};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_emit_is_traced_and_indexed() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    let alkane_id = AlkaneId { block: 2, tx: 1 };
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![82, 2, 7, 8],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![83, 1, 9],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let emitted = [7u128, 8u128]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect::<Vec<u8>>();
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[1].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let emits = trace_data
+        .0
+        .lock()
+        .expect("Mutex poisoned")
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::Emit(v) => Some(v.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(emits.len(), 1);
+    assert_eq!(emits[0].alkane, alkane_id);
+    assert_eq!(emits[0].topic, b"test".to_vec());
+    assert_eq!(emits[0].data, emitted);
+
+    // the reverted call is still traced, but its event is not indexed
+    let mut request = proto::alkanes::EventsRequest::new();
+    request.id = MessageField::some(alkane_id.clone().into());
+    request.topic = b"test".to_vec();
+    let response = view::events(&request.write_to_bytes()?)?;
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events[0].height, block_height as u64);
+    assert_eq!(response.events[0].data, emitted);
+    assert_eq!(
+        response.events[0].outpoint.txid,
+        test_block.txdata[1].compute_txid().as_byte_array().to_vec()
+    );
+
+    request.from_height = block_height as u64 + 1;
+    let response = view::events(&request.write_to_bytes()?)?;
+    assert_eq!(response.events.len(), 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_events_are_paged_by_height() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    let alkane_id = AlkaneId { block: 2, tx: 1 };
+
+    let deploy = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![82, 1, 0],
+        }]
+        .into(),
+    );
+    index_block(&deploy, block_height)?;
+    for i in 1..3 {
+        let block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+            [vec![]].into(),
+            [Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![82, 1, i],
+            }]
+            .into(),
+        );
+        index_block(&block, block_height + i as u32)?;
+    }
+
+    let mut request = proto::alkanes::EventsRequest::new();
+    request.id = MessageField::some(alkane_id.clone().into());
+    request.topic = b"test".to_vec();
+    request.limit = 2;
+    let response = view::events(&request.write_to_bytes()?)?;
+    assert_eq!(
+        response
+            .events
+            .iter()
+            .map(|event| event.height)
+            .collect::<Vec<_>>(),
+        vec![block_height as u64, block_height as u64 + 1]
+    );
+    assert_eq!(response.next, 2);
+
+    request.start = response.next;
+    let response = view::events(&request.write_to_bytes()?)?;
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events[0].height, block_height as u64 + 2);
+    assert_eq!(response.next, 0);
+
+    // the scan stops at the first height past to_height
+    request.start = 0;
+    request.limit = 0;
+    request.to_height = block_height as u64;
+    let response = view::events(&request.write_to_bytes()?)?;
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.next, 0);
+
+    Ok(())
+}
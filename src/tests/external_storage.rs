@@ -0,0 +1,69 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn returned_data(block: &Block, i: usize) -> Result<Vec<u8>> {
+    let outpoint = OutPoint {
+        txid: block.txdata[i].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => Ok(trace_response.inner.data),
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_load_external_storage() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [
+            alkanes_std_test_build::get_bytes(),
+            alkanes_std_test_build::get_bytes(),
+            vec![],
+        ]
+        .into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![84, 1, 5],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![89, 2, 1, 5],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 2 },
+                inputs: vec![89, 2, 1, 6],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    // {2, 2} reads the key {2, 1} stored in the first transaction
+    assert_eq!(returned_data(&test_block, 2)?, vec![1]);
+    // missing keys read as empty
+    assert_eq!(returned_data(&test_block, 3)?, Vec::<u8>::new());
+
+    Ok(())
+}
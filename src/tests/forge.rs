@@ -87,6 +87,8 @@ fn test_cant_forge_edicts() -> Result<()> {
     test_block.txdata.push(create_protostone_encoded_transaction(outpoint, vec![Protostone {
       protocol_tag: 1,
       from: None,
+      fuel_limit: None,
+      all_or_nothing: false,
       edicts: vec![ProtostoneEdict {
         id: ProtoruneRuneId {
           block: 2,
@@ -3,10 +3,11 @@ use alkanes_support::cellpack::Cellpack;
 use alkanes_support::id::AlkaneId;
 use alkanes_support::trace::{Trace, TraceEvent};
 use anyhow::Result;
-use bitcoin::OutPoint;
+use bitcoin::{Block, OutPoint};
 
 use crate::index_block;
 use crate::tests::helpers::{self as alkane_helpers};
+use crate::vm::fuel::FuelTank;
 use alkane_helpers::clear;
 use alkanes::view;
 #[allow(unused_imports)]
@@ -14,6 +15,9 @@ use metashrew_core::{
     println,
     stdio::{stdout, Write},
 };
+use ordinals::Runestone;
+use protorune::protostone::Protostones;
+use protorune_support::protostone::Protostone;
 use wasm_bindgen_test::wasm_bindgen_test;
 
 #[wasm_bindgen_test]
@@ -94,3 +98,137 @@ fn test_infinite_extcall_loop() -> Result<()> {
 
     Ok(())
 }
+
+// a deploy of the test contract followed by `cellpack` in a protostone carrying `fuel_limit`
+fn block_with_fuel_limit(cellpack: &Cellpack, fuel_limit: u64) -> Block {
+    let mut test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [cellpack.clone()].into(),
+    );
+
+    // rewrite the protostone so it carries a fuel limit
+    let tx = test_block.txdata.last_mut().unwrap();
+    tx.output[1].script_pubkey = (Runestone {
+        etching: None,
+        pointer: Some(0),
+        edicts: vec![],
+        mint: None,
+        protocol: vec![Protostone {
+            message: cellpack.encipher(),
+            pointer: Some(0),
+            refund: Some(0),
+            edicts: vec![],
+            from: None,
+            fuel_limit: Some(fuel_limit),
+            all_or_nothing: false,
+            burn: None,
+            protocol_tag: 1,
+        }]
+        .encipher()
+        .ok(),
+    })
+    .encipher();
+    test_block
+}
+
+#[wasm_bindgen_test]
+fn test_fuel_limit_caps_message() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    let fuel_limit = 500_000;
+
+    let infinite_exec_cellpack = Cellpack {
+        target: AlkaneId { block: 1, tx: 0 },
+        inputs: vec![20],
+    };
+
+    let test_block = block_with_fuel_limit(&infinite_exec_cellpack, fuel_limit);
+
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[0].clone() {
+        TraceEvent::EnterCall(context) => {
+            assert_eq!(context.fuel, fuel_limit);
+            assert_eq!(context.requested_fuel, Some(fuel_limit));
+        }
+        _ => panic!("Expected EnterCall variant, but got a different variant"),
+    }
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let data = String::from_utf8_lossy(&trace_response.inner.data);
+            assert!(data.contains("ALKANES: revert: all fuel consumed by WebAssembly"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+
+    // only the granted fuel is burned, the rest of the allocation is kept for the block
+    let tank = FuelTank::get_fuel_tank_copy().unwrap();
+    assert!(tank.transaction_fuel > 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_large_fuel_limit_does_not_starve_block() -> Result<()> {
+    clear();
+    let block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    FuelTank::initialize(&block, 840_000);
+    let tank = FuelTank::get_fuel_tank_copy().unwrap();
+    let (total, quarter) = (tank.block_fuel, tank.size / 4);
+
+    FuelTank::fuel_transaction(quarter, 0);
+    let allocated = FuelTank::get_fuel_tank_copy().unwrap().transaction_fuel;
+    // the grant is capped at the allocation and nothing more leaves the block
+    assert_eq!(FuelTank::request_fuel(u64::MAX), allocated);
+    let tank = FuelTank::get_fuel_tank_copy().unwrap();
+    assert_eq!(tank.block_fuel, total - tank.block_metered_fuel);
+
+    // a transaction of the same size that follows is allocated at least as much
+    FuelTank::refuel_block();
+    FuelTank::fuel_transaction(quarter, 1);
+    assert!(FuelTank::get_fuel_tank_copy().unwrap().transaction_fuel >= allocated);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_fuel_limit_is_ignored_before_v2() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let test_block = block_with_fuel_limit(
+        &Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        },
+        500_000,
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[0].clone() {
+        TraceEvent::EnterCall(context) => {
+            assert_eq!(context.requested_fuel, None);
+        }
+        _ => panic!("Expected EnterCall variant, but got a different variant"),
+    }
+    clear();
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::{hash160, sha256, Hash};
+use bitcoin::OutPoint;
+use sha3::{Digest, Keccak256};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_native_hashes() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let data: Vec<u128> = vec![1, 2, u128::MAX];
+    let mut inputs = vec![79, data.len() as u128];
+    inputs.extend(data.iter());
+    let hash_cellpack = Cellpack {
+        target: AlkaneId { block: 1, tx: 0 },
+        inputs,
+    };
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [hash_cellpack].into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+
+    let bytes = data
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect::<Vec<u8>>();
+    let mut expected = sha256::Hash::hash(&bytes).to_byte_array().to_vec();
+    expected.extend(hash160::Hash::hash(&bytes).to_byte_array());
+    expected.extend(Keccak256::digest(&bytes));
+
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data, expected);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
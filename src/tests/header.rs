@@ -0,0 +1,142 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::utils::MEDIAN_TIME_SPAN;
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// indexes a block at each height before `height` down to the eleven median-time-past reads
+fn index_history(height: u32, time: u32) -> Result<()> {
+    for h in height - MEDIAN_TIME_SPAN as u32..height {
+        let mut block = create_block_with_coinbase_tx(h);
+        block.header.time = time;
+        index_block(&block, h)?;
+    }
+    Ok(())
+}
+
+fn header_block() -> Block {
+    alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![88],
+        }]
+        .into(),
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_header_fields() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    let history_time = 1_700_000_000;
+    index_history(block_height, history_time)?;
+
+    let test_block = header_block();
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+
+    let mut expected = test_block.header.time.to_le_bytes().to_vec();
+    expected.extend(&history_time.to_le_bytes());
+    expected.extend(test_block.block_hash().as_byte_array());
+    expected.extend(test_block.header.prev_blockhash.as_byte_array());
+
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data, expected);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_header_requires_block_time_history() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    // only the last of the eleven preceding blocks is missing
+    index_history(block_height - 1, 1_700_000_000)?;
+
+    let test_block = header_block();
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let revert = trace_response.revert().expect("typed revert");
+            assert!(revert
+                .message
+                .contains("needs the time of block 839999, which is not indexed"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_header_median_time_past() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    // eleven blocks whose times are out of order, as block times may be
+    let times = [7, 3, 11, 1, 9, 5, 10, 2, 8, 4, 6].map(|t| 1_700_000_000 + t * 600);
+    for (i, time) in times.iter().enumerate() {
+        let mut block = create_block_with_coinbase_tx(block_height + i as u32);
+        block.header.time = *time;
+        index_block(&block, block_height + i as u32)?;
+    }
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![88],
+        }]
+        .into(),
+    );
+    index_block(&test_block, block_height + MEDIAN_TIME_SPAN as u32)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            let data = trace_response.inner.data;
+            assert_eq!(data[4..8], (1_700_000_000u32 + 6 * 600).to_le_bytes());
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
@@ -1,5 +1,11 @@
+use crate::indexer::index_block;
 use crate::message::AlkaneMessageContext;
+use crate::stateroot::state_root;
+use crate::undo::rollback_to;
 use crate::view;
+use crate::vm::schedule::{
+    reset_protocol_schedule, set_protocol_schedule, ProtocolRules, GENESIS_RULES, V2_RULES,
+};
 use alkanes_support::cellpack::Cellpack;
 use alkanes_support::envelope::RawEnvelope;
 use alkanes_support::gz::compress;
@@ -20,6 +26,7 @@ use metashrew_core::{
 use metashrew_support::index_pointer::KeyValuePointer;
 use metashrew_support::utils::consensus_encode;
 use ordinals::{Etching, Rune, Runestone};
+use protobuf::{Message, MessageField};
 use protorune::balance_sheet::load_sheet;
 use protorune::message::MessageContext;
 use protorune::protostone::Protostones;
@@ -27,6 +34,7 @@ use protorune::tables::RuneTable;
 use protorune::test_helpers::{create_block_with_coinbase_tx, get_address, ADDRESS1};
 use protorune_support::balance_sheet::BalanceSheet;
 use protorune_support::network::{set_network, NetworkParams};
+use protorune_support::proto::protorune::ProtorunesWalletRequest;
 use protorune_support::protostone::{Protostone, ProtostoneEdict};
 use std::str::FromStr;
 
@@ -92,6 +100,31 @@ pub fn configure_network() {
 pub fn clear() {
     clear_base();
     configure_network();
+    reset_protocol_schedule();
+}
+
+/// Height from which `schedule_v2()` applies V2_RULES; the usual test height of 840_000
+/// runs under the genesis rules.
+pub const V2_TEST_BLOCK: u64 = 850_000;
+
+// the v2 host functions stay available from genesis, so that the test contracts, which
+// import them, can run under the genesis rules
+static STAGED_RULES: &[ProtocolRules] = &[
+    ProtocolRules {
+        host_functions: V2_RULES.host_functions,
+        ..GENESIS_RULES
+    },
+    ProtocolRules {
+        height: V2_TEST_BLOCK,
+        host_functions: &[],
+        ..V2_RULES
+    },
+];
+
+/// Schedules V2_RULES, but for its host functions, at V2_TEST_BLOCK until the next
+/// `clear()`, for tests of behavior that differs before the upgrade.
+pub fn schedule_v2() {
+    set_protocol_schedule(STAGED_RULES);
 }
 
 #[cfg(test)]
@@ -247,6 +280,8 @@ pub fn create_multiple_cellpack_with_witness_and_txins_edicts(
                 pointer: Some(4),
                 refund: None,
                 from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
                 protocol_tag: 13, // this value must be 13 if protoburn
                 message: vec![],
             }],
@@ -260,6 +295,8 @@ pub fn create_multiple_cellpack_with_witness_and_txins_edicts(
                 refund: Some(0),
                 edicts: edicts.clone(),
                 from: None,
+                fuel_limit: None,
+                all_or_nothing: false,
                 burn: None,
                 protocol_tag: protocol_id as u128,
             })
@@ -462,3 +499,61 @@ pub fn assert_revert_context_at_index(
         ),
     }
 }
+
+/// Indexes `blocks` at consecutive heights starting from `start_height`.
+pub fn index_chain(start_height: u32, blocks: &[Block]) -> Result<()> {
+    for (i, block) in blocks.iter().enumerate() {
+        index_block(block, start_height + i as u32)?;
+    }
+    Ok(())
+}
+
+/// Rolls back every block from `fork_height` up and indexes `blocks` in their place.
+pub fn index_fork(fork_height: u32, blocks: &[Block]) -> Result<()> {
+    rollback_to(fork_height as u64 - 1)?;
+    index_chain(fork_height, blocks)
+}
+
+/// The alkanes protocol's protorunes_by_address response for each address, followed by the
+/// alkane_inventory response for each alkane, serialized.
+pub fn chain_views(addresses: &[String], alkanes: &[AlkaneId]) -> Result<Vec<Vec<u8>>> {
+    let mut views = vec![];
+    for address in addresses {
+        let mut request = ProtorunesWalletRequest::new();
+        request.wallet = address.clone().into_bytes();
+        request.protocol_tag = MessageField::some(AlkaneMessageContext::protocol_tag().into());
+        views.push(view::protorunes_by_address(&request.write_to_bytes()?)?.write_to_bytes()?);
+    }
+    for alkane in alkanes {
+        views.push(view::alkane_inventory(&alkane.clone().into())?.write_to_bytes()?);
+    }
+    Ok(views)
+}
+
+/// Indexes `common` then `stale` from `start_height`, reorgs onto `canonical` and asserts
+/// that the views and the state root match indexing `common` and `canonical` directly.
+pub fn assert_reorg_matches_canonical(
+    start_height: u32,
+    common: &[Block],
+    stale: &[Block],
+    canonical: &[Block],
+    addresses: &[String],
+    alkanes: &[AlkaneId],
+) -> Result<()> {
+    let fork_height = start_height + common.len() as u32;
+    let tip = (fork_height + canonical.len() as u32 - 1) as u64;
+
+    clear();
+    index_chain(start_height, common)?;
+    index_chain(fork_height, canonical)?;
+    let expected_views = chain_views(addresses, alkanes)?;
+    let expected_root = state_root(tip);
+
+    clear();
+    index_chain(start_height, common)?;
+    index_chain(fork_height, stale)?;
+    index_fork(fork_height, canonical)?;
+    assert_eq!(chain_views(addresses, alkanes)?, expected_views);
+    assert_eq!(state_root(tip), expected_root);
+    Ok(())
+}
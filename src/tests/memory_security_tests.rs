@@ -1,6 +1,7 @@
 use crate::index_block;
 use crate::tests::helpers::{self as alkane_helpers};
 use crate::tests::std::alkanes_std_test_build;
+use crate::vm::fuel::FUEL_PER_MEMORY_PAGE;
 use crate::vm::utils::{get_memory, read_arraybuffer};
 use alkanes_support::cellpack::Cellpack;
 use alkanes_support::id::AlkaneId;
@@ -137,6 +138,97 @@ fn test_malformed_transfer_parcel_extcall() -> Result<()> {
     Ok(())
 }
 
+// Deploys the test alkane at [2, 1] and has it grow `pages` pages in itself and in each of
+// `depth` nested self-calls
+fn nested_grow_block(pages: u128, depth: u128) -> bitcoin::Block {
+    let mut test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    test_block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![93, pages, depth],
+            }],
+            false,
+        ));
+    test_block
+}
+
+#[wasm_bindgen_test]
+fn test_nested_memory_growth_within_budget() -> Result<()> {
+    alkane_helpers::clear();
+    let block_height = 840_000;
+
+    let test_block = nested_grow_block(150, 3);
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = alkanes::view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    assert!(!trace_events
+        .iter()
+        .any(|event| matches!(event, TraceEvent::RevertContext(_))));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_nested_memory_growth_exceeds_budget() -> Result<()> {
+    alkane_helpers::clear();
+    let block_height = 840_000;
+
+    // every frame stays under MEMORY_LIMIT, but together they grow past the v2 memory budget
+    let test_block = nested_grow_block(150, 20);
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    alkane_helpers::assert_revert_context(&outpoint, "memory budget exceeded")?;
+
+    Ok(())
+}
+
+// Fuel the top-level call of `nested_grow_block(pages, 0)` reports when indexed on a fresh
+// tree
+fn nested_grow_fuel_used(pages: u128) -> Result<u64> {
+    alkane_helpers::clear();
+    let test_block = nested_grow_block(pages, 0);
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = alkanes::view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events.last() {
+        Some(TraceEvent::ReturnContext(response)) => Ok(response.fuel_used),
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_memory_growth_is_charged_fuel() -> Result<()> {
+    let without_growth = nested_grow_fuel_used(0)?;
+    let with_growth = nested_grow_fuel_used(150)?;
+    assert!(with_growth >= without_growth + 150 * FUEL_PER_MEMORY_PAGE);
+    alkane_helpers::clear();
+    Ok(())
+}
+
 // #[wasm_bindgen_test]
 // fn test_malformed_storage_map() -> Result<()> {
 //     alkane_helpers::clear();
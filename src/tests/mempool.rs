@@ -0,0 +1,189 @@
+use crate::index_block;
+use crate::mempool::simulate_mempool;
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::tests::std::alkanes_std_test_build;
+use crate::view;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::prevout::Prevouts;
+use alkanes_support::proto;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, OutPoint, Transaction, Witness};
+use metashrew_support::utils::consensus_encode;
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use protorune_support::balance_sheet::{BalanceSheetOperations, ProtoruneRuneId};
+use std::io::Cursor;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn index_deploy(block_height: u32) -> Result<()> {
+    alkane_helpers::clear();
+    let deploy = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    index_block(&deploy, block_height)
+}
+
+// mints `amount` of 2:1, spending `previous_output` when given
+fn mint_tx(amount: u128, previous_output: Option<OutPoint>) -> Transaction {
+    let cellpack = Cellpack {
+        target: AlkaneId { block: 2, tx: 1 },
+        inputs: vec![30, 2, 1, amount],
+    };
+    match previous_output {
+        Some(previous_output) => alkane_helpers::create_multiple_cellpack_with_witness_and_in(
+            Witness::new(),
+            vec![cellpack],
+            previous_output,
+            false,
+        ),
+        None => alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![cellpack],
+            false,
+        ),
+    }
+}
+
+fn output_amount(response: &proto::alkanes::MempoolSimulateResponse, outpoint: &OutPoint) -> u128 {
+    let output = response
+        .outputs
+        .iter()
+        .find(|output| {
+            output.outpoint.txid == outpoint.txid.as_byte_array().to_vec()
+                && output.outpoint.vout == outpoint.vout
+        })
+        .expect("outpoint is not among the simulated outputs");
+    output
+        .alkanes
+        .iter()
+        .filter(|transfer| transfer.id.block.lo == 2 && transfer.id.tx.lo == 1)
+        .map(|transfer| transfer.value.lo as u128)
+        .sum()
+}
+
+#[wasm_bindgen_test]
+fn test_mempool_simulation_matches_indexing() -> Result<()> {
+    let block_height = 840_000;
+    index_deploy(block_height)?;
+    let first = mint_tx(1000, None);
+    let first_out = OutPoint {
+        txid: first.compute_txid(),
+        vout: 0,
+    };
+    let second = mint_tx(500, Some(first_out));
+    let second_out = OutPoint {
+        txid: second.compute_txid(),
+        vout: 0,
+    };
+
+    let response = simulate_mempool(
+        block_height as u64,
+        &[consensus_encode(&first)?, consensus_encode(&second)?],
+    )?;
+    assert_eq!(response.height, block_height as u64 + 1);
+    assert!(response.failures.is_empty());
+    assert_eq!(output_amount(&response, &first_out), 1000);
+    assert_eq!(output_amount(&response, &second_out), 1500);
+    assert_eq!(response.traces.len(), 2);
+    assert_eq!(response.traces[1].outpoint.vout, 3);
+
+    // nothing the simulation wrote is left behind
+    let message_out = OutPoint {
+        txid: second.compute_txid(),
+        vout: 3,
+    };
+    assert!(view::trace(&message_out)?.is_empty());
+
+    // mining the transactions gives the balances the simulation reported
+    let mut block: Block = create_block_with_coinbase_tx(block_height + 1);
+    block.txdata.push(first);
+    block.txdata.push(second);
+    index_block(&block, block_height + 1)?;
+    let sheet = alkane_helpers::get_last_outpoint_sheet(&block)?;
+    assert_eq!(sheet.get_cached(&ProtoruneRuneId { block: 2, tx: 1 }), 1500);
+    assert!(!view::trace(&message_out)?.is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mempool_simulation_reports_failures() -> Result<()> {
+    let block_height = 840_000;
+    index_deploy(block_height)?;
+    let first = mint_tx(u128::MAX, None);
+    let second = mint_tx(
+        u128::MAX,
+        Some(OutPoint {
+            txid: first.compute_txid(),
+            vout: 0,
+        }),
+    );
+
+    let response = simulate_mempool(
+        block_height as u64,
+        &[
+            consensus_encode(&first)?,
+            consensus_encode(&second)?,
+            vec![0xde, 0xad],
+        ],
+    )?;
+    assert_eq!(response.failures.len(), 2);
+    // failures are ordered by transaction
+    let reverted = &response.failures[0];
+    assert_eq!(reverted.txindex, 1);
+    assert_eq!(
+        reverted.outpoint.txid,
+        second.compute_txid().as_byte_array().to_vec()
+    );
+    assert_eq!(reverted.outpoint.vout, 3);
+    assert!(reverted.error.contains("overflow error"));
+    // the typed revert is decoded, so its binary fields do not leak into the message
+    assert!(!reverted.error.contains('\0'));
+    let invalid = &response.failures[1];
+    assert_eq!(invalid.txindex, 2);
+    assert!(invalid.outpoint.is_none());
+    assert!(invalid.error.contains("invalid transaction"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mempool_simulation_reads_unconfirmed_prevouts() -> Result<()> {
+    let block_height = 840_000;
+    index_deploy(block_height)?;
+    let first = mint_tx(1000, None);
+    // reads the outputs spent by its inputs, the first of which is the first transaction's
+    let second = alkane_helpers::create_multiple_cellpack_with_witness_and_in(
+        Witness::new(),
+        vec![Cellpack {
+            target: AlkaneId { block: 2, tx: 1 },
+            inputs: vec![87],
+        }],
+        OutPoint {
+            txid: first.compute_txid(),
+            vout: 0,
+        },
+        false,
+    );
+
+    let response = simulate_mempool(
+        block_height as u64,
+        &[consensus_encode(&first)?, consensus_encode(&second)?],
+    )?;
+    assert!(response.failures.is_empty());
+    let trace: Trace = response.traces[1].trace.clone().unwrap().try_into()?;
+    let events = trace.0.lock().unwrap();
+    let prevouts = match events.last() {
+        Some(TraceEvent::ReturnContext(response)) => {
+            Prevouts::parse(&mut Cursor::new(response.inner.data.clone()))?
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    };
+    assert_eq!(prevouts.0, vec![Some(first.output[0].clone())]);
+    Ok(())
+}
@@ -12,26 +12,72 @@ pub mod abi_test;
 #[cfg(test)]
 pub mod alkane;
 #[cfg(test)]
+pub mod all_or_nothing;
+#[cfg(test)]
 pub mod arbitrary_alkane_mint;
 #[cfg(test)]
 pub mod auth_token;
 #[cfg(test)]
+pub mod binary_cache;
+#[cfg(test)]
+pub mod call_tree;
+#[cfg(test)]
+pub mod codehash;
+#[cfg(test)]
 pub mod crash;
 #[cfg(test)]
+pub mod deploy_validation;
+#[cfg(test)]
 pub mod edict_then_message;
 #[cfg(test)]
+pub mod events;
+#[cfg(test)]
+pub mod external_storage;
+#[cfg(test)]
 pub mod forge;
 #[cfg(test)]
 pub mod fuel;
 #[cfg(test)]
 pub mod genesis;
 #[cfg(test)]
+pub mod hashing;
+#[cfg(test)]
+pub mod header;
+#[cfg(test)]
 pub mod memory_security_tests;
 #[cfg(test)]
+pub mod mempool;
+#[cfg(test)]
 pub mod networks;
 #[cfg(test)]
+pub mod precompiles;
+#[cfg(test)]
+pub mod prevouts;
+#[cfg(test)]
+pub mod reentrancy;
+#[cfg(test)]
+pub mod reorg;
+#[cfg(test)]
+pub mod replay;
+#[cfg(test)]
+pub mod schedule;
+#[cfg(test)]
 pub mod serialization;
 #[cfg(test)]
+pub mod signatures;
+#[cfg(test)]
+pub mod snapshot;
+#[cfg(test)]
+pub mod static_call;
+#[cfg(test)]
+pub mod stateroot;
+#[cfg(test)]
+pub mod storage_keys;
+#[cfg(test)]
+pub mod try_call;
+#[cfg(test)]
+pub mod typed_revert;
+#[cfg(test)]
 pub mod vec_input_test;
 #[cfg(test)]
 pub mod view;
@@ -0,0 +1,246 @@
+use crate::tests::std::alkanes_std_test_build;
+use crate::view::{call_view, STATIC_FUEL};
+use crate::vm::precompiles::{
+    bignum, merkle, multicall, BIGNUM_PRECOMPILE_ID, MERKLE_PRECOMPILE_ID, MULTICALL_PRECOMPILE_ID,
+};
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::OutPoint;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn word(bytes: &[u8; 32]) -> Vec<u128> {
+    vec![
+        u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        u128::from_le_bytes(bytes[16..].try_into().unwrap()),
+    ]
+}
+
+fn u256(lo: u128, hi: u128) -> Vec<u8> {
+    let mut bytes = lo.to_le_bytes().to_vec();
+    bytes.extend(hi.to_le_bytes());
+    bytes
+}
+
+#[wasm_bindgen_test]
+fn test_bignum_precompile_in_message() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [vec![]].into(),
+        [Cellpack {
+            target: BIGNUM_PRECOMPILE_ID,
+            inputs: vec![bignum::ADD, u128::MAX, 0, 1, 0],
+        }]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[1].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[0].clone() {
+        TraceEvent::EnterCall(context) => assert_eq!(context.target, BIGNUM_PRECOMPILE_ID),
+        _ => panic!("Expected EnterCall variant, but got a different variant"),
+    }
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data, u256(0, 1));
+            assert!(trace_response.fuel_used > 0);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_bignum_precompile_view() -> Result<()> {
+    clear();
+    let mul = call_view(
+        &BIGNUM_PRECOMPILE_ID,
+        &vec![bignum::MUL, u128::MAX, 0, u128::MAX, 0],
+        STATIC_FUEL,
+    )?;
+    assert_eq!(mul, u256(1, u128::MAX - 1));
+    let mulmod = call_view(
+        &BIGNUM_PRECOMPILE_ID,
+        &vec![bignum::MULMOD, 7, 0, 8, 0, 5, 0],
+        STATIC_FUEL,
+    )?;
+    assert_eq!(mulmod, u256(1, 0));
+    assert!(call_view(
+        &BIGNUM_PRECOMPILE_ID,
+        &vec![bignum::DIV, 1, 0, 0, 0],
+        STATIC_FUEL
+    )
+    .is_err());
+    assert!(call_view(
+        &BIGNUM_PRECOMPILE_ID,
+        &vec![bignum::SUB, 0, 0, 1, 0],
+        STATIC_FUEL
+    )
+    .is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_merkle_precompile_view() -> Result<()> {
+    clear();
+    let leaves: Vec<[u8; 32]> = (0u8..4)
+        .map(|i| sha256d::Hash::hash(&[i]).to_byte_array())
+        .collect();
+    let node = |left: &[u8; 32], right: &[u8; 32]| {
+        sha256d::Hash::hash(&[left.as_slice(), right.as_slice()].concat()).to_byte_array()
+    };
+    let left = node(&leaves[0], &leaves[1]);
+    let right = node(&leaves[2], &leaves[3]);
+    let root = node(&left, &right);
+
+    // leaf 2 is a left child at level 0 and a right child at level 1
+    let proof = [word(&leaves[2]), vec![2, 2], word(&leaves[3]), word(&left)].concat();
+    let computed = call_view(
+        &MERKLE_PRECOMPILE_ID,
+        &[vec![merkle::ROOT], proof.clone()].concat(),
+        STATIC_FUEL,
+    )?;
+    assert_eq!(computed, root.to_vec());
+
+    let verified = call_view(
+        &MERKLE_PRECOMPILE_ID,
+        &[vec![merkle::VERIFY], word(&root), proof.clone()].concat(),
+        STATIC_FUEL,
+    )?;
+    assert_eq!(verified, vec![1u8]);
+
+    let rejected = call_view(
+        &MERKLE_PRECOMPILE_ID,
+        &[vec![merkle::VERIFY], word(&left), proof].concat(),
+        STATIC_FUEL,
+    )?;
+    assert_eq!(rejected, vec![0u8]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_multicall_precompile() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    // deploy the test alkane, then route a call to it and to the bignum precompile
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: MULTICALL_PRECOMPILE_ID,
+                inputs: vec![
+                    multicall::AGGREGATE,
+                    2,
+                    BIGNUM_PRECOMPILE_ID.block,
+                    BIGNUM_PRECOMPILE_ID.tx,
+                    5,
+                    bignum::ADD,
+                    2,
+                    0,
+                    3,
+                    0,
+                    2,
+                    1,
+                    1,
+                    50,
+                ],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    let targets: Vec<AlkaneId> = trace_events
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::EnterCall(context) => Some(context.target.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        targets,
+        vec![
+            MULTICALL_PRECOMPILE_ID,
+            BIGNUM_PRECOMPILE_ID,
+            AlkaneId { block: 2, tx: 1 }
+        ]
+    );
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            let data = trace_response.inner.data;
+            assert_eq!(data[..4], 32u32.to_le_bytes());
+            assert_eq!(data[4..36], u256(5, 0)[..]);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_unregistered_precompile_reverts() -> Result<()> {
+    clear();
+    assert!(call_view(&AlkaneId { block: 7, tx: 99 }, &vec![0], STATIC_FUEL).is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_precompiles_are_not_available_before_v2() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [vec![]].into(),
+        [Cellpack {
+            target: BIGNUM_PRECOMPILE_ID,
+            inputs: vec![bignum::ADD, 2, 0, 3, 0],
+        }]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[1].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(_) => {}
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    clear();
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::prevout::Prevouts;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+use std::io::Cursor;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn returned_prevouts(block: &Block, i: usize) -> Result<Prevouts> {
+    let outpoint = OutPoint {
+        txid: block.txdata[i].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            Prevouts::parse(&mut Cursor::new(trace_response.inner.data))
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_prevouts() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![87],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![87],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    // the first transaction spends an output the indexer has never seen
+    let prevouts = returned_prevouts(&test_block, 1)?;
+    assert_eq!(prevouts.0, vec![None]);
+
+    // the second spends the first output of the first transaction in the same block
+    let prevouts = returned_prevouts(&test_block, 2)?;
+    assert_eq!(
+        prevouts.0,
+        vec![Some(test_block.txdata[1].output[0].clone())]
+    );
+
+    Ok(())
+}
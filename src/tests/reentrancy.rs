@@ -0,0 +1,102 @@
+use crate::tests::std::{alkanes_std_reentrancy_test_build, alkanes_std_test_build};
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// deploys the test alkane at [2, 1] and the non-reentrant alkane at [2, 2], then has the
+// latter call out to the former with `inputs`
+fn call_out_block(inputs: Vec<u128>) -> Block {
+    let mut call_out_inputs = vec![0, 2, 1, inputs.len() as u128];
+    call_out_inputs.extend(inputs);
+    alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [
+            alkanes_std_test_build::get_bytes(),
+            alkanes_std_reentrancy_test_build::get_bytes(),
+            vec![],
+        ]
+        .into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![1],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 2 },
+                inputs: call_out_inputs,
+            },
+        ]
+        .into(),
+    )
+}
+
+fn last_trace_event(block: &Block) -> Result<TraceEvent> {
+    let outpoint = OutPoint {
+        txid: block.txdata[3].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    Ok(trace_events[trace_events.len() - 1].clone())
+}
+
+#[wasm_bindgen_test]
+fn test_reentrant_call_into_locked_alkane_reverts() -> Result<()> {
+    clear();
+    // the test alkane calls straight back into [2, 2]
+    let test_block = call_out_block(vec![31, 2, 2, 1, 1]);
+    index_block(&test_block, 840_000)?;
+    match last_trace_event(&test_block)? {
+        TraceEvent::RevertContext(trace_response) => {
+            let data = String::from_utf8_lossy(&trace_response.inner.data);
+            assert!(data.contains("re-entrant call into locked alkane [2, 2]"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_reentrant_call_is_allowed_before_call_guards() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let test_block = call_out_block(vec![31, 2, 2, 1, 1]);
+    index_block(&test_block, alkane_helpers::V2_TEST_BLOCK as u32 - 1)?;
+    match last_trace_event(&test_block)? {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data, vec![1]);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_locked_alkane_can_call_out() -> Result<()> {
+    clear();
+    let test_block = call_out_block(vec![99]);
+    index_block(&test_block, 840_000)?;
+    match last_trace_event(&test_block)? {
+        TraceEvent::ReturnContext(trace_response) => {
+            assert_eq!(trace_response.inner.data, vec![0x01, 0x02, 0x03, 0x04]);
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+    Ok(())
+}
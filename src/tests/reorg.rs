@@ -0,0 +1,123 @@
+use crate::index_block;
+use crate::stateroot::state_root;
+use crate::tables::STATE_ROOTS;
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::tests::std::alkanes_std_test_build;
+use crate::undo::{rollback_to, undo_log};
+use crate::view;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::envelope::RawEnvelope;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use bitcoin::{Block, OutPoint, Witness};
+use metashrew_support::index_pointer::KeyValuePointer;
+use protorune::test_helpers::{create_block_with_coinbase_tx, get_address, ADDRESS1};
+use protorune_support::balance_sheet::{BalanceSheetOperations, ProtoruneRuneId};
+use std::sync::Arc;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn deploy_block() -> Block {
+    alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    )
+}
+
+// deploys a second copy of the test alkane, spending the first deploy's output so the
+// transaction differs from the one in deploy_block()
+fn redeploy_block(deploy: &Block) -> Block {
+    let mut block = create_block_with_coinbase_tx(840_000);
+    block.txdata.push(
+        alkane_helpers::create_multiple_cellpack_with_witness_and_in(
+            RawEnvelope::from(alkanes_std_test_build::get_bytes()).to_gzipped_witness(),
+            vec![Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            }],
+            OutPoint {
+                txid: deploy.txdata[1].compute_txid(),
+                vout: 0,
+            },
+            false,
+        ),
+    );
+    block
+}
+
+fn mint_block(amount: u128) -> Block {
+    let mut block = alkane_helpers::init_with_multiple_cellpacks_with_tx([].into(), vec![]);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![30, 2, 1, amount],
+            }],
+            false,
+        ));
+    block
+}
+
+fn last_outpoint_amount(block: &Block) -> Result<u128> {
+    Ok(alkane_helpers::get_last_outpoint_sheet(block)?
+        .get_cached(&ProtoruneRuneId { block: 2, tx: 1 }))
+}
+
+#[wasm_bindgen_test]
+fn test_reorg_matches_canonical_chain() -> Result<()> {
+    let deploy = deploy_block();
+    alkane_helpers::assert_reorg_matches_canonical(
+        840_000,
+        &[deploy.clone()],
+        &[redeploy_block(&deploy), mint_block(2000)],
+        &[mint_block(500)],
+        &[get_address(&ADDRESS1().as_str()).to_string()],
+        &[AlkaneId { block: 2, tx: 1 }, AlkaneId { block: 2, tx: 2 }],
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_rollback_undoes_block() -> Result<()> {
+    alkane_helpers::clear();
+    let block_height = 840_000;
+    let mint = mint_block(1000);
+    index_block(&deploy_block(), block_height)?;
+    let root = state_root(block_height as u64);
+    index_block(&mint, block_height + 1)?;
+    assert_eq!(last_outpoint_amount(&mint)?, 1000);
+    let trace_outpoint = OutPoint {
+        txid: mint.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    assert!(!view::trace(&trace_outpoint)?.is_empty());
+
+    assert_eq!(rollback_to(block_height as u64)?, 1);
+    assert_eq!(last_outpoint_amount(&mint)?, 0);
+    assert!(view::trace(&trace_outpoint)?.is_empty());
+    assert_eq!(state_root(block_height as u64 + 1), vec![0u8; 32]);
+    assert_eq!(state_root(block_height as u64), root);
+    assert!(undo_log(block_height as u64 + 1)?.is_none());
+    assert_eq!(rollback_to(block_height as u64)?, 0);
+
+    // the undone block can be indexed again
+    index_block(&mint, block_height + 1)?;
+    assert_eq!(last_outpoint_amount(&mint)?, 1000);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_rollback_requires_undo_logs() -> Result<()> {
+    alkane_helpers::clear();
+    STATE_ROOTS
+        .select_value::<u64>(100)
+        .set(Arc::new(vec![1u8; 32]));
+    let err = rollback_to(99).unwrap_err();
+    assert!(err.to_string().contains("block 100 has no undo log"));
+    assert_eq!(state_root(100), vec![1u8; 32]);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::query::call_view;
+use crate::replay::{parse_block_file, replay};
+use crate::stateroot::state_root;
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const BLOCK_849236: &[u8] = include_bytes!("static/849236.txt");
+
+#[wasm_bindgen_test]
+fn test_parse_block_file_raw_and_hex() -> Result<()> {
+    let (height, block) = parse_block_file(BLOCK_849236)?;
+    assert_eq!(height, 849236);
+
+    let dump = hex::encode(BLOCK_849236) + "\n";
+    let (hex_height, hex_block) = parse_block_file(dump.as_bytes())?;
+    assert_eq!(hex_height, height);
+    assert_eq!(hex_block.block_hash(), block.block_hash());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_replay_serves_views() -> Result<()> {
+    alkane_helpers::clear();
+    let block_height = 840_000;
+    let deploy = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    replay(&[(block_height, deploy)])?;
+
+    let root = call_view("staterootbyheight", &block_height.to_le_bytes())?;
+    assert_eq!(root, state_root(block_height.into()));
+    assert_ne!(root, vec![0u8; 32]);
+    assert!(call_view("nosuchview", &block_height.to_le_bytes()).is_err());
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::network::genesis::GENESIS_BLOCK;
+use crate::tests::helpers::{self as alkane_helpers, V2_TEST_BLOCK};
+use crate::tests::std::alkanes_std_test_build;
+use crate::vm::cache::new_engine;
+use crate::vm::fuel::{FuelTank, VirtualFuelBytes, TOTAL_FUEL};
+use crate::vm::schedule::{
+    protocol_rules, protocol_schedule, ProtocolRules, ProtocolSchedule, GENESIS_RULES, V2_BLOCK,
+    V2_RULES,
+};
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+use wasmi::Module;
+
+static UPGRADED: &[ProtocolRules] = &[
+    ProtocolRules {
+        height: 100,
+        ..GENESIS_RULES
+    },
+    ProtocolRules {
+        name: "upgrade",
+        height: 200,
+        total_fuel: 2 * TOTAL_FUEL,
+        host_functions: &["__request_context"],
+        strict_static_calls: false,
+        ..GENESIS_RULES
+    },
+];
+
+static STAGED: &[ProtocolRules] = &[
+    GENESIS_RULES,
+    ProtocolRules {
+        height: V2_TEST_BLOCK,
+        ..V2_RULES
+    },
+];
+
+#[wasm_bindgen_test]
+fn test_schedule_selects_rules_by_height() -> Result<()> {
+    let schedule = ProtocolSchedule::new(UPGRADED);
+    assert!(!schedule.is_active(99));
+    assert!(schedule.is_active(100));
+    assert_eq!(schedule.at(0).name, "genesis");
+    assert_eq!(schedule.at(199).name, "genesis");
+    assert_eq!(schedule.at(200).name, "upgrade");
+    assert_eq!(schedule.at(u64::MAX).total_fuel, 2 * TOTAL_FUEL);
+    assert_eq!(schedule.get("upgrade").unwrap().height, 200);
+    assert!(schedule.get("missing").is_none());
+
+    // the network's own schedule starts at its genesis block
+    assert!(protocol_schedule().is_active(GENESIS_BLOCK));
+    assert_eq!(protocol_rules(V2_BLOCK), &V2_RULES);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_v2_rules_gate_post_genesis_features() -> Result<()> {
+    alkane_helpers::clear();
+    alkane_helpers::schedule_v2();
+    let genesis = protocol_rules(V2_TEST_BLOCK - 1);
+    assert_eq!(genesis.name, "genesis");
+    assert!(!genesis.strict_static_calls);
+    assert_eq!(genesis.fuel_per_memory_page, 0);
+    assert_eq!(genesis.memory_budget, usize::MAX);
+    assert!(!genesis.storage_key_index);
+    assert!(!genesis.call_guards);
+    let v2 = protocol_rules(V2_TEST_BLOCK);
+    assert_eq!(v2.name, "v2");
+    assert!(v2.strict_static_calls);
+    assert_eq!(v2.fuel_per_memory_page, V2_RULES.fuel_per_memory_page);
+    assert!(v2.memory_budget < usize::MAX);
+    assert!(v2.storage_key_index);
+    assert!(v2.call_guards);
+    alkane_helpers::clear();
+
+    let schedule = ProtocolSchedule::new(STAGED);
+    for name in ["__sha256", "__emit", "__header", "__codehash", "__try_call"] {
+        assert!(!schedule.host_function_active(name, V2_TEST_BLOCK - 1));
+        assert!(schedule.host_function_active(name, V2_TEST_BLOCK));
+    }
+    assert!(schedule.host_function_active("__call", V2_TEST_BLOCK - 1));
+
+    // a contract importing a v2 host function cannot be deployed before the upgrade
+    let module = Module::new(&new_engine(), &alkanes_std_test_build::get_bytes())?;
+    let err = schedule
+        .check_imports(&module, V2_TEST_BLOCK - 1)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("is not available until block 850000 (v2)"));
+    schedule.check_imports(&module, V2_TEST_BLOCK)?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::secp256k1::{Keypair, Message, PublicKey, Secp256k1, SecretKey};
+use bitcoin::OutPoint;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn to_inputs(bytes: &[u8]) -> Vec<u128> {
+    let mut values = vec![bytes.len().div_ceil(16) as u128];
+    values.extend(bytes.chunks(16).map(|chunk| {
+        let mut padded = [0u8; 16];
+        padded[..chunk.len()].copy_from_slice(chunk);
+        u128::from_le_bytes(padded)
+    }));
+    values
+}
+
+fn signature_cellpack(
+    target: AlkaneId,
+    opcode: u128,
+    pubkey: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Cellpack {
+    let mut inputs = vec![opcode];
+    inputs.extend(to_inputs(pubkey));
+    inputs.extend(to_inputs(message));
+    inputs.extend(to_inputs(signature));
+    Cellpack { target, inputs }
+}
+
+#[wasm_bindgen_test]
+fn test_verify_signatures() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[0x11; 32])?;
+    let keypair = Keypair::from_secret_key(&secp, &secret_key);
+    let (xonly, _parity) = keypair.x_only_public_key();
+    let pubkey = PublicKey::from_secret_key(&secp, &secret_key);
+
+    let digest = sha256::Hash::hash(b"alkanes permit").to_byte_array();
+    let other_digest = sha256::Hash::hash(b"alkanes voucher").to_byte_array();
+    let message = Message::from_digest(digest);
+    let schnorr_signature = secp.sign_schnorr_no_aux_rand(&message, &keypair);
+    let ecdsa_signature = secp.sign_ecdsa(&message, &secret_key);
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![], vec![]].into(),
+        [
+            signature_cellpack(
+                AlkaneId { block: 1, tx: 0 },
+                80,
+                &xonly.serialize(),
+                &digest,
+                schnorr_signature.as_ref(),
+            ),
+            signature_cellpack(
+                AlkaneId { block: 2, tx: 1 },
+                81,
+                &pubkey.serialize(),
+                &digest,
+                &ecdsa_signature.serialize_compact(),
+            ),
+            signature_cellpack(
+                AlkaneId { block: 2, tx: 1 },
+                80,
+                &xonly.serialize(),
+                &other_digest,
+                schnorr_signature.as_ref(),
+            ),
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    for (i, expected) in [1u8, 1u8, 0u8].into_iter().enumerate() {
+        let outpoint = OutPoint {
+            txid: test_block.txdata[i + 1].compute_txid(),
+            vout: 3,
+        };
+        let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+        let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+        match trace_events[trace_events.len() - 1].clone() {
+            TraceEvent::ReturnContext(trace_response) => {
+                assert_eq!(trace_response.inner.data, vec![expected]);
+            }
+            _ => panic!("Expected ReturnContext variant, but got a different variant"),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::index_block;
+use crate::snapshot::{
+    content_root, decode_snapshot, encode_snapshot, export_snapshot, import_snapshot,
+    SNAPSHOT_VERSION,
+};
+use crate::stateroot::state_root;
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use bitcoin::{Block, Witness};
+use protorune_support::balance_sheet::{BalanceSheetOperations, ProtoruneRuneId};
+use std::collections::BTreeMap;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn mint_block(amount: u128) -> Block {
+    let mut block = alkane_helpers::init_with_multiple_cellpacks_with_tx([].into(), vec![]);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![30, 2, 1, amount],
+            }],
+            false,
+        ));
+    block
+}
+
+fn index_history(block_height: u32) -> Result<()> {
+    alkane_helpers::clear();
+    let deploy = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    index_block(&deploy, block_height)?;
+    index_block(&mint_block(1000), block_height + 1)?;
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_snapshot_restores_index() -> Result<()> {
+    let block_height = 840_000;
+    let tip = block_height as u64 + 1;
+    let next = mint_block(500);
+
+    index_history(block_height)?;
+    let snapshot = export_snapshot(tip, false)?;
+    let snapshot_root = decode_snapshot(&snapshot)?.0.content_root;
+    index_block(&next, block_height + 2)?;
+    let expected_sheet = alkane_helpers::get_last_outpoint_sheet(&next)?;
+    let expected_root = state_root(tip + 1);
+
+    alkane_helpers::clear();
+    let header = import_snapshot(&snapshot, &snapshot_root)?;
+    assert_eq!(header.version, SNAPSHOT_VERSION);
+    assert_eq!(header.height, tip);
+    assert!(!header.traces);
+
+    // indexing continues from the snapshot exactly as it did from the full history
+    index_block(&next, block_height + 2)?;
+    let sheet = alkane_helpers::get_last_outpoint_sheet(&next)?;
+    assert_eq!(
+        sheet.get_cached(&ProtoruneRuneId { block: 2, tx: 1 }),
+        expected_sheet.get_cached(&ProtoruneRuneId { block: 2, tx: 1 })
+    );
+    assert_eq!(state_root(tip + 1), expected_root);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_snapshot_traces_are_optional() -> Result<()> {
+    let block_height = 840_000;
+    index_history(block_height)?;
+    let tip = block_height as u64 + 1;
+
+    let (_, without) = decode_snapshot(&export_snapshot(tip, false)?)?;
+    assert!(!without.iter().any(|(key, _)| key.starts_with(b"/trace/")));
+    let (header, with) = decode_snapshot(&export_snapshot(tip, true)?)?;
+    assert!(header.traces);
+    assert!(with.iter().any(|(key, _)| key.starts_with(b"/trace/")));
+    assert!(with
+        .iter()
+        .any(|(key, _)| key.starts_with(b"/alkanes/sequence")));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_snapshot_rejects_corruption() -> Result<()> {
+    let block_height = 840_000;
+    index_history(block_height)?;
+    let tip = block_height as u64 + 1;
+    // only the last block indexed can be exported
+    assert!(export_snapshot(tip - 1, false).is_err());
+    let snapshot = export_snapshot(tip, false)?;
+    let root = decode_snapshot(&snapshot)?.0.content_root;
+
+    let mut corrupted = snapshot.clone();
+    let last = corrupted.len() - 40;
+    corrupted[last] ^= 0xff;
+    let err = import_snapshot(&corrupted, &root).unwrap_err();
+    assert!(err.to_string().contains("does not match its hash"));
+
+    let mut wrong_version = snapshot.clone();
+    wrong_version[8] = 0xff;
+    let err = import_snapshot(&wrong_version, &root).unwrap_err();
+    assert!(err.to_string().contains("unsupported snapshot version"));
+
+    let err = import_snapshot(&snapshot[..snapshot.len() - 1], &root).unwrap_err();
+    assert!(!err.to_string().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_snapshot_is_verified_against_trusted_root() -> Result<()> {
+    let block_height = 840_000;
+    index_history(block_height)?;
+    let tip = block_height as u64 + 1;
+    let snapshot = export_snapshot(tip, false)?;
+    let (header, entries) = decode_snapshot(&snapshot)?;
+    let root = header.content_root.clone();
+    let expected_state_root = state_root(tip);
+
+    alkane_helpers::clear();
+    let err = import_snapshot(&snapshot, &[0u8; 32]).unwrap_err();
+    assert!(err.to_string().contains("snapshot content root"));
+    // nothing was written by the rejected import
+    assert_eq!(state_root(tip), vec![0u8; 32]);
+
+    // re-encoding after tampering with any entry gives valid checksums but a different root
+    let mut tampered: BTreeMap<Vec<u8>, Vec<u8>> = entries.iter().cloned().collect();
+    let (_, value) = tampered
+        .iter_mut()
+        .find(|(key, _)| key.starts_with(b"/alkanes/"))
+        .unwrap();
+    value.push(0);
+    let forged = encode_snapshot(header.height, header.state_root.clone(), false, &tampered)?;
+    let err = import_snapshot(&forged, &root).unwrap_err();
+    assert!(err.to_string().contains("snapshot content root"));
+    assert_eq!(state_root(tip), vec![0u8; 32]);
+
+    // a snapshot whose stored root disagrees with its header is rejected as well
+    let mut state: BTreeMap<Vec<u8>, Vec<u8>> = entries.into_iter().collect();
+    state.retain(|key, _| !key.starts_with(b"/stateroot/byheight/"));
+    let forged = encode_snapshot(header.height, header.state_root.clone(), false, &state)?;
+    let err = import_snapshot(&forged, &content_root(&state)).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("does not store the expected state root"));
+    assert_eq!(state_root(tip), vec![0u8; 32]);
+
+    import_snapshot(&snapshot, &root)?;
+    assert_eq!(state_root(tip), expected_state_root);
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::index_block;
+use crate::message::AlkaneMessageContext;
+use crate::stateroot::{self, merkle_root, state_root};
+use crate::tests::helpers::{self as alkane_helpers};
+use crate::tests::std::alkanes_std_test_build;
+use crate::utils::{balance_pointer, pipe_storagemap_to};
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::storage::StorageMap;
+use anyhow::Result;
+use bitcoin::{Block, OutPoint, Witness};
+use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
+use metashrew_support::index_pointer::KeyValuePointer;
+use protorune::message::MessageContext;
+use protorune::tables::{RuneTable, RUNES};
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use protorune_support::utils::outpoint_encode;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn deploy_block() -> Block {
+    alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    )
+}
+
+fn mint_block(amount: u128) -> Block {
+    let mut block = alkane_helpers::init_with_multiple_cellpacks_with_tx([].into(), vec![]);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![30, 2, 1, amount],
+            }],
+            false,
+        ));
+    block
+}
+
+// indexes the blocks from a clean index and returns the root at each height
+fn roots(blocks: &[Block], height: u32) -> Result<Vec<Vec<u8>>> {
+    alkane_helpers::clear();
+    let mut result = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        index_block(block, height + i as u32)?;
+        result.push(state_root((height + i as u32).into()));
+    }
+    Ok(result)
+}
+
+#[wasm_bindgen_test]
+fn test_state_root_is_deterministic() -> Result<()> {
+    let block_height = 840_000;
+    let blocks = [deploy_block(), mint_block(1000)];
+
+    let first = roots(&blocks, block_height)?;
+    let second = roots(&blocks, block_height)?;
+    assert_eq!(first, second);
+    assert_eq!(first[0].len(), 32);
+    assert_ne!(first[0], first[1]);
+    // nothing was indexed above the last block
+    assert_eq!(state_root(block_height as u64 + 2), vec![0u8; 32]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_state_root_pinpoints_divergence() -> Result<()> {
+    let block_height = 840_000;
+    let deploy = deploy_block();
+
+    let expected = roots(
+        &[deploy.clone(), mint_block(1000), mint_block(5)],
+        block_height,
+    )?;
+    let diverged = roots(&[deploy, mint_block(2000), mint_block(5)], block_height)?;
+    assert_eq!(expected[0], diverged[0]);
+    assert_ne!(expected[1], diverged[1]);
+    // later roots are chained to the diverging block even when they index the same data
+    assert_ne!(expected[2], diverged[2]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_state_root_covers_key_lists() -> Result<()> {
+    alkane_helpers::clear();
+    stateroot::reset();
+    let alkane = AlkaneId { block: 2, tx: 1 };
+    let mut storage = StorageMap::default();
+    storage.set(b"/key".to_vec(), b"value".to_vec());
+    pipe_storagemap_to(
+        &storage,
+        &mut IndexPointer::from_keyword("/alkanes/").select(&alkane.clone().into()),
+        840_000,
+    );
+    let mut atomic = AtomicPointer::default();
+    stateroot::written(balance_pointer(&mut atomic, &alkane, &alkane)).set_value::<u128>(1);
+    balance_pointer(&mut atomic, &alkane, &alkane);
+    atomic.commit();
+
+    let keys = stateroot::block_state(&create_block_with_coinbase_tx(840_000))?
+        .into_keys()
+        .map(|key| String::from_utf8_lossy(&key).to_string())
+        .collect::<Vec<_>>();
+    for part in [
+        "/storage/",
+        "/storagekeys/",
+        "/storagekeyindex/",
+        "/inventory/",
+    ] {
+        assert!(
+            keys.iter().any(|key| key.contains(part)),
+            "{} is not committed",
+            part
+        );
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_state_root_covers_writes_and_spent_sheets() -> Result<()> {
+    alkane_helpers::clear();
+    stateroot::reset();
+    let alkane = AlkaneId { block: 2, tx: 1 };
+    let mut atomic = AtomicPointer::default();
+    let read = balance_pointer(&mut atomic, &alkane, &alkane);
+    assert_eq!(read.get_value::<u128>(), 0);
+    atomic.commit();
+
+    let mut block = create_block_with_coinbase_tx(840_000);
+    let spent = OutPoint {
+        txid: block.txdata[0].compute_txid(),
+        vout: 0,
+    };
+    let mut spend = block.txdata[0].clone();
+    spend.input[0].previous_output = spent;
+    block.txdata.push(spend);
+    let keys = stateroot::block_state(&block)?;
+    // a key that was only read is not part of the block's state
+    assert!(!keys.contains_key(read.unwrap().as_ref()));
+    let spent = outpoint_encode(&spent)?;
+    for table in [
+        RUNES.OUTPOINT_TO_RUNES.clone(),
+        RuneTable::for_protocol(AlkaneMessageContext::protocol_tag()).OUTPOINT_TO_RUNES,
+    ] {
+        assert!(keys.contains_key(table.select(&spent).unwrap().as_ref()));
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_merkle_root() -> Result<()> {
+    assert_eq!(merkle_root(vec![]), [0u8; 32]);
+    assert_eq!(merkle_root(vec![[7u8; 32]]), [7u8; 32]);
+    let two = merkle_root(vec![[1u8; 32], [2u8; 32]]);
+    assert_ne!(two, merkle_root(vec![[2u8; 32], [1u8; 32]]));
+    // an odd leaf is carried up unchanged
+    assert_eq!(
+        merkle_root(vec![[1u8; 32], [2u8; 32], [3u8; 32]]),
+        merkle_root(vec![two, [3u8; 32]])
+    );
+    Ok(())
+}
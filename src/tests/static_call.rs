@@ -0,0 +1,118 @@
+use crate::tests::std::alkanes_std_test_build;
+use crate::vm::precompiles::{multicall, MULTICALL_PRECOMPILE_ID};
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// deploys the test alkane, then has it staticcall `target` with `inputs`
+fn static_call_block(target: AlkaneId, inputs: Vec<u128>) -> Block {
+    let mut static_inputs = vec![33, target.block, target.tx, inputs.len() as u128];
+    static_inputs.extend(inputs);
+    alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: static_inputs,
+            },
+        ]
+        .into(),
+    )
+}
+
+// deploys the test alkane, then has it staticcall itself with `inputs`
+fn static_self_call_block(inputs: Vec<u128>) -> Block {
+    static_call_block(AlkaneId { block: 2, tx: 1 }, inputs)
+}
+
+fn reverted_with(block: &Block, message: &str) -> Result<bool> {
+    let outpoint = OutPoint {
+        txid: block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    Ok(trace_events.iter().any(|event| match event {
+        TraceEvent::RevertContext(trace_response) => {
+            String::from_utf8_lossy(&trace_response.inner.data).contains(message)
+        }
+        _ => false,
+    }))
+}
+
+#[wasm_bindgen_test]
+fn test_static_call_rejects_storage_writes() -> Result<()> {
+    clear();
+    let test_block = static_self_call_block(vec![84, 1, 7]);
+    index_block(&test_block, 840_000)?;
+    assert!(reverted_with(
+        &test_block,
+        "static call attempted to write"
+    )?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_static_call_rejects_nested_call() -> Result<()> {
+    clear();
+    let test_block = static_self_call_block(vec![31, 2, 1, 1, 99]);
+    index_block(&test_block, 840_000)?;
+    assert!(reverted_with(
+        &test_block,
+        "ALKANES: revert: static call attempted a nested __call"
+    )?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_static_call_allows_reads() -> Result<()> {
+    clear();
+    let test_block = static_self_call_block(vec![99]);
+    index_block(&test_block, 840_000)?;
+    assert!(!reverted_with(&test_block, "static call attempted")?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_static_call_rejects_emit() -> Result<()> {
+    clear();
+    let test_block = static_self_call_block(vec![82, 1, 7]);
+    index_block(&test_block, 840_000)?;
+    assert!(reverted_with(
+        &test_block,
+        "ALKANES: revert: static call attempted to __emit"
+    )?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_static_call_rejects_multicall() -> Result<()> {
+    clear();
+    let test_block = static_call_block(
+        MULTICALL_PRECOMPILE_ID,
+        vec![multicall::AGGREGATE, 1, 2, 1, 1, 99],
+    );
+    index_block(&test_block, 840_000)?;
+    assert!(reverted_with(
+        &test_block,
+        "ALKANES: revert: static call attempted a nested __call through multicall"
+    )?);
+    Ok(())
+}
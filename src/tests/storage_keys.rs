@@ -0,0 +1,128 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::storage::StorageKeyPage;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{Block, OutPoint};
+use std::io::Cursor;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn storage_key(key: u128) -> Vec<u8> {
+    let mut k = b"/k/".to_vec();
+    k.extend(key.to_le_bytes());
+    k
+}
+
+fn returned_page(block: &Block, i: usize) -> Result<StorageKeyPage> {
+    let outpoint = OutPoint {
+        txid: block.txdata[i].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            StorageKeyPage::parse(&mut Cursor::new(trace_response.inner.data))
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_storage_key_listing_and_deletion() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+    let alkane_id = AlkaneId { block: 2, tx: 1 };
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![], vec![], vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![84, 3, 1, 2, 3],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![85, 2],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![86, 0, 10],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![86, 0, 1],
+            },
+        ]
+        .into(),
+    );
+
+    index_block(&test_block, block_height)?;
+
+    // the deleted key is dropped from the index and from storage
+    let page = returned_page(&test_block, 3)?;
+    assert_eq!(page.keys, vec![storage_key(1), storage_key(3)]);
+    assert_eq!(page.next, None);
+
+    let page = returned_page(&test_block, 4)?;
+    assert_eq!(page.keys, vec![storage_key(1)]);
+    assert_eq!(page.next, Some(1));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_storage_keys_stored_before_the_index_are_not_listed() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let alkane_id = AlkaneId { block: 2, tx: 1 };
+
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![84, 2, 1, 2],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![86, 0, 10],
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, alkane_helpers::V2_TEST_BLOCK as u32 - 1)?;
+    assert!(returned_page(&test_block, 2)?.keys.is_empty());
+
+    // once the index is active, a key is listed when it is written again
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [vec![], vec![]].into(),
+        [
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![84, 1, 2],
+            },
+            Cellpack {
+                target: alkane_id.clone(),
+                inputs: vec![86, 0, 10],
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, alkane_helpers::V2_TEST_BLOCK as u32)?;
+    assert_eq!(returned_page(&test_block, 2)?.keys, vec![storage_key(2)]);
+
+    clear();
+    Ok(())
+}
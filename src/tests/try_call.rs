@@ -0,0 +1,111 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::{OutPoint, Witness};
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+use alkanes::view::{plain_parcel_from_cellpack, simulate_parcel_with_call_tree};
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use protorune_support::balance_sheet::ProtoruneRuneId;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_try_call_falls_back_after_revert() -> Result<()> {
+    clear();
+    let block_height = 840_000;
+
+    let mut test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+
+    // minting [2, 0] fails since the test alkane holds none of it, so the fallback mints
+    // the alkane's own token instead
+    let fallback_cellpack = Cellpack {
+        target: AlkaneId { block: 2, tx: 1 },
+        inputs: vec![
+            92, 2, 1, 4, 30, 2, 0, 1_000_000, 2, 1, 4, 30, 2, 1, 1_000_000,
+        ],
+    };
+    test_block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![fallback_cellpack],
+            false,
+        ));
+
+    index_block(&test_block, block_height)?;
+
+    let sheet = alkane_helpers::get_last_outpoint_sheet(&test_block)?;
+    assert_eq!(sheet.get_cached(&ProtoruneRuneId { block: 2, tx: 0 }), 0);
+    assert_eq!(
+        sheet.get_cached(&ProtoruneRuneId { block: 2, tx: 1 }),
+        1_000_000
+    );
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata.last().unwrap().compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    // the failed attempt is still traced, but the outer call returns
+    assert!(trace_events
+        .iter()
+        .any(|event| matches!(event, TraceEvent::RevertContext(_))));
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(_) => {}
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_try_call_charges_the_caller_for_a_failed_callee() -> Result<()> {
+    clear();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes()].into(),
+        [Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![50],
+        }]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    // the callee loops until it has burned all the fuel it was given, which is all the
+    // caller had left, so recovering from its failure leaves the caller nothing to run on
+    let fuel = 1_000_000;
+    let (result, call_tree) = simulate_parcel_with_call_tree(
+        &plain_parcel_from_cellpack(Cellpack {
+            target: AlkaneId { block: 2, tx: 1 },
+            inputs: vec![91, 2, 1, 1, 20],
+        }),
+        fuel,
+    );
+    assert!(result.is_err());
+    let root = call_tree.unwrap();
+    assert!(root.reverted);
+    assert_eq!(root.fuel_total, fuel);
+    let looped = &root.calls[0];
+    assert!(looped.reverted);
+    // most of the grant went to the callee and is counted against the caller
+    assert!(looped.fuel_total > fuel / 2);
+    assert_eq!(root.fuel_self, fuel - looped.fuel_total);
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use crate::tests::std::alkanes_std_test_build;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::revert::{Revert, REVERT_CODE_CONTRACT, REVERT_SELECTOR};
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::Result;
+use bitcoin::OutPoint;
+
+use crate::index_block;
+use crate::tests::helpers::{self as alkane_helpers};
+use alkane_helpers::clear;
+use alkanes::view;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_revert_is_typed_in_trace() -> Result<()> {
+    clear();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![100],
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            let revert = trace_response.revert().expect("typed revert");
+            assert_eq!(revert.code, REVERT_CODE_CONTRACT);
+            assert_eq!(revert.contract, AlkaneId { block: 2, tx: 1 });
+            assert!(revert.message.contains("Error: Revert"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_caller_recovers_from_callee_revert() -> Result<()> {
+    clear();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![91, 2, 1, 1, 100],
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            let revert = Revert::decode(&trace_response.inner.data).expect("typed revert");
+            assert_eq!(revert.code, REVERT_CODE_CONTRACT);
+            assert_eq!(revert.contract, AlkaneId { block: 2, tx: 1 });
+            // the callee ran one frame below the caller
+            assert!(revert.depth > 0);
+            assert!(revert.message.contains("Error: Revert"));
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_revert_is_untyped_before_v2() -> Result<()> {
+    clear();
+    alkane_helpers::schedule_v2();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [alkanes_std_test_build::get_bytes(), vec![]].into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs: vec![100],
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[2].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::RevertContext(trace_response) => {
+            assert_eq!(trace_response.inner.data[..4], REVERT_SELECTOR);
+            let revert = trace_response.revert().expect("Error(string) revert");
+            assert_eq!(revert.contract, AlkaneId::default());
+            assert!(revert.message.contains("Error: Revert"));
+        }
+        _ => panic!("Expected RevertContext variant, but got a different variant"),
+    }
+    clear();
+    Ok(())
+}
+
+// deploys the test alkane at [2, 1] and [2, 2], then calls [2, 1] with `inputs` and returns
+// the revert it hands back
+fn nested_revert(inputs: Vec<u128>) -> Result<Revert> {
+    clear();
+    let test_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        [
+            alkanes_std_test_build::get_bytes(),
+            alkanes_std_test_build::get_bytes(),
+            vec![],
+        ]
+        .into(),
+        [
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![50],
+            },
+            Cellpack {
+                target: AlkaneId { block: 2, tx: 1 },
+                inputs,
+            },
+        ]
+        .into(),
+    );
+    index_block(&test_block, 840_000)?;
+
+    let outpoint = OutPoint {
+        txid: test_block.txdata[3].compute_txid(),
+        vout: 3,
+    };
+    let trace_data: Trace = view::trace(&outpoint)?.try_into()?;
+    let trace_events = trace_data.0.lock().expect("Mutex poisoned");
+    match trace_events[trace_events.len() - 1].clone() {
+        TraceEvent::ReturnContext(trace_response) => {
+            Ok(Revert::decode(&trace_response.inner.data).expect("typed revert"))
+        }
+        _ => panic!("Expected ReturnContext variant, but got a different variant"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_nested_revert_names_the_callee_and_its_call_depth() -> Result<()> {
+    // [2, 1] calls [2, 2], which calls back into [2, 1] to revert two calls down
+    let revert = nested_revert(vec![91, 2, 2, 5, 91, 2, 1, 1, 100])?;
+    assert_eq!(revert.code, REVERT_CODE_CONTRACT);
+    assert_eq!(revert.contract, AlkaneId { block: 2, tx: 1 });
+    assert_eq!(revert.depth, 2);
+
+    // a callee that fails before it runs is still the one named, not its caller [2, 2]
+    let revert = nested_revert(vec![91, 2, 2, 4, 91, 2, 9, 0])?;
+    assert_eq!(revert.contract, AlkaneId { block: 2, tx: 9 });
+    assert_eq!(revert.depth, 2);
+    Ok(())
+}
@@ -1,11 +1,16 @@
-use crate::tables::{TRACES, TRACES_BY_HEIGHT};
+use crate::tables::{EVENTS, TRACES, TRACES_BY_HEIGHT};
+use alkanes_support::id::AlkaneId;
 use alkanes_support::proto;
-use alkanes_support::trace::Trace;
-use anyhow::Result;
+use alkanes_support::revert::{Revert, REVERT_CODE_MESSAGE};
+use alkanes_support::trace::{Trace, TraceEmit, TraceEvent, TraceResponse};
+use anyhow::{anyhow, Result};
+use bitcoin::hashes::{sha256, Hash};
 use bitcoin::OutPoint;
+use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
 use metashrew_support::index_pointer::KeyValuePointer;
 use metashrew_support::utils::consensus_encode;
-use protobuf::Message;
+use protobuf::{Message, MessageField};
+use protorune::journal;
 use std::sync::Arc;
 #[allow(unused_imports)]
 use {
@@ -15,11 +20,77 @@ use {
 
 pub fn save_trace(outpoint: &OutPoint, height: u64, trace: Trace) -> Result<()> {
     let buffer: Vec<u8> = consensus_encode::<OutPoint>(outpoint)?;
-    TRACES.select(&buffer).set(Arc::<Vec<u8>>::new(
+    journal::written(TRACES.select(&buffer)).set(Arc::<Vec<u8>>::new(
         <Trace as Into<proto::alkanes::AlkanesTrace>>::into(trace).write_to_bytes()?,
     ));
-    TRACES_BY_HEIGHT
-        .select_value(height)
-        .append(Arc::new(buffer));
+    journal::appended(TRACES_BY_HEIGHT.select_value(height)).append(Arc::new(buffer));
+    Ok(())
+}
+
+/// Rewrites the trace of a message that returned but was then rolled back, ending it in a
+/// revert instead of its ReturnContext. Traces that did not return are left as they are.
+pub fn revert_trace(outpoint: &OutPoint, reason: &str) -> Result<()> {
+    let mut pointer = journal::written(TRACES.select(&consensus_encode::<OutPoint>(outpoint)?));
+    let data = pointer.get();
+    if data.is_empty() {
+        return Ok(());
+    }
+    let trace: Trace = proto::alkanes::AlkanesTrace::parse_from_bytes(&data)?.try_into()?;
+    {
+        let mut events = trace.0.lock().unwrap();
+        let target = match events.first() {
+            Some(TraceEvent::EnterCall(context)) => context.inner.myself.clone(),
+            _ => AlkaneId::default(),
+        };
+        let fuel_used = match events.last() {
+            Some(TraceEvent::ReturnContext(response)) => response.fuel_used,
+            _ => return Ok(()),
+        };
+        let revert = Revert::from_error(
+            &anyhow!(reason.to_string()),
+            REVERT_CODE_MESSAGE,
+            &target,
+            0,
+        );
+        *events.last_mut().unwrap() =
+            TraceEvent::RevertContext(TraceResponse::from_revert(&revert, fuel_used));
+    }
+    pointer.set(Arc::new(
+        <Trace as Into<proto::alkanes::AlkanesTrace>>::into(trace).write_to_bytes()?,
+    ));
+    Ok(())
+}
+
+// topics are hashed so that variable length topics can't collide in the key space
+pub fn event_pointer(alkane: &AlkaneId, topic: &[u8]) -> IndexPointer {
+    let alkane_bytes: Vec<u8> = alkane.clone().into();
+    EVENTS
+        .select(&alkane_bytes)
+        .select(&sha256::Hash::hash(topic).to_byte_array().to_vec())
+}
+
+pub fn save_event(
+    atomic: &mut AtomicPointer,
+    outpoint: &OutPoint,
+    height: u64,
+    emit: &TraceEmit,
+) -> Result<()> {
+    let mut event = proto::alkanes::AlkanesEvent::new();
+    event.outpoint = MessageField::some(proto::alkanes::Outpoint {
+        txid: outpoint.txid.as_byte_array().to_vec(),
+        vout: outpoint.vout,
+        ..Default::default()
+    });
+    event.height = height;
+    event.alkane = MessageField::some(emit.alkane.clone().into());
+    event.topic = emit.topic.clone();
+    event.data = emit.data.clone();
+    let ptr = atomic.derive(&event_pointer(&emit.alkane, &emit.topic));
+    let by_height = ptr.keyword("/byheight/").select_value(height);
+    if by_height.length() == 0 {
+        journal::appended(ptr.keyword("/heights")).append_value::<u64>(height);
+    }
+    journal::record_append(&by_height);
+    by_height.append(Arc::new(event.write_to_bytes()?));
     Ok(())
 }
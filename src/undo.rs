@@ -0,0 +1,111 @@
+use crate::etl::BLOCKS;
+use crate::tables::{STATE_ROOTS, UNDO_LOGS};
+use anyhow::{anyhow, Result};
+use metashrew_core::index_pointer::IndexPointer;
+use metashrew_support::index_pointer::KeyValuePointer;
+use metashrew_support::utils::{consume_exact, consume_sized_int, is_empty};
+use protorune::journal;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+/*
+An undo log holds, for every key a block changed, the value the key had before the block:
+
+  entry count u32 | (key length u32 | key | value length u32 | value)*, keys in ascending order
+
+The log is read off protorune's write journal, which records the prior value of each key as
+it is first written, so building it costs as much as the block's own writes. Under metashrew
+proper the host keeps every height's values and handles reorgs itself.
+*/
+
+const UNDO_PREFIX: &str = "/undo/";
+
+/// Starts journaling the writes of a block.
+pub fn capture() {
+    journal::open();
+}
+
+/// Prior values of every key changed since `capture`, except the undo logs themselves.
+pub fn block_undo() -> BTreeMap<Vec<u8>, Vec<u8>> {
+    journal::close()
+        .into_iter()
+        .filter(|(key, prior)| {
+            !key.starts_with(UNDO_PREFIX.as_bytes())
+                && IndexPointer::wrap(key).get().as_ref() != prior
+        })
+        .collect()
+}
+
+/// Puts back the value of every key changed since `capture`.
+pub fn restore() {
+    for (key, value) in block_undo() {
+        IndexPointer::wrap(&key).set(Arc::new(value));
+    }
+}
+
+fn encode_undo(undo: &BTreeMap<Vec<u8>, Vec<u8>>) -> Vec<u8> {
+    let mut data = (undo.len() as u32).to_le_bytes().to_vec();
+    for (key, value) in undo {
+        data.extend((key.len() as u32).to_le_bytes());
+        data.extend(key);
+        data.extend((value.len() as u32).to_le_bytes());
+        data.extend(value);
+    }
+    data
+}
+
+fn decode_undo(data: Vec<u8>) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    let mut cursor = Cursor::new(data);
+    let count = consume_sized_int::<u32>(&mut cursor)?;
+    let mut entries = vec![];
+    for _ in 0..count {
+        let key_length = consume_sized_int::<u32>(&mut cursor)?;
+        let key = consume_exact(&mut cursor, key_length as usize)?;
+        let value_length = consume_sized_int::<u32>(&mut cursor)?;
+        let value = consume_exact(&mut cursor, value_length as usize)?;
+        entries.push((key, value));
+    }
+    if !is_empty(&mut cursor) {
+        return Err(anyhow!("undo log has trailing data"));
+    }
+    Ok(entries)
+}
+
+/// Stores the undo log of the block at `height`, which must be the last write of the block.
+pub fn record_undo(height: u64) {
+    UNDO_LOGS
+        .select_value::<u64>(height)
+        .set(Arc::new(encode_undo(&block_undo())));
+}
+
+/// The undo log recorded for `height`, if any.
+pub fn undo_log(height: u64) -> Result<Option<Vec<(Vec<u8>, Vec<u8>)>>> {
+    let data = UNDO_LOGS.select_value::<u64>(height).get();
+    if data.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(decode_undo(data.as_ref().clone())?))
+    }
+}
+
+/// Undoes every block indexed above `height`, newest first, leaving the index as it was once
+/// `height` was indexed. Returns the number of blocks undone. Nothing is written unless every
+/// block above `height` has an undo log.
+pub fn rollback_to(height: u64) -> Result<u64> {
+    let mut logs = vec![];
+    let mut next = height + 1;
+    while !STATE_ROOTS.select_value::<u64>(next).get().is_empty() {
+        let log = undo_log(next)?.ok_or_else(|| anyhow!("block {} has no undo log", next))?;
+        logs.push((next, log));
+        next += 1;
+    }
+    for (block, log) in logs.iter().rev() {
+        for (key, value) in log {
+            IndexPointer::wrap(key).set(Arc::new(value.clone()));
+        }
+        BLOCKS.select_value(*block as u32).set(Arc::new(vec![]));
+        UNDO_LOGS.select_value::<u64>(*block).set(Arc::new(vec![]));
+    }
+    Ok(logs.len() as u64)
+}
@@ -1,3 +1,6 @@
+use crate::stateroot::{appended, written};
+use crate::tables::BLOCK_TIMES;
+use crate::vm::schedule::protocol_rules;
 use alkanes_support::id::AlkaneId;
 use alkanes_support::parcel::AlkaneTransferParcel;
 use alkanes_support::storage::StorageMap;
@@ -37,7 +40,7 @@ pub fn balance_pointer(
         .keyword("/balances/")
         .select(&who_bytes);
     if ptr.get().len() != 0 {
-        alkane_inventory_pointer(who).append(Arc::new(what_bytes));
+        appended(alkane_inventory_pointer(who)).append(Arc::new(what_bytes));
     }
     ptr
 }
@@ -70,7 +73,7 @@ pub fn credit_balances(
     runes: &Vec<RuneTransfer>,
 ) -> Result<()> {
     for rune in runes.clone() {
-        let mut ptr = balance_pointer(atomic, to, &rune.id.clone().into());
+        let mut ptr = written(balance_pointer(atomic, to, &rune.id.clone().into()));
         ptr.set_value::<u128>(
             rune.value
                 .checked_add(ptr.get_value::<u128>())
@@ -87,7 +90,7 @@ pub fn debit_balances(
     runes: &AlkaneTransferParcel,
 ) -> Result<()> {
     for rune in runes.0.clone() {
-        let mut pointer = balance_pointer(atomic, to, &rune.id.clone().into());
+        let mut pointer = written(balance_pointer(atomic, to, &rune.id.clone().into()));
         let pointer_value = pointer.get_value::<u128>();
         let v = {
             // NOTE: we intentionally allow alkanes to mint an infinite amount of themselves
@@ -114,7 +117,7 @@ pub fn transfer_from(
     to: &AlkaneId,
 ) -> Result<()> {
     for transfer in &parcel.0 {
-        let mut from_pointer =
+        let from_pointer =
             balance_pointer(atomic, &from.clone().into(), &transfer.id.clone().into());
         let mut balance = from_pointer.get_value::<u128>();
         if balance < transfer.value {
@@ -124,18 +127,96 @@ pub fn transfer_from(
                 return Err(anyhow!("balance underflow during transfer_from"));
             }
         }
-        from_pointer.set_value::<u128>(balance - transfer.value);
-        let mut to_pointer =
-            balance_pointer(atomic, &to.clone().into(), &transfer.id.clone().into());
+        written(from_pointer).set_value::<u128>(balance - transfer.value);
+        let mut to_pointer = written(balance_pointer(
+            atomic,
+            &to.clone().into(),
+            &transfer.id.clone().into(),
+        ));
         to_pointer.set_value::<u128>(to_pointer.get_value::<u128>() + transfer.value);
     }
     Ok(())
 }
-pub fn pipe_storagemap_to<T: KeyValuePointer>(map: &StorageMap, pointer: &mut T) {
-    map.0.iter().for_each(|(k, v)| {
-        pointer
-            .keyword("/storage/")
-            .select(k)
-            .set(Arc::new(v.clone()));
+/// The key list of the alkane at `pointer`, which is incomplete for alkanes that stored keys
+/// before the storage key index activated (see `pipe_storagemap_to`).
+pub fn storage_keys_pointer<T: KeyValuePointer>(pointer: &T) -> T {
+    pointer.keyword("/storagekeys/")
+}
+
+fn storage_key_position<T: KeyValuePointer>(pointer: &T, k: &Vec<u8>) -> T {
+    pointer.keyword("/storagekeyindex/").select(k)
+}
+
+fn index_storage_key<T: KeyValuePointer>(pointer: &T, k: &Vec<u8>) {
+    let position = storage_key_position(pointer, k);
+    if position.get_value::<u32>() == 0 {
+        let keys = appended(storage_keys_pointer(pointer));
+        keys.append(Arc::new(k.clone()));
+        written(position).set_value::<u32>(keys.length());
+    }
+}
+
+// swap-remove so the key list stays dense for paginated listing
+fn unindex_storage_key<T: KeyValuePointer>(pointer: &T, k: &Vec<u8>) {
+    let position = storage_key_position(pointer, k);
+    let index = position.get_value::<u32>();
+    if index == 0 {
+        return;
+    }
+    let keys = storage_keys_pointer(pointer);
+    let last = keys.length() - 1;
+    if index - 1 != last {
+        let moved = keys.select_index(last).get();
+        written(keys.select_index(index - 1)).set(moved.clone());
+        written(storage_key_position(pointer, moved.as_ref())).set_value::<u32>(index);
+    }
+    written(keys.length_key()).set_value::<u32>(last);
+    written(position).set_value::<u32>(0);
+}
+
+/// Writes `map` to the storage of the alkane at `pointer`. From the height the storage key
+/// index activates, the alkane's key list is kept in step with the writes; a key last written
+/// before then is stored but not listed until it is written again.
+pub fn pipe_storagemap_to<T: KeyValuePointer>(map: &StorageMap, pointer: &mut T, height: u64) {
+    let index_keys = protocol_rules(height).storage_key_index;
+    let mut pairs = map.0.iter().collect::<Vec<_>>();
+    pairs.sort();
+    pairs.into_iter().for_each(|(k, v)| {
+        written(pointer.keyword("/storage/").select(k)).set(Arc::new(v.clone()));
+        if !index_keys {
+            return;
+        }
+        if v.is_empty() {
+            unindex_storage_key(pointer, k);
+        } else {
+            index_storage_key(pointer, k);
+        }
     });
 }
+
+/// Number of blocks preceding a block whose timestamps its median-time-past is taken over.
+pub const MEDIAN_TIME_SPAN: u64 = 11;
+
+/// Median timestamp of the (up to) eleven blocks preceding `height`, as in BIP113, reading
+/// one stored block time per block. Block times are kept in snapshots, so every index that
+/// reaches `height` holds the same ones; fails rather than take a shorter median when one of
+/// the blocks was never indexed, as within eleven blocks of where indexing started.
+pub fn median_time_past(height: u64) -> Result<u32> {
+    let mut times = (height.saturating_sub(MEDIAN_TIME_SPAN)..height)
+        .map(
+            |h| match BLOCK_TIMES.select_value::<u64>(h).get_value::<u32>() {
+                0 => Err(anyhow!(
+                    "median-time-past of block {} needs the time of block {}, which is not indexed",
+                    height,
+                    h
+                )),
+                time => Ok(time),
+            },
+        )
+        .collect::<Result<Vec<u32>>>()?;
+    if times.is_empty() {
+        return Err(anyhow!("block {} has no preceding blocks", height));
+    }
+    times.sort();
+    Ok(times[times.len() / 2])
+}
@@ -1,13 +1,15 @@
 use crate::message::AlkaneMessageContext;
 use crate::network::set_view_mode;
 use crate::tables::{TRACES, TRACES_BY_HEIGHT};
+use crate::trace::event_pointer;
 use crate::utils::{
     alkane_id_to_outpoint, alkane_inventory_pointer, balance_pointer, credit_balances,
     debit_balances, pipe_storagemap_to,
 };
 use crate::vm::instance::AlkanesInstance;
 use crate::vm::runtime::AlkanesRuntimeContext;
-use crate::vm::utils::{prepare_context, run_after_special, run_special_cellpacks};
+use crate::vm::schedule::protocol_rules;
+use crate::vm::utils::{prepare_context, run_after_special_metered, run_special_cellpacks};
 use alkanes_support::cellpack::Cellpack;
 use alkanes_support::id::AlkaneId;
 use alkanes_support::parcel::AlkaneTransfer;
@@ -17,6 +19,8 @@ use alkanes_support::proto::alkanes::{
     AlkaneInventoryResponse,
 };
 use alkanes_support::response::ExtendedCallResponse;
+use alkanes_support::revert::{Revert, REVERT_CODE_VM};
+use alkanes_support::trace::{FuelFrame, TraceContext, TraceEvent, TraceResponse};
 use anyhow::{anyhow, Result};
 use bitcoin::blockdata::transaction::Version;
 use bitcoin::consensus::encode::serialize;
@@ -133,7 +137,7 @@ pub fn call_multiview(ids: &[AlkaneId], inputs: &Vec<Vec<u128>>, fuel: u64) -> R
     let mut response: Vec<u8> = vec![];
 
     for result in results {
-        let (result, _gas_used) = result.unwrap();
+        let (result, _gas_used) = result.0.unwrap();
         response.extend_from_slice(&result.data.len().to_le_bytes());
         response.extend_from_slice(&result.data);
     }
@@ -414,12 +418,62 @@ pub fn trace(outpoint: &OutPoint) -> Result<Vec<u8>> {
         .clone())
 }
 
+// most heights one events request scans, and how many it scans when no limit is given
+const EVENTS_PAGE_LIMIT: u32 = 1_000;
+
+/// Events the alkane emitted under the topic between `from_height` and `to_height`, found by
+/// scanning up to `limit` of the heights the topic was emitted at, from the `start`th.
+/// `next` in the response is where the following page starts, or 0 once none is left.
+pub fn events(input: &Vec<u8>) -> Result<proto::alkanes::EventsResponse> {
+    let request = proto::alkanes::EventsRequest::parse_from_bytes(input)?;
+    let alkane: AlkaneId = request
+        .id
+        .into_option()
+        .ok_or(anyhow!("EventsRequest is missing an alkane id"))?
+        .into();
+    let to_height = if request.to_height == 0 {
+        u64::MAX
+    } else {
+        request.to_height
+    };
+    let ptr = event_pointer(&alkane, &request.topic);
+    let heights = ptr.keyword("/heights");
+    let length = heights.length();
+    let limit = match request.limit {
+        0 => EVENTS_PAGE_LIMIT,
+        limit => limit.min(EVENTS_PAGE_LIMIT),
+    };
+    let end = std::cmp::min(length, request.start.saturating_add(limit));
+    let mut response = proto::alkanes::EventsResponse::new();
+    for i in request.start..end {
+        let height = heights.select_index(i).get_value::<u64>();
+        // heights are listed in the order they were indexed
+        if height > to_height {
+            return Ok(response);
+        }
+        if height < request.from_height {
+            continue;
+        }
+        for event in ptr.keyword("/byheight/").select_value(height).get_list() {
+            response
+                .events
+                .push(proto::alkanes::AlkanesEvent::parse_from_bytes(
+                    event.as_ref(),
+                )?);
+        }
+    }
+    if end < length {
+        response.next = end;
+    }
+    Ok(response)
+}
+
 pub fn simulate_safe(
     parcel: &MessageContextParcel,
     fuel: u64,
-) -> Result<(ExtendedCallResponse, u64)> {
+) -> (Result<(ExtendedCallResponse, u64)>, Option<FuelFrame>) {
     set_view_mode();
-    simulate_parcel(parcel, fuel)
+    simulate_parcel_with_call_tree(parcel, fuel)
 }
 
 pub fn meta_safe(parcel: &MessageContextParcel) -> Result<Vec<u8>> {
@@ -439,20 +493,69 @@ pub fn simulate_parcel(
     parcel: &MessageContextParcel,
     fuel: u64,
 ) -> Result<(ExtendedCallResponse, u64)> {
-    let list = decode_varint_list(&mut Cursor::new(parcel.calldata.clone()))?;
-    let cellpack: Cellpack = list.clone().try_into()?;
-    println!("{:?}, {:?}", list, cellpack);
+    simulate_parcel_with_call_tree(parcel, fuel).0
+}
+
+/// Simulates a parcel and rebuilds the per-frame fuel call tree from its trace. The tree is
+/// returned even when the simulation fails, so the frame that ran out of fuel can be found.
+pub fn simulate_parcel_with_call_tree(
+    parcel: &MessageContextParcel,
+    fuel: u64,
+) -> (Result<(ExtendedCallResponse, u64)>, Option<FuelFrame>) {
+    let cellpack: Cellpack = match decode_varint_list(&mut Cursor::new(parcel.calldata.clone()))
+        .and_then(|list| list.try_into())
+    {
+        Ok(v) => v,
+        Err(e) => return (Err(e), None),
+    };
     let context = Arc::new(Mutex::new(AlkanesRuntimeContext::from_parcel_and_cellpack(
         parcel, &cellpack,
     )));
+    let mut fuel_used = 0;
+    let result = simulate_in_context(parcel, &cellpack, context.clone(), fuel, &mut fuel_used);
+    let trace = context.lock().unwrap().trace.clone();
+    if let Err(e) = &result {
+        let revert = Revert::from_error(e, REVERT_CODE_VM, &cellpack.target, 0);
+        trace.clock(TraceEvent::RevertContext(
+            protocol_rules(parcel.height).revert_response(&revert, fuel_used),
+        ));
+    }
+    let call_tree = FuelFrame::from_trace(&trace.0.lock().unwrap());
+    (result, call_tree)
+}
+
+// `fuel_used` is set to what the call consumed, whether or not it succeeds
+fn simulate_in_context(
+    parcel: &MessageContextParcel,
+    cellpack: &Cellpack,
+    context: Arc<Mutex<AlkanesRuntimeContext>>,
+    fuel: u64,
+    fuel_used: &mut u64,
+) -> Result<(ExtendedCallResponse, u64)> {
+    println!("{:?}", cellpack);
     let mut atomic = parcel.atomic.derive(&IndexPointer::default());
-    let (caller, myself, binary) = run_special_cellpacks(context.clone(), &cellpack)?;
+    let (caller, myself, binary) = run_special_cellpacks(context.clone(), cellpack)?;
     credit_balances(&mut atomic, &myself, &parcel.runes)?;
     prepare_context(context.clone(), &caller, &myself, false);
-    let (response, gas_used) = run_after_special(context.clone(), binary, fuel)?;
+    let inner = context.lock().unwrap().flat();
+    let trace = context.lock().unwrap().trace.clone();
+    trace.clock(TraceEvent::EnterCall(TraceContext {
+        inner,
+        target: cellpack.target.clone(),
+        fuel,
+        requested_fuel: None,
+    }));
+    let (result, consumed) = run_after_special_metered(context.clone(), binary, fuel);
+    *fuel_used = consumed;
+    let (response, gas_used) = result?;
+    trace.clock(TraceEvent::ReturnContext(TraceResponse {
+        inner: response.clone(),
+        fuel_used: gas_used,
+    }));
     pipe_storagemap_to(
         &response.storage,
         &mut atomic.derive(&IndexPointer::from_keyword("/alkanes/").select(&myself.clone().into())),
+        parcel.height,
     );
     let mut combined = parcel.runtime_balances.as_ref().clone();
     <BalanceSheet<AtomicPointer> as TryFrom<Vec<RuneTransfer>>>::try_from(parcel.runes.clone())?
@@ -468,10 +571,10 @@ pub fn simulate_parcel(
 pub fn multi_simulate(
     parcels: &[MessageContextParcel],
     fuel: u64,
-) -> Vec<Result<(ExtendedCallResponse, u64)>> {
-    let mut responses: Vec<Result<(ExtendedCallResponse, u64)>> = vec![];
+) -> Vec<(Result<(ExtendedCallResponse, u64)>, Option<FuelFrame>)> {
+    let mut responses: Vec<(Result<(ExtendedCallResponse, u64)>, Option<FuelFrame>)> = vec![];
     for parcel in parcels {
-        responses.push(simulate_parcel(parcel, fuel));
+        responses.push(simulate_parcel_with_call_tree(parcel, fuel));
     }
     responses
 }
@@ -479,7 +582,7 @@ pub fn multi_simulate(
 pub fn multi_simulate_safe(
     parcels: &[MessageContextParcel],
     fuel: u64,
-) -> Vec<Result<(ExtendedCallResponse, u64)>> {
+) -> Vec<(Result<(ExtendedCallResponse, u64)>, Option<FuelFrame>)> {
     set_view_mode();
     multi_simulate(parcels, fuel)
 }
@@ -0,0 +1,198 @@
+use alkanes_support::{gz::decompress, id::AlkaneId};
+use anyhow::Result;
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use wasmi::{Config, Engine, Module};
+
+/// Number of alkanes whose decompressed binary and compiled module are kept in memory.
+pub const BINARY_CACHE_CAPACITY: usize = 64;
+
+// a wasmi engine never frees compiled code, so the engine (and every module compiled
+// against it) is replaced after this many compilations per cache slot
+const ENGINE_RECYCLE_FACTOR: usize = 8;
+
+static BINARY_CACHE: Lazy<Mutex<BinaryCache>> = Lazy::new(|| Mutex::new(BinaryCache::new()));
+
+pub fn new_engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+/// Like [`new_engine`], but binaries using floating point fail to compile. Deploys are
+/// compiled with it, so the module that validated a deploy is the one that runs it.
+pub fn new_deploy_engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    config.floats(false);
+    Engine::new(&config)
+}
+
+struct CachedAlkane {
+    compressed: Arc<Vec<u8>>,
+    binary: Arc<Vec<u8>>,
+    module: Option<(Engine, Arc<Module>)>,
+    last_used: u64,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct CacheStats {
+    pub binary_hits: u64,
+    pub binary_misses: u64,
+    pub module_hits: u64,
+    pub module_misses: u64,
+}
+
+/// LRU cache of decompressed alkane binaries and their compiled modules, keyed by
+/// AlkaneId. An entry is only reused while the compressed bytes stored for the id are
+/// unchanged, so a re-deploy or a rolled back deploy replaces it.
+pub struct BinaryCache {
+    engine: Engine,
+    deploy_engine: Engine,
+    entries: BTreeMap<AlkaneId, CachedAlkane>,
+    tick: u64,
+    compiled: usize,
+    stats: CacheStats,
+}
+
+impl BinaryCache {
+    pub fn new() -> Self {
+        Self {
+            engine: new_engine(),
+            deploy_engine: new_deploy_engine(),
+            entries: BTreeMap::new(),
+            tick: 0,
+            compiled: 0,
+            stats: CacheStats::default(),
+        }
+    }
+    fn touch(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+    fn evict(&mut self) {
+        while self.entries.len() >= BINARY_CACHE_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone());
+            match oldest {
+                Some(id) => self.entries.remove(&id),
+                None => break,
+            };
+        }
+    }
+    fn recycle_engine(&mut self) {
+        if self.compiled >= BINARY_CACHE_CAPACITY * ENGINE_RECYCLE_FACTOR {
+            self.engine = new_engine();
+            self.deploy_engine = new_deploy_engine();
+            self.compiled = 0;
+            self.entries
+                .values_mut()
+                .for_each(|entry| entry.module = None);
+        }
+    }
+    pub fn binary(&mut self, id: &AlkaneId, compressed: Arc<Vec<u8>>) -> Result<Arc<Vec<u8>>> {
+        let tick = self.touch();
+        if let Some(entry) = self.entries.get_mut(id) {
+            if Arc::ptr_eq(&entry.compressed, &compressed) || entry.compressed == compressed {
+                entry.last_used = tick;
+                self.stats.binary_hits += 1;
+                return Ok(entry.binary.clone());
+            }
+        }
+        self.stats.binary_misses += 1;
+        let binary = Arc::new(decompress(compressed.as_ref().clone())?);
+        self.entries.remove(id);
+        self.evict();
+        self.entries.insert(
+            id.clone(),
+            CachedAlkane {
+                compressed,
+                binary: binary.clone(),
+                module: None,
+                last_used: tick,
+            },
+        );
+        Ok(binary)
+    }
+    fn compile(&mut self, binary: &Arc<Vec<u8>>, deploy: bool) -> Result<(Engine, Arc<Module>)> {
+        self.recycle_engine();
+        let engine = if deploy {
+            self.deploy_engine.clone()
+        } else {
+            self.engine.clone()
+        };
+        let entry = self
+            .entries
+            .values_mut()
+            .find(|entry| Arc::ptr_eq(&entry.binary, binary));
+        if !deploy {
+            if let Some(module) = entry.as_ref().and_then(|entry| entry.module.clone()) {
+                self.stats.module_hits += 1;
+                return Ok(module);
+            }
+        }
+        let module = Arc::new(Module::new(&engine, &mut &binary[..])?);
+        if let Some(entry) = entry {
+            entry.module = Some((engine.clone(), module.clone()));
+        }
+        self.compiled += 1;
+        self.stats.module_misses += 1;
+        Ok((engine, module))
+    }
+    /// Returns a compiled module for `binary` with the engine it belongs to. Binaries that
+    /// did not come from [`BinaryCache::binary`] are compiled without being cached.
+    pub fn module(&mut self, binary: &Arc<Vec<u8>>) -> Result<(Engine, Arc<Module>)> {
+        self.compile(binary, false)
+    }
+    /// Compiles a binary being deployed with the deploy engine, replacing any module cached
+    /// for it, so that later calls reuse the module validation checked.
+    pub fn deploy_module(&mut self, binary: &Arc<Vec<u8>>) -> Result<(Engine, Arc<Module>)> {
+        self.compile(binary, true)
+    }
+    pub fn stats(&self) -> CacheStats {
+        self.stats.clone()
+    }
+}
+
+pub fn load_binary(id: &AlkaneId, compressed: Arc<Vec<u8>>) -> Result<Arc<Vec<u8>>> {
+    BINARY_CACHE.lock().unwrap().binary(id, compressed)
+}
+
+pub fn load_module(binary: &Arc<Vec<u8>>) -> Result<(Engine, Arc<Module>)> {
+    BINARY_CACHE.lock().unwrap().module(binary)
+}
+
+pub fn load_deploy_module(binary: &Arc<Vec<u8>>) -> Result<(Engine, Arc<Module>)> {
+    BINARY_CACHE.lock().unwrap().deploy_module(binary)
+}
+
+pub fn cache_stats() -> CacheStats {
+    BINARY_CACHE.lock().unwrap().stats()
+}
+
+#[cfg(feature = "debug-log")]
+pub fn log_cache_stats() {
+    let stats = cache_stats();
+    let rate = |hits: u64, misses: u64| match hits + misses {
+        0 => 0.0,
+        total => (hits as f64) * 100.0 / (total as f64),
+    };
+    println!(
+        "binary cache: binaries {}/{} hits ({:.1}%), modules {}/{} hits ({:.1}%)",
+        stats.binary_hits,
+        stats.binary_hits + stats.binary_misses,
+        rate(stats.binary_hits, stats.binary_misses),
+        stats.module_hits,
+        stats.module_hits + stats.module_misses,
+        rate(stats.module_hits, stats.module_misses)
+    );
+}
@@ -1 +1,4 @@
 pub(super) const MEMORY_LIMIT: usize = 43554432;
+pub(super) const WASM_PAGE_SIZE: usize = 65536;
+/// every instance is grown to this many pages before it runs
+pub(super) const INITIAL_MEMORY_PAGES: u32 = 512;
@@ -1,11 +1,23 @@
+use super::schedule::protocol_rules;
+use super::{AlkanesRuntimeContext, Saveable, SaveableExtendedCallResponse, MAX_CHECKPOINT_DEPTH};
+use alkanes_support::cellpack::Cellpack;
 use alkanes_support::id::AlkaneId;
-use alkanes_support::trace::{TraceContext, TraceEvent};
-use metashrew_core::index_pointer::AtomicPointer;
+use alkanes_support::parcel::AlkaneTransferParcel;
+use alkanes_support::response::ExtendedCallResponse;
+use alkanes_support::revert::{Revert, REVERT_CODE_VM};
+use alkanes_support::trace::{TraceContext, TraceEvent, TraceResponse};
+use anyhow::{anyhow, Result};
+use metashrew_core::index_pointer::{AtomicPointer, IndexPointer};
+use std::marker::PhantomData;
 
 pub trait Extcall {
     fn isdelegate() -> bool;
     fn isstatic() -> bool;
     fn event(context: TraceContext) -> TraceEvent;
+    /// whether the caller keeps running when the call fails, rather than being aborted
+    fn recoverable() -> bool {
+        false
+    }
     fn handle_atomic(atomic: &mut AtomicPointer) {
         if Self::isstatic() {
             atomic.rollback();
@@ -67,3 +79,108 @@ impl Extcall for Staticcall {
         TraceEvent::EnterStaticcall(context)
     }
 }
+
+/// The `__try_*` flavour of a call, which hands a failure back to the caller to handle.
+pub struct TryCall<T: Extcall>(PhantomData<T>);
+
+impl<T: Extcall> Extcall for TryCall<T> {
+    fn isdelegate() -> bool {
+        T::isdelegate()
+    }
+    fn isstatic() -> bool {
+        T::isstatic()
+    }
+    fn event(context: TraceContext) -> TraceEvent {
+        T::event(context)
+    }
+    fn recoverable() -> bool {
+        true
+    }
+}
+
+/*
+The steps of a call shared by the __call family of host functions and by precompiles that
+call other alkanes, such as multicall. The caller checks the checkpoint depth and takes a
+checkpoint, builds the callee's context with `subcontext` and clocks `enter_call`, then
+either finishes with `return_call` or unwinds with `revert_call`.
+*/
+
+/// Refuses a call once the checkpoints held by the message reach MAX_CHECKPOINT_DEPTH.
+pub fn check_checkpoint_depth(context: &AlkanesRuntimeContext) -> Result<()> {
+    let depth = context.message.atomic.checkpoint_depth();
+    if depth >= MAX_CHECKPOINT_DEPTH {
+        return Err(anyhow!(format!(
+            "Possible infinite recursion encountered: checkpoint depth too large({})",
+            depth
+        )));
+    }
+    Ok(())
+}
+
+/// The context of a `T` call from `context` into `target`.
+pub fn subcontext<T: Extcall>(
+    context: &AlkanesRuntimeContext,
+    target: AlkaneId,
+    cellpack: &Cellpack,
+    incoming_alkanes: AlkaneTransferParcel,
+) -> AlkanesRuntimeContext {
+    let mut subbed = context.clone();
+    subbed.message.atomic = context.message.atomic.derive(&IndexPointer::default());
+    (subbed.caller, subbed.myself) =
+        T::change_context(target, context.caller.clone(), context.myself.clone());
+    subbed.returndata = vec![];
+    subbed.incoming_alkanes = incoming_alkanes;
+    subbed.inputs = cellpack.inputs.clone();
+    subbed.is_static = subbed.is_static || T::isstatic();
+    subbed.depth = context.depth + 1;
+    subbed
+}
+
+/// Clocks the entry into `subcontext` with the fuel it is given.
+pub fn enter_call<T: Extcall>(subcontext: &AlkanesRuntimeContext, fuel: u64) {
+    let mut trace_context: TraceContext = subcontext.flat().into();
+    trace_context.fuel = fuel;
+    subcontext.trace.clock(T::event(trace_context));
+}
+
+/// Clocks the return of a successful call and saves its response into the caller's atomic.
+pub fn return_call(
+    context: &mut AlkanesRuntimeContext,
+    subcontext: &AlkanesRuntimeContext,
+    response: &ExtendedCallResponse,
+    fuel_used: u64,
+) -> Result<()> {
+    let mut return_context: TraceResponse = response.clone().into();
+    return_context.fuel_used = fuel_used;
+    context
+        .trace
+        .clock(TraceEvent::ReturnContext(return_context));
+    let mut saveable: SaveableExtendedCallResponse = response.clone().into();
+    saveable.associate(subcontext);
+    saveable.save(&mut context.message.atomic, context.message.height)?;
+    Ok(())
+}
+
+/// Clocks the revert of a failed call into `callee`, at call depth `callee_depth`, with the
+/// fuel it consumed and unwinds the caller's atomic to the checkpoint depth it had before the
+/// call, which also takes back the alkanes it sent. An error that is not already a typed
+/// revert is reported as raised by the callee. Returns the encoded revert.
+pub fn revert_call(
+    context: &mut AlkanesRuntimeContext,
+    e: &anyhow::Error,
+    callee: &AlkaneId,
+    callee_depth: u32,
+    depth: usize,
+    fuel_used: u64,
+) -> Vec<u8> {
+    let revert = Revert::from_error(e, REVERT_CODE_VM, callee, callee_depth);
+    let revert_context = protocol_rules(context.message.height).revert_response(&revert, fuel_used);
+    let data = revert_context.inner.data.clone();
+    context
+        .trace
+        .clock(TraceEvent::RevertContext(revert_context));
+    while context.message.atomic.checkpoint_depth() > depth {
+        context.message.atomic.rollback();
+    }
+    data
+}
@@ -1,6 +1,9 @@
 use crate::{
     message::AlkaneMessageContext,
-    vm::{AlkanesInstance, AlkanesState},
+    vm::{
+        schedule::{protocol_rules, VfsizeRule},
+        AlkanesInstance, AlkanesState,
+    },
 };
 use alkanes_support::utils::overflow_error;
 use anyhow::{anyhow, Result};
@@ -20,11 +23,14 @@ use {
 };
 
 pub trait VirtualFuelBytes {
-    fn vfsize(&self) -> u64;
+    fn vfsize_with(&self, rule: VfsizeRule) -> u64;
+    fn vfsize(&self) -> u64 {
+        self.vfsize_with(VfsizeRule::DiscountDeployWitness)
+    }
 }
 
 impl VirtualFuelBytes for Transaction {
-    fn vfsize(&self) -> u64 {
+    fn vfsize_with(&self, rule: VfsizeRule) -> u64 {
         if let Some(Artifact::Runestone(ref runestone)) = Runestone::decipher(&self) {
             if let Ok(protostones) = Protostone::from_runestone(runestone) {
                 let cellpacks = protostones
@@ -47,14 +53,15 @@ impl VirtualFuelBytes for Transaction {
                     .collect::<Vec<Vec<u128>>>();
                 if cellpacks.len() == 0 {
                     0
-                } else if cellpacks
-                    .iter()
-                    .position(|v| {
-                        <&[u128] as TryInto<[u128; 2]>>::try_into(&v[0..2]).unwrap()
-                            == [1u128, 0u128]
-                            || v[0] == 3u128
-                    })
-                    .is_some()
+                } else if rule == VfsizeRule::DiscountDeployWitness
+                    && cellpacks
+                        .iter()
+                        .position(|v| {
+                            <&[u128] as TryInto<[u128; 2]>>::try_into(&v[0..2]).unwrap()
+                                == [1u128, 0u128]
+                                || v[0] == 3u128
+                        })
+                        .is_some()
                 {
                     let mut cloned = self.clone();
                     if cloned.input.len() > 0 {
@@ -74,8 +81,10 @@ impl VirtualFuelBytes for Transaction {
 }
 
 impl VirtualFuelBytes for Block {
-    fn vfsize(&self) -> u64 {
-        self.txdata.iter().fold(0u64, |r, v| r + v.vfsize())
+    fn vfsize_with(&self, rule: VfsizeRule) -> u64 {
+        self.txdata
+            .iter()
+            .fold(0u64, |r, v| r + v.vfsize_with(rule))
     }
 }
 
@@ -99,6 +108,26 @@ pub const TOTAL_FUEL: u64 = 50_000_000;
 #[cfg(feature = "bellscoin")]
 pub const TOTAL_FUEL: u64 = 50_000_000;
 
+// Charged per 64KiB page a contract grows its memory by
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "dogecoin",
+    feature = "bellscoin",
+    feature = "fractal",
+    feature = "luckycoin"
+)))]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 2_000;
+#[cfg(feature = "mainnet")]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 2_000;
+#[cfg(feature = "dogecoin")]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 2_500;
+#[cfg(feature = "fractal")]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 3_000;
+#[cfg(feature = "luckycoin")]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 3_000;
+#[cfg(feature = "bellscoin")]
+pub const FUEL_PER_MEMORY_PAGE: u64 = 3_000;
+
 #[derive(Default, Clone, Debug)]
 pub struct FuelTank {
     pub current_txindex: u32,
@@ -107,6 +136,9 @@ pub struct FuelTank {
     pub block_fuel: u64,
     pub transaction_fuel: u64,
     pub block_metered_fuel: u64,
+    /// fuel parameters of the rule set in force for the block
+    pub total_fuel: u64,
+    pub minimum_fuel: u64,
 }
 
 static _FUEL_TANK: RwLock<Option<FuelTank>> = RwLock::new(None);
@@ -124,15 +156,18 @@ impl FuelTank {
         _FUEL_TANK.read().unwrap().as_ref().unwrap().current_txindex == u32::MAX
     }
 
-    pub fn initialize(block: &Block) {
+    pub fn initialize(block: &Block, height: u64) {
+        let rules = protocol_rules(height);
         let mut tank = _FUEL_TANK.write().unwrap();
         *tank = Some(FuelTank {
             current_txindex: u32::MAX,
             txsize: 0,
-            size: block.vfsize(),
-            block_fuel: TOTAL_FUEL,
+            size: block.vfsize_with(rules.vfsize),
+            block_fuel: rules.total_fuel,
             transaction_fuel: 0,
             block_metered_fuel: 0,
+            total_fuel: rules.total_fuel,
+            minimum_fuel: rules.minimum_fuel,
         });
     }
 
@@ -146,7 +181,7 @@ impl FuelTank {
         tank.block_metered_fuel = tank.block_fuel * txsize / tank.size;
 
         // Ensure minimum fuel allocation
-        tank.transaction_fuel = std::cmp::max(MINIMUM_FUEL, tank.block_metered_fuel);
+        tank.transaction_fuel = std::cmp::max(tank.minimum_fuel, tank.block_metered_fuel);
 
         // Deduct allocated fuel from block fuel
         tank.block_fuel = tank.block_fuel - std::cmp::min(tank.block_fuel, tank.block_metered_fuel);
@@ -160,7 +195,7 @@ impl FuelTank {
             println!("  - Block fuel before: {}", _block_fuel_before);
             println!("  - Block fuel after: {}", tank.block_fuel);
             println!("  - Allocated fuel: {}", tank.transaction_fuel);
-            println!("  - Minimum fuel: {}", MINIMUM_FUEL);
+            println!("  - Minimum fuel: {}", tank.minimum_fuel);
         }
     }
 
@@ -209,7 +244,7 @@ impl FuelTank {
                 n,
                 tank.transaction_fuel,
                 tank.current_txindex,
-                tank.block_metered_fuel + (TOTAL_FUEL - tank.block_fuel),
+                tank.block_metered_fuel + (tank.total_fuel - tank.block_fuel),
                 tank.block_fuel,
                 tank.txsize,
                 tank.size
@@ -235,6 +270,35 @@ impl FuelTank {
         tank.block_metered_fuel = 0;
     }
 
+    /// Burns only the fuel granted to a reverted message that capped its own spending, so
+    /// the rest of the transaction's allocation is still refunded to the block.
+    pub fn burn_fuel(n: u64) {
+        let mut tank = _FUEL_TANK.write().unwrap();
+        let tank = tank.as_mut().unwrap();
+        tank.transaction_fuel = tank.transaction_fuel.saturating_sub(n);
+        tank.block_metered_fuel = tank.block_metered_fuel.saturating_sub(n);
+    }
+
+    /// Grants the fuel limit carried in a protostone. The request is raised to the minimum fuel
+    /// and capped at what is left of the transaction's allocation; the fuel left in the block
+    /// is never drawn on, so a large request cannot starve the transactions after it.
+    pub fn request_fuel(requested: u64) -> u64 {
+        let tank = _FUEL_TANK.read().unwrap();
+        let tank = tank.as_ref().unwrap();
+        let requested = std::cmp::max(tank.minimum_fuel, requested);
+        let granted = std::cmp::min(requested, tank.transaction_fuel);
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!("Fuel limit for transaction {}:", tank.current_txindex);
+            println!("  - Requested fuel: {}", requested);
+            println!("  - Granted fuel: {}", granted);
+            println!("  - Block fuel remaining: {}", tank.block_fuel);
+        }
+
+        granted
+    }
+
     pub fn start_fuel() -> u64 {
         _FUEL_TANK
             .read()
@@ -254,10 +318,25 @@ pub const FUEL_SEQUENCE: u64 = 5;
 pub const FUEL_FUEL: u64 = 5;
 pub const FUEL_EXTCALL: u64 = 500;
 pub const FUEL_HEIGHT: u64 = 10;
+pub const FUEL_HEADER: u64 = 50;
+pub const FUEL_PER_BLOCK_TIME: u64 = 10; // Charged per block time read for median-time-past
 pub const FUEL_BALANCE: u64 = 10;
+pub const FUEL_IS_DEPLOYED: u64 = 10;
 pub const FUEL_EXTCALL_DEPLOY: u64 = 10_000;
 pub const FUEL_LOAD_BLOCK: u64 = 1000; // Fixed cost for loading a block
 pub const FUEL_LOAD_TRANSACTION: u64 = 500; // Fixed cost for loading a transaction
+pub const FUEL_HASH: u64 = 50; // Fixed cost per native hash invocation
+pub const FUEL_PER_SHA256_BYTE: u64 = 1;
+pub const FUEL_PER_HASH160_BYTE: u64 = 1;
+pub const FUEL_PER_KECCAK256_BYTE: u64 = 2;
+pub const FUEL_VERIFY_SCHNORR: u64 = 5000; // Fixed cost for a BIP340 signature check
+pub const FUEL_VERIFY_ECDSA: u64 = 5000; // Fixed cost for an ECDSA signature check
+pub const FUEL_EMIT: u64 = 100; // Fixed cost per emitted event, plus FUEL_PER_STORE_BYTE
+pub const FUEL_PER_STORAGE_KEY: u64 = 20; // Charged per key scanned when listing storage keys
+pub const FUEL_PER_PREVOUT: u64 = 100; // Charged per input when reading spent outputs
+pub const FUEL_BIGNUM: u64 = 100; // Fixed cost per big-number precompile operation
+pub const FUEL_PER_BIGNUM_EXPONENT_BIT: u64 = 20; // Extra cost per exponent bit for POW
+pub const FUEL_PER_MERKLE_LEVEL: u64 = 150; // One double-SHA256 over a 64 byte node
 
 pub trait Fuelable {
     fn consume_fuel(&mut self, n: u64) -> Result<()>;
@@ -285,3 +364,8 @@ pub fn compute_extcall_fuel(savecount: u64) -> Result<u64> {
     let save_fuel = overflow_error(FUEL_PER_STORE_BYTE.checked_mul(savecount))?;
     overflow_error::<u64>(FUEL_EXTCALL.checked_add(save_fuel))
 }
+
+pub fn compute_hash_fuel(size: u64, fuel_per_byte: u64) -> Result<u64> {
+    let byte_fuel = overflow_error(fuel_per_byte.checked_mul(size))?;
+    overflow_error::<u64>(FUEL_HASH.checked_add(byte_fuel))
+}
@@ -1,21 +1,31 @@
-use super::fuel::compute_extcall_fuel;
+use super::cache::load_binary;
+use super::extcall::{
+    check_checkpoint_depth, enter_call, return_call, revert_call, subcontext,
+};
+use super::fuel::{compute_extcall_fuel, compute_hash_fuel};
 use super::{
-    get_memory, read_arraybuffer, send_to_arraybuffer, sequence_pointer, AlkanesState, Extcall,
-    Saveable, SaveableExtendedCallResponse,
+    alkane_binary_pointer, get_memory, read_arraybuffer, send_to_arraybuffer, sequence_pointer,
+    AlkanesState, Extcall,
+};
+use crate::trace::save_event;
+use crate::utils::{
+    balance_pointer, median_time_past, pipe_storagemap_to, storage_keys_pointer, transfer_from,
+    MEDIAN_TIME_SPAN,
 };
-use crate::utils::{balance_pointer, pipe_storagemap_to, transfer_from};
-use crate::vm::{run_after_special, run_special_cellpacks};
+use crate::vm::{run_after_special_metered, run_special_cellpacks};
 use alkanes_support::{
     cellpack::Cellpack,
     id::AlkaneId,
     parcel::AlkaneTransferParcel,
+    prevout::Prevouts,
     response::CallResponse,
-    storage::StorageMap,
-    trace::{TraceContext, TraceEvent, TraceResponse},
+    storage::{StorageKeyPage, StorageMap},
+    trace::{TraceEmit, TraceEvent},
     utils::overflow_error,
 };
 #[allow(unused_imports)]
 use anyhow::{anyhow, Result};
+use bitcoin::{Amount, OutPoint, ScriptBuf, TxOut};
 use metashrew_core::index_pointer::IndexPointer;
 #[allow(unused_imports)]
 use metashrew_core::{
@@ -25,15 +35,26 @@ use metashrew_core::{
 use metashrew_support::index_pointer::KeyValuePointer;
 
 use crate::vm::fuel::{
-    consume_fuel, Fuelable, FUEL_BALANCE, FUEL_EXTCALL, FUEL_EXTCALL_DEPLOY, FUEL_FUEL,
-    FUEL_HEIGHT, FUEL_LOAD_BLOCK, FUEL_LOAD_TRANSACTION, FUEL_PER_LOAD_BYTE, FUEL_PER_REQUEST_BYTE,
-    FUEL_PER_STORE_BYTE, FUEL_SEQUENCE,
+    consume_fuel, Fuelable, FUEL_BALANCE, FUEL_EMIT, FUEL_EXTCALL, FUEL_EXTCALL_DEPLOY, FUEL_FUEL,
+    FUEL_HEADER, FUEL_HEIGHT, FUEL_IS_DEPLOYED, FUEL_LOAD_BLOCK, FUEL_LOAD_TRANSACTION,
+    FUEL_PER_BLOCK_TIME, FUEL_PER_HASH160_BYTE, FUEL_PER_KECCAK256_BYTE, FUEL_PER_LOAD_BYTE,
+    FUEL_PER_PREVOUT, FUEL_PER_REQUEST_BYTE, FUEL_PER_SHA256_BYTE, FUEL_PER_STORAGE_KEY,
+    FUEL_PER_STORE_BYTE, FUEL_SEQUENCE, FUEL_VERIFY_ECDSA, FUEL_VERIFY_SCHNORR,
+};
+use bitcoin::hashes::{hash160, sha256, Hash};
+use bitcoin::secp256k1::{
+    ecdsa, schnorr, Message, PublicKey, Secp256k1, VerifyOnly, XOnlyPublicKey,
 };
-use protorune_support::utils::consensus_encode;
+use once_cell::sync::Lazy;
+use protobuf::Message as _;
+use protorune_support::utils::{consensus_encode, outpoint_encode};
+use sha3::{Digest, Keccak256};
 use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 use wasmi::*;
 
+static SECP256K1: Lazy<Secp256k1<VerifyOnly>> = Lazy::new(Secp256k1::verification_only);
+
 pub struct AlkanesHostFunctionsImpl(());
 
 // New wrapper struct that ensures proper context management
@@ -158,6 +179,166 @@ impl AlkanesHostFunctionsImpl {
         consume_fuel(caller, fuel_cost)?;
         send_to_arraybuffer(caller, v.try_into()?, value.as_ref())
     }
+    fn external_storage(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        k: i32,
+    ) -> Result<(Vec<u8>, Arc<Vec<u8>>)> {
+        let (id, key) = {
+            let mem = get_memory(caller)?;
+            let data = mem.data(&caller);
+            (
+                AlkaneId::parse(&mut Cursor::new(read_arraybuffer(data, id)?))?,
+                read_arraybuffer(data, k)?,
+            )
+        };
+        let value = caller
+            .data_mut()
+            .context
+            .lock()
+            .unwrap()
+            .message
+            .atomic
+            .keyword("/alkanes/")
+            .select(&id.into())
+            .keyword("/storage/")
+            .select(&key)
+            .get();
+        Ok((key, value))
+    }
+    pub(super) fn request_external_storage(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        k: i32,
+    ) -> Result<i32> {
+        let (key, value) = Self::external_storage(caller, id, k)?;
+        let result: i32 = value.len().try_into()?;
+        let fuel_cost =
+            overflow_error(((key.len() + value.len()) as u64).checked_mul(FUEL_PER_REQUEST_BYTE))?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "request_external_storage: key_size={} bytes, result_size={} bytes, fuel_cost={}",
+                key.len(),
+                result,
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        Ok(result)
+    }
+    pub(super) fn load_external_storage(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        k: i32,
+        v: i32,
+    ) -> Result<i32> {
+        let (key, value) = Self::external_storage(caller, id, k)?;
+        let fuel_cost =
+            overflow_error(((key.len() + value.len()) as u64).checked_mul(FUEL_PER_LOAD_BYTE))?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "load_external_storage: key_size={} bytes, value_size={} bytes, fuel_cost={}",
+                key.len(),
+                value.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        send_to_arraybuffer(caller, v.try_into()?, value.as_ref())
+    }
+    fn storage_keys_page(
+        caller: &mut Caller<'_, AlkanesState>,
+        prefix: i32,
+        offset: i32,
+        limit: i32,
+    ) -> Result<(StorageKeyPage, u64)> {
+        let mem = get_memory(caller)?;
+        let prefix = {
+            let data = mem.data(&caller);
+            read_arraybuffer(data, prefix)?
+        };
+        let offset: u32 = offset.try_into()?;
+        let limit: u32 = limit.try_into()?;
+        if limit == 0 {
+            return Err(anyhow!("storage key page limit must be positive"));
+        }
+        let myself = caller.data_mut().context.lock().unwrap().myself.clone();
+        let keys = storage_keys_pointer(
+            &caller
+                .data_mut()
+                .context
+                .lock()
+                .unwrap()
+                .message
+                .atomic
+                .keyword("/alkanes/")
+                .select(&myself.into()),
+        );
+        let length = keys.length();
+        let end = std::cmp::min(length, offset.saturating_add(limit));
+        let mut page = StorageKeyPage::default();
+        for i in offset..end {
+            let key = keys.select_index(i).get();
+            if key.starts_with(&prefix) {
+                page.keys.push(key.as_ref().clone());
+            }
+        }
+        page.next = if end < length { Some(end) } else { None };
+        Ok((page, end.saturating_sub(offset) as u64))
+    }
+    pub(super) fn request_storage_keys(
+        caller: &mut Caller<'_, AlkanesState>,
+        prefix: i32,
+        offset: i32,
+        limit: i32,
+    ) -> Result<i32> {
+        let (page, scanned) = Self::storage_keys_page(caller, prefix, offset, limit)?;
+        let result: i32 = page.serialize().len().try_into()?;
+        let fuel_cost = overflow_error(
+            scanned
+                .checked_mul(FUEL_PER_STORAGE_KEY)
+                .and_then(|v| v.checked_add((result as u64).checked_mul(FUEL_PER_REQUEST_BYTE)?)),
+        )?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "request_storage_keys: scanned={} keys, result_size={} bytes, fuel_cost={}",
+                scanned, result, fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        Ok(result)
+    }
+    pub(super) fn load_storage_keys(
+        caller: &mut Caller<'_, AlkanesState>,
+        prefix: i32,
+        offset: i32,
+        limit: i32,
+        output: i32,
+    ) -> Result<i32> {
+        let (page, scanned) = Self::storage_keys_page(caller, prefix, offset, limit)?;
+        let serialized = page.serialize();
+        let fuel_cost = overflow_error(scanned.checked_mul(FUEL_PER_STORAGE_KEY).and_then(|v| {
+            v.checked_add((serialized.len() as u64).checked_mul(FUEL_PER_LOAD_BYTE)?)
+        }))?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "load_storage_keys: scanned={} keys, result_size={} bytes, fuel_cost={}",
+                scanned,
+                serialized.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        send_to_arraybuffer(caller, output.try_into()?, &serialized)
+    }
     pub(super) fn request_context(caller: &mut Caller<'_, AlkanesState>) -> Result<i32> {
         let result: i32 = caller
             .data_mut()
@@ -302,6 +483,76 @@ impl AlkanesHostFunctionsImpl {
         send_to_arraybuffer(caller, v.try_into()?, &transaction)?;
         Ok(())
     }
+    fn prevouts(caller: &mut Caller<'_, AlkanesState>) -> Result<Prevouts> {
+        let context = caller.data_mut().context.lock().unwrap();
+        let outputs = context
+            .message
+            .atomic
+            .derive(&*protorune::tables::OUTPOINT_TO_OUTPUT);
+        Ok(Prevouts(
+            context
+                .message
+                .transaction
+                .input
+                .iter()
+                .map(|input| -> Result<Option<TxOut>> {
+                    let value = outputs
+                        .select(&outpoint_encode(&input.previous_output)?)
+                        .get();
+                    if value.len() == 0 {
+                        return Ok(None);
+                    }
+                    let output = protorune_support::proto::protorune::Output::parse_from_bytes(
+                        value.as_ref(),
+                    )?;
+                    Ok(Some(TxOut {
+                        value: Amount::from_sat(output.value),
+                        script_pubkey: ScriptBuf::from_bytes(output.script),
+                    }))
+                })
+                .collect::<Result<Vec<Option<TxOut>>>>()?,
+        ))
+    }
+    pub(super) fn request_prevouts(caller: &mut Caller<'_, AlkanesState>) -> Result<i32> {
+        let prevouts = Self::prevouts(caller)?;
+        let result: i32 = prevouts.serialize().len().try_into()?;
+        let fuel_cost = overflow_error((prevouts.0.len() as u64).checked_mul(FUEL_PER_PREVOUT))?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "request_prevouts: inputs={}, result_size={} bytes, fuel_cost={}",
+                prevouts.0.len(),
+                result,
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        Ok(result)
+    }
+    pub(super) fn load_prevouts(caller: &mut Caller<'_, AlkanesState>, output: i32) -> Result<i32> {
+        let prevouts = Self::prevouts(caller)?;
+        let serialized = prevouts.serialize();
+        let fuel_cost = overflow_error(
+            (prevouts.0.len() as u64)
+                .checked_mul(FUEL_PER_PREVOUT)
+                .and_then(|v| {
+                    v.checked_add((serialized.len() as u64).checked_mul(FUEL_PER_LOAD_BYTE)?)
+                }),
+        )?;
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "load_prevouts: inputs={}, size={} bytes, fuel_cost={}",
+                prevouts.0.len(),
+                serialized.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+        send_to_arraybuffer(caller, output.try_into()?, &serialized)
+    }
     pub(super) fn request_block(caller: &mut Caller<'_, AlkanesState>) -> Result<i32> {
         let block_data =
             consensus_encode(&caller.data_mut().context.lock().unwrap().message.block)?;
@@ -391,6 +642,24 @@ impl AlkanesHostFunctionsImpl {
         send_to_arraybuffer(caller, output.try_into()?, &height)?;
         Ok(())
     }
+    pub(super) fn header(caller: &mut Caller<'_, AlkanesState>, output: i32) -> Result<()> {
+        let (height, header) = {
+            let context = caller.data_mut().context.lock().unwrap();
+            (context.message.height, context.message.block.header.clone())
+        };
+        let fuel_cost = FUEL_HEADER + height.min(MEDIAN_TIME_SPAN) * FUEL_PER_BLOCK_TIME;
+        consume_fuel(caller, fuel_cost)?;
+        let mut result = consensus_encode(&header)?;
+        result.extend(&median_time_past(height)?.to_le_bytes());
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!("header: height={}, fuel_cost={}", height, fuel_cost);
+        }
+
+        send_to_arraybuffer(caller, output.try_into()?, &result)?;
+        Ok(())
+    }
     pub(super) fn balance<'a>(
         caller: &mut Caller<'a, AlkanesState>,
         who_ptr: i32,
@@ -432,55 +701,325 @@ impl AlkanesHostFunctionsImpl {
         send_to_arraybuffer(caller, output.try_into()?, &balance)?;
         Ok(())
     }
-    fn _handle_extcall_abort<'a, T: Extcall>(
+    pub(super) fn sha256(
         caller: &mut Caller<'_, AlkanesState>,
-        e: anyhow::Error,
-        should_rollback: bool,
-    ) -> i32 {
-        println!("[[handle_extcall]] Error during extcall: {:?}", e);
-        let mut data: Vec<u8> = vec![0x08, 0xc3, 0x79, 0xa0];
-        data.extend(e.to_string().as_bytes());
+        data_ptr: i32,
+        output: i32,
+    ) -> Result<()> {
+        let data = {
+            let mem = get_memory(caller)?;
+            read_arraybuffer(mem.data(&caller), data_ptr)?
+        };
+        let fuel_cost = compute_hash_fuel(data.len() as u64, FUEL_PER_SHA256_BYTE)?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "sha256: data_size={} bytes, fuel_cost={}",
+                data.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
 
-        let mut revert_context: TraceResponse = TraceResponse::default();
-        revert_context.inner.data = data.clone();
+        let digest = sha256::Hash::hash(&data).to_byte_array().to_vec();
+        send_to_arraybuffer(caller, output.try_into()?, &digest)?;
+        Ok(())
+    }
+    fn read_alkane_id(caller: &mut Caller<'_, AlkanesState>, id: i32) -> Result<AlkaneId> {
+        let mem = get_memory(caller)?;
+        let data = mem.data(&caller);
+        AlkaneId::parse(&mut Cursor::new(read_arraybuffer(data, id)?))
+    }
+    pub(super) fn codehash(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        output: i32,
+    ) -> Result<i32> {
+        let id = Self::read_alkane_id(caller, id)?;
+        let compressed = alkane_binary_pointer(
+            &caller.data_mut().context.lock().unwrap().message.atomic,
+            &id,
+        )
+        .get();
+        if compressed.len() == 0 {
+            consume_fuel(caller, FUEL_IS_DEPLOYED)?;
+            return Ok(0);
+        }
+        // the compressed bytes are paid for before they are unpacked, and whatever the
+        // binary holds beyond them once it is
+        consume_fuel(
+            caller,
+            compute_hash_fuel(compressed.len() as u64, FUEL_PER_SHA256_BYTE)?,
+        )?;
+        let binary = load_binary(&id, compressed.clone())?;
+        let fuel_cost = overflow_error(
+            FUEL_PER_SHA256_BYTE.checked_mul(binary.len().saturating_sub(compressed.len()) as u64),
+        )?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "codehash: alkane=[{},{}], compressed_size={} bytes, binary_size={} bytes, fuel_cost={}",
+                id.block,
+                id.tx,
+                compressed.len(),
+                binary.len(),
+                fuel_cost
+            );
+        }
 
-        let mut response = CallResponse::default();
-        response.data = data.clone();
-        let serialized = response.serialize();
+        consume_fuel(caller, fuel_cost)?;
 
-        // Store the serialized length before we drop context_guard
-        let result = (serialized.len() as i32).checked_neg().unwrap_or(-1);
+        let digest = sha256::Hash::hash(&binary).to_byte_array().to_vec();
+        send_to_arraybuffer(caller, output.try_into()?, &digest)?;
+        Ok(1)
+    }
+    pub(super) fn is_deployed(caller: &mut Caller<'_, AlkanesState>, id: i32) -> Result<i32> {
+        let id = Self::read_alkane_id(caller, id)?;
+        let deployed = alkane_binary_pointer(
+            &caller.data_mut().context.lock().unwrap().message.atomic,
+            &id,
+        )
+        .get()
+        .len()
+            != 0;
 
-        // Handle revert state in a separate scope so context_guard is dropped
+        #[cfg(feature = "debug-log")]
         {
-            let mut context_guard = caller.data_mut().context.lock().unwrap();
-            context_guard
-                .trace
-                .clock(TraceEvent::RevertContext(revert_context));
-            if should_rollback {
-                context_guard.message.atomic.rollback();
-            }
-            context_guard.returndata = serialized;
-            // context_guard is dropped here when the scope ends
+            println!(
+                "is_deployed: alkane=[{},{}], deployed={}, fuel_cost={}",
+                id.block, id.tx, deployed, FUEL_IS_DEPLOYED
+            );
         }
 
+        consume_fuel(caller, FUEL_IS_DEPLOYED)?;
+        Ok(deployed as i32)
+    }
+    pub(super) fn hash160(
+        caller: &mut Caller<'_, AlkanesState>,
+        data_ptr: i32,
+        output: i32,
+    ) -> Result<()> {
+        let data = {
+            let mem = get_memory(caller)?;
+            read_arraybuffer(mem.data(&caller), data_ptr)?
+        };
+        let fuel_cost = compute_hash_fuel(data.len() as u64, FUEL_PER_HASH160_BYTE)?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "hash160: data_size={} bytes, fuel_cost={}",
+                data.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+
+        let digest = hash160::Hash::hash(&data).to_byte_array().to_vec();
+        send_to_arraybuffer(caller, output.try_into()?, &digest)?;
+        Ok(())
+    }
+    pub(super) fn keccak256(
+        caller: &mut Caller<'_, AlkanesState>,
+        data_ptr: i32,
+        output: i32,
+    ) -> Result<()> {
+        let data = {
+            let mem = get_memory(caller)?;
+            read_arraybuffer(mem.data(&caller), data_ptr)?
+        };
+        let fuel_cost = compute_hash_fuel(data.len() as u64, FUEL_PER_KECCAK256_BYTE)?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "keccak256: data_size={} bytes, fuel_cost={}",
+                data.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+
+        let digest = Keccak256::digest(&data).to_vec();
+        send_to_arraybuffer(caller, output.try_into()?, &digest)?;
+        Ok(())
+    }
+    fn check_schnorr(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let (Ok(pubkey), Ok(message), Ok(signature)) = (
+            XOnlyPublicKey::from_slice(pubkey),
+            Message::from_digest_slice(message),
+            schnorr::Signature::from_slice(signature),
+        ) else {
+            return false;
+        };
+        SECP256K1
+            .verify_schnorr(&signature, &message, &pubkey)
+            .is_ok()
+    }
+    fn check_ecdsa(pubkey: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let signature = if signature.len() == 64 {
+            ecdsa::Signature::from_compact(signature)
+        } else {
+            ecdsa::Signature::from_der(signature)
+        };
+        let (Ok(pubkey), Ok(message), Ok(signature)) = (
+            PublicKey::from_slice(pubkey),
+            Message::from_digest_slice(message),
+            signature,
+        ) else {
+            return false;
+        };
+        SECP256K1
+            .verify_ecdsa(&message, &signature, &pubkey)
+            .is_ok()
+    }
+    fn read_signature_args(
+        caller: &mut Caller<'_, AlkanesState>,
+        pubkey_ptr: i32,
+        message_ptr: i32,
+        signature_ptr: i32,
+    ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let mem = get_memory(caller)?;
+        let data = mem.data(&caller);
+        Ok((
+            read_arraybuffer(data, pubkey_ptr)?,
+            read_arraybuffer(data, message_ptr)?,
+            read_arraybuffer(data, signature_ptr)?,
+        ))
+    }
+    pub(super) fn verify_schnorr(
+        caller: &mut Caller<'_, AlkanesState>,
+        pubkey_ptr: i32,
+        message_ptr: i32,
+        signature_ptr: i32,
+    ) -> Result<i32> {
+        let (pubkey, message, signature) =
+            Self::read_signature_args(caller, pubkey_ptr, message_ptr, signature_ptr)?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "verify_schnorr: pubkey_size={} bytes, signature_size={} bytes, fuel_cost={}",
+                pubkey.len(),
+                signature.len(),
+                FUEL_VERIFY_SCHNORR
+            );
+        }
+
+        consume_fuel(caller, FUEL_VERIFY_SCHNORR)?;
+
+        Ok(Self::check_schnorr(&pubkey, &message, &signature) as i32)
+    }
+    pub(super) fn verify_ecdsa(
+        caller: &mut Caller<'_, AlkanesState>,
+        pubkey_ptr: i32,
+        message_ptr: i32,
+        signature_ptr: i32,
+    ) -> Result<i32> {
+        let (pubkey, message, signature) =
+            Self::read_signature_args(caller, pubkey_ptr, message_ptr, signature_ptr)?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "verify_ecdsa: pubkey_size={} bytes, signature_size={} bytes, fuel_cost={}",
+                pubkey.len(),
+                signature.len(),
+                FUEL_VERIFY_ECDSA
+            );
+        }
+
+        consume_fuel(caller, FUEL_VERIFY_ECDSA)?;
+
+        Ok(Self::check_ecdsa(&pubkey, &message, &signature) as i32)
+    }
+    fn _handle_extcall_abort<'a, T: Extcall>(
+        caller: &mut Caller<'_, AlkanesState>,
+        e: anyhow::Error,
+        callee: &AlkaneId,
+        callee_depth: u32,
+        depth: usize,
+        fuel_used: u64,
+    ) -> i32 {
+        println!("[[handle_extcall]] Error during extcall: {:?}", e);
+        // the caller pays for what the failed call burned, since after __try_call it runs on
+        if let Ok(remaining) = caller.get_fuel() {
+            let _ = caller.set_fuel(remaining.saturating_sub(fuel_used));
+        }
+        let result = {
+            let mut context_guard = caller.data_mut().context.lock().unwrap();
+            let mut response = CallResponse::default();
+            response.data = revert_call(
+                &mut context_guard,
+                &e,
+                callee,
+                callee_depth,
+                depth,
+                fuel_used,
+            );
+            context_guard.returndata = response.serialize();
+            (context_guard.returndata.len() as i32)
+                .checked_neg()
+                .unwrap_or(-1)
+        };
+
         // Now we can use caller again
-        Self::_abort(caller.into());
+        if !T::recoverable() {
+            Self::_abort(caller.into());
+        }
         result
     }
+    fn check_static_extcall<T: Extcall>(
+        cellpack: &Cellpack,
+        incoming_alkanes: &AlkaneTransferParcel,
+        storage_map: &StorageMap,
+    ) -> Result<()> {
+        if !T::isstatic() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted a nested {}",
+                if T::isdelegate() {
+                    "__delegatecall"
+                } else {
+                    "__call"
+                }
+            ));
+        }
+        if cellpack.target.is_deployment() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted to CREATE through [{}, {}]",
+                cellpack.target.block,
+                cellpack.target.tx
+            ));
+        }
+        if !storage_map.0.is_empty() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted to write {} storage keys",
+                storage_map.0.len()
+            ));
+        }
+        if !incoming_alkanes.0.is_empty() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted to transfer {} alkanes",
+                incoming_alkanes.0.len()
+            ));
+        }
+        Ok(())
+    }
     fn _prepare_extcall_before_checkpoint<'a, T: Extcall>(
         caller: &mut Caller<'_, AlkanesState>,
         cellpack_ptr: i32,
         incoming_alkanes_ptr: i32,
         checkpoint_ptr: i32,
     ) -> Result<(Cellpack, AlkaneTransferParcel, StorageMap, u64)> {
-        let current_depth = AlkanesHostFunctionsImpl::get_checkpoint_depth(caller);
-        if current_depth >= 75 {
-            return Err(anyhow!(format!(
-                "Possible infinite recursion encountered: checkpoint depth too large({})",
-                current_depth
-            )));
-        }
+        check_checkpoint_depth(&caller.data().context.lock().unwrap())?;
+        // Settle memory the caller grew so far before it pays for the call
+        let memory_fuel = caller.data_mut().limiter.take_fuel()?;
+        caller.set_fuel(overflow_error(caller.get_fuel()?.checked_sub(memory_fuel))?)?;
         // Read all input data first
         let mem = get_memory(caller)?;
         let data = mem.data(&caller);
@@ -491,6 +1030,9 @@ impl AlkanesHostFunctionsImpl {
         let storage_map_buffer = read_arraybuffer(data, checkpoint_ptr)?;
         let storage_map_len = storage_map_buffer.len();
         let storage_map = StorageMap::parse(&mut Cursor::new(storage_map_buffer))?;
+        if caller.data_mut().context.lock().unwrap().is_static {
+            Self::check_static_extcall::<T>(&cellpack, &incoming_alkanes, &storage_map)?;
+        }
         // Handle deployment fuel first
         if cellpack.target.is_deployment() {
             #[cfg(feature = "debug-log")]
@@ -516,6 +1058,13 @@ impl AlkanesHostFunctionsImpl {
         checkpoint_ptr: i32,
         _start_fuel: u64, // this arg is not used, but cannot be removed due to backwards compat
     ) -> i32 {
+        let depth = Self::get_checkpoint_depth(caller);
+        // a call that cannot be read or made is reported against the caller's own frame
+        let (mut callee, caller_depth) = {
+            let context = caller.data().context.lock().unwrap();
+            (context.myself.clone(), context.depth)
+        };
+        let mut fuel_used = 0;
         match Self::_prepare_extcall_before_checkpoint::<T>(
             caller,
             cellpack_ptr,
@@ -523,18 +1072,35 @@ impl AlkanesHostFunctionsImpl {
             checkpoint_ptr,
         ) {
             Ok((cellpack, incoming_alkanes, storage_map, storage_map_len)) => {
+                callee = cellpack.target.clone();
                 match Self::extcall::<T>(
                     caller,
                     cellpack,
                     incoming_alkanes,
                     storage_map,
                     storage_map_len,
+                    &mut callee,
+                    &mut fuel_used,
                 ) {
                     Ok(v) => v,
-                    Err(e) => Self::_handle_extcall_abort::<T>(caller, e, true),
+                    Err(e) => Self::_handle_extcall_abort::<T>(
+                        caller,
+                        e,
+                        &callee,
+                        caller_depth + 1,
+                        depth,
+                        fuel_used,
+                    ),
                 }
             }
-            Err(e) => Self::_handle_extcall_abort::<T>(caller, e, false),
+            Err(e) => Self::_handle_extcall_abort::<T>(
+                caller,
+                e,
+                &callee,
+                caller_depth,
+                depth,
+                fuel_used,
+            ),
         }
     }
     pub(super) fn extcall<'a, T: Extcall>(
@@ -543,26 +1109,37 @@ impl AlkanesHostFunctionsImpl {
         incoming_alkanes: AlkaneTransferParcel,
         storage_map: StorageMap,
         storage_map_len: u64,
+        callee: &mut AlkaneId,
+        fuel_used: &mut u64,
     ) -> Result<i32> {
         // Prepare subcontext data
+        let memory_grown = caller.data().limiter.grown();
         let (subcontext, binary_rc) = {
             let mut context_guard = caller.data_mut().context.lock().unwrap();
             context_guard.message.atomic.checkpoint();
             let myself = context_guard.myself.clone();
-            let caller_id = context_guard.caller.clone();
             std::mem::drop(context_guard); // Release lock before calling run_special_cellpacks
 
             let (_subcaller, submyself, binary) =
                 run_special_cellpacks(caller.data_mut().context.clone(), &cellpack)?;
+            *callee = submyself.clone();
+            caller
+                .data()
+                .context
+                .lock()
+                .unwrap()
+                .check_call(&submyself, T::isdelegate())?;
 
             // Re-acquire lock for state updates
             {
                 let mut context_guard = caller.data_mut().context.lock().unwrap();
+                let height = context_guard.message.height;
                 pipe_storagemap_to(
                     &storage_map,
                     &mut context_guard.message.atomic.derive(
                         &IndexPointer::from_keyword("/alkanes/").select(&myself.clone().into()),
                     ),
+                    height,
                 );
 
                 transfer_from(
@@ -578,16 +1155,13 @@ impl AlkanesHostFunctionsImpl {
             let context_guard = caller.data_mut().context.lock().unwrap();
 
             // Create subcontext
-            let mut subbed = context_guard.clone();
-            subbed.message.atomic = context_guard
-                .message
-                .atomic
-                .derive(&IndexPointer::default());
-            (subbed.caller, subbed.myself) =
-                T::change_context(submyself.clone(), caller_id, myself.clone());
-            subbed.returndata = vec![];
-            subbed.incoming_alkanes = incoming_alkanes.clone();
-            subbed.inputs = cellpack.inputs.clone();
+            let mut subbed = subcontext::<T>(
+                &context_guard,
+                submyself.clone(),
+                &cellpack,
+                incoming_alkanes.clone(),
+            );
+            subbed.memory_grown = memory_grown;
             (subbed, binary)
         };
 
@@ -604,37 +1178,80 @@ impl AlkanesHostFunctionsImpl {
 
         consume_fuel(caller, total_fuel)?;
 
-        let mut trace_context: TraceContext = subcontext.flat().into();
         let start_fuel: u64 = caller.get_fuel()?;
-        trace_context.fuel = start_fuel;
-        let event: TraceEvent = T::event(trace_context);
-        subcontext.trace.clock(event);
+        enter_call::<T>(&subcontext, start_fuel);
 
         // Run the call in a new context
-        let (response, gas_used) = run_after_special(
+        let (result, consumed) = run_after_special_metered(
             Arc::new(Mutex::new(subcontext.clone())),
             binary_rc,
             start_fuel,
-        )?;
+        );
+        *fuel_used = consumed;
+        let (response, gas_used) = result?;
         let serialized = CallResponse::from(response.clone().into()).serialize();
         {
             caller.set_fuel(overflow_error(start_fuel.checked_sub(gas_used))?)?;
-            let mut return_context: TraceResponse = response.clone().into();
-            return_context.fuel_used = gas_used;
 
             // Update trace and context state
             let mut context_guard = caller.data_mut().context.lock().unwrap();
-            context_guard
-                .trace
-                .clock(TraceEvent::ReturnContext(return_context));
-            let mut saveable: SaveableExtendedCallResponse = response.clone().into();
-            saveable.associate(&subcontext);
-            saveable.save(&mut context_guard.message.atomic)?;
+            return_call(&mut context_guard, &subcontext, &response, gas_used)?;
             context_guard.returndata = serialized.clone();
             T::handle_atomic(&mut context_guard.message.atomic);
         }
         Ok(serialized.len() as i32)
     }
+    pub(super) fn emit<'a>(
+        caller: &mut Caller<'_, AlkanesState>,
+        topic_ptr: i32,
+        data_ptr: i32,
+    ) -> Result<()> {
+        let (topic, data) = {
+            let mem = get_memory(caller)?;
+            let data = mem.data(&caller);
+            (
+                read_arraybuffer(data, topic_ptr)?,
+                read_arraybuffer(data, data_ptr)?,
+            )
+        };
+        let fuel_cost = overflow_error(
+            ((topic.len() + data.len()) as u64)
+                .checked_mul(FUEL_PER_STORE_BYTE)
+                .and_then(|v| v.checked_add(FUEL_EMIT)),
+        )?;
+
+        #[cfg(feature = "debug-log")]
+        {
+            println!(
+                "emit: topic_size={} bytes, data_size={} bytes, fuel_cost={}",
+                topic.len(),
+                data.len(),
+                fuel_cost
+            );
+        }
+
+        consume_fuel(caller, fuel_cost)?;
+
+        let mut context_guard = caller.data_mut().context.lock().unwrap();
+        if context_guard.is_static {
+            return Err(anyhow!("ALKANES: revert: static call attempted to __emit"));
+        }
+        let emit = TraceEmit {
+            alkane: context_guard.myself.clone(),
+            topic,
+            data,
+        };
+        // events are written through the atomic so they are discarded along with
+        // the state changes of a reverted frame
+        let outpoint = OutPoint {
+            txid: context_guard.message.transaction.compute_txid(),
+            vout: context_guard.message.vout,
+        };
+        let height = context_guard.message.height;
+        save_event(&mut context_guard.message.atomic, &outpoint, height, &emit)?;
+        context_guard.trace.clock(TraceEvent::Emit(emit));
+        Ok(())
+    }
     pub(super) fn log<'a>(caller: &mut Caller<'_, AlkanesState>, v: i32) -> Result<()> {
         let mem = get_memory(caller)?;
         let message = {
@@ -697,6 +1314,58 @@ impl SafeAlkanesHostFunctionsImpl {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::load_storage(c, k, v))
     }
 
+    pub(super) fn request_external_storage(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        k: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::request_external_storage(c, id, k)
+        })
+    }
+
+    pub(super) fn load_external_storage(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        k: i32,
+        v: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::load_external_storage(c, id, k, v)
+        })
+    }
+
+    pub(super) fn request_storage_keys(
+        caller: &mut Caller<'_, AlkanesState>,
+        prefix: i32,
+        offset: i32,
+        limit: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::request_storage_keys(c, prefix, offset, limit)
+        })
+    }
+
+    pub(super) fn load_storage_keys(
+        caller: &mut Caller<'_, AlkanesState>,
+        prefix: i32,
+        offset: i32,
+        limit: i32,
+        output: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::load_storage_keys(c, prefix, offset, limit, output)
+        })
+    }
+
+    pub(super) fn emit<'a>(
+        caller: &mut Caller<'_, AlkanesState>,
+        topic: i32,
+        data: i32,
+    ) -> Result<()> {
+        Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::emit(c, topic, data))
+    }
+
     pub(super) fn log<'a>(caller: &mut Caller<'_, AlkanesState>, v: i32) -> Result<()> {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::log(c, v))
     }
@@ -720,6 +1389,16 @@ impl SafeAlkanesHostFunctionsImpl {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::load_context(c, v))
     }
 
+    pub(super) fn request_prevouts(caller: &mut Caller<'_, AlkanesState>) -> Result<i32> {
+        Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::request_prevouts(c))
+    }
+
+    pub(super) fn load_prevouts(caller: &mut Caller<'_, AlkanesState>, output: i32) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::load_prevouts(c, output)
+        })
+    }
+
     pub(super) fn request_transaction(caller: &mut Caller<'_, AlkanesState>) -> Result<i32> {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::request_transaction(c))
     }
@@ -750,10 +1429,80 @@ impl SafeAlkanesHostFunctionsImpl {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::fuel(c, output))
     }
 
+    pub(super) fn header(caller: &mut Caller<'_, AlkanesState>, output: i32) -> Result<()> {
+        Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::header(c, output))
+    }
+
     pub(super) fn height(caller: &mut Caller<'_, AlkanesState>, output: i32) -> Result<()> {
         Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::height(c, output))
     }
 
+    pub(super) fn sha256(
+        caller: &mut Caller<'_, AlkanesState>,
+        data: i32,
+        output: i32,
+    ) -> Result<()> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::sha256(c, data, output)
+        })
+    }
+
+    pub(super) fn codehash(
+        caller: &mut Caller<'_, AlkanesState>,
+        id: i32,
+        output: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::codehash(c, id, output)
+        })
+    }
+
+    pub(super) fn is_deployed(caller: &mut Caller<'_, AlkanesState>, id: i32) -> Result<i32> {
+        Self::with_context_safety(caller, |c| AlkanesHostFunctionsImpl::is_deployed(c, id))
+    }
+
+    pub(super) fn hash160(
+        caller: &mut Caller<'_, AlkanesState>,
+        data: i32,
+        output: i32,
+    ) -> Result<()> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::hash160(c, data, output)
+        })
+    }
+
+    pub(super) fn keccak256(
+        caller: &mut Caller<'_, AlkanesState>,
+        data: i32,
+        output: i32,
+    ) -> Result<()> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::keccak256(c, data, output)
+        })
+    }
+
+    pub(super) fn verify_schnorr(
+        caller: &mut Caller<'_, AlkanesState>,
+        pubkey: i32,
+        message: i32,
+        signature: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::verify_schnorr(c, pubkey, message, signature)
+        })
+    }
+
+    pub(super) fn verify_ecdsa(
+        caller: &mut Caller<'_, AlkanesState>,
+        pubkey: i32,
+        message: i32,
+        signature: i32,
+    ) -> Result<i32> {
+        Self::with_context_safety(caller, |c| {
+            AlkanesHostFunctionsImpl::verify_ecdsa(c, pubkey, message, signature)
+        })
+    }
+
     pub(super) fn handle_extcall<'a, T: Extcall>(
         caller: &mut Caller<'a, AlkanesState>,
         cellpack_ptr: i32,
@@ -1,13 +1,15 @@
+use super::cache::load_module;
 use super::{
-    extcall::*, read_arraybuffer, AlkanesExportsImpl, AlkanesRuntimeContext, AlkanesState,
-    SafeAlkanesHostFunctionsImpl, MEMORY_LIMIT,
+    extcall::*, protocol_rules, protocol_schedule, read_arraybuffer, AlkanesExportsImpl,
+    AlkanesLimiter, AlkanesRuntimeContext, AlkanesState, SafeAlkanesHostFunctionsImpl,
+    INITIAL_MEMORY_PAGES,
 };
 use alkanes_support::{
     response::{CallResponse, ExtendedCallResponse},
+    revert::{Revert, REVERT_CODE_VM},
     trace::{TraceEvent, TraceResponse},
 };
 use anyhow::{anyhow, Result};
-use hex;
 use std::sync::{Arc, Mutex};
 use wasmi::*;
 
@@ -35,6 +37,12 @@ impl AlkanesInstance {
             Ok(())
         }
     }
+    /// Deducts the fuel owed for memory grown since the last charge.
+    pub fn charge_memory_growth(&mut self) -> Result<()> {
+        let fuel = self.store.data_mut().limiter.take_fuel()?;
+        self.consume_fuel(fuel)
+            .map_err(|_| anyhow!("ALKANES: revert: all fuel consumed by memory growth"))
+    }
     pub fn read_arraybuffer(&mut self, data_start: i32) -> anyhow::Result<Vec<u8>> {
         read_arraybuffer(self.get_memory()?.data(&self.store), data_start)
     }
@@ -67,34 +75,9 @@ impl AlkanesInstance {
             .atomic
             .rollback();
     }
-    pub fn from_alkane(
-        context: Arc<Mutex<AlkanesRuntimeContext>>,
-        binary: Arc<Vec<u8>>,
-        start_fuel: u64,
-    ) -> Result<Self> {
-        /*
-        let binary = context
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&context.myself.clone().into())
-            .get();
-            */
-        let mut config = Config::default();
-        config.consume_fuel(true);
-        let engine = Engine::new(&config);
-        let mut store = Store::<AlkanesState>::new(
-            &engine,
-            AlkanesState {
-                had_failure: false,
-                limiter: StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build(),
-                context: context.clone(),
-            },
-        );
-        store.limiter(|state| &mut state.limiter);
-        Store::<AlkanesState>::set_fuel(&mut store, start_fuel)?; // TODO: implement gas limits
-        let module = Module::new(&engine, &mut &binary[..])?;
-        let mut linker: Linker<AlkanesState> = Linker::<AlkanesState>::new(&engine);
+    /// Builds the linker exposing every host function to alkane binaries.
+    pub(crate) fn linker(engine: &Engine) -> Result<Linker<AlkanesState>> {
+        let mut linker: Linker<AlkanesState> = Linker::<AlkanesState>::new(engine);
         linker.func_wrap("env", "abort", SafeAlkanesHostFunctionsImpl::abort)?;
         linker.func_wrap(
             "env",
@@ -122,6 +105,73 @@ impl AlkanesInstance {
                 }
             },
         )?;
+        linker.func_wrap(
+            "env",
+            "__request_external_storage",
+            |mut caller: Caller<'_, AlkanesState>, id: i32, k: i32| {
+                match SafeAlkanesHostFunctionsImpl::request_external_storage(&mut caller, id, k) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__load_external_storage",
+            |mut caller: Caller<'_, AlkanesState>, id: i32, k: i32, v: i32| {
+                match SafeAlkanesHostFunctionsImpl::load_external_storage(&mut caller, id, k, v) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__request_storage_keys",
+            |mut caller: Caller<'_, AlkanesState>, prefix: i32, offset: i32, limit: i32| {
+                match SafeAlkanesHostFunctionsImpl::request_storage_keys(
+                    &mut caller,
+                    prefix,
+                    offset,
+                    limit,
+                ) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__load_storage_keys",
+            |mut caller: Caller<'_, AlkanesState>,
+             prefix: i32,
+             offset: i32,
+             limit: i32,
+             output: i32| {
+                match SafeAlkanesHostFunctionsImpl::load_storage_keys(
+                    &mut caller,
+                    prefix,
+                    offset,
+                    limit,
+                    output,
+                ) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
         linker.func_wrap(
             "env",
             "__log",
@@ -131,6 +181,15 @@ impl AlkanesInstance {
                 }
             },
         )?;
+        linker.func_wrap(
+            "env",
+            "__emit",
+            |mut caller: Caller<'_, AlkanesState>, topic: i32, data: i32| {
+                if let Err(_e) = SafeAlkanesHostFunctionsImpl::emit(&mut caller, topic, data) {
+                    SafeAlkanesHostFunctionsImpl::_abort(caller);
+                }
+            },
+        )?;
         linker.func_wrap(
             "env",
             "__balance",
@@ -195,7 +254,106 @@ impl AlkanesInstance {
                 }
             },
         )?;
+        linker.func_wrap(
+            "env",
+            "__header",
+            |mut caller: Caller<'_, AlkanesState>, output: i32| {
+                if let Err(_e) = SafeAlkanesHostFunctionsImpl::header(&mut caller, output) {
+                    SafeAlkanesHostFunctionsImpl::_abort(caller);
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__sha256",
+            |mut caller: Caller<'_, AlkanesState>, data: i32, output: i32| {
+                if let Err(_e) = SafeAlkanesHostFunctionsImpl::sha256(&mut caller, data, output) {
+                    SafeAlkanesHostFunctionsImpl::_abort(caller);
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__codehash",
+            |mut caller: Caller<'_, AlkanesState>, id: i32, output: i32| -> i32 {
+                match SafeAlkanesHostFunctionsImpl::codehash(&mut caller, id, output) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__is_deployed",
+            |mut caller: Caller<'_, AlkanesState>, id: i32| -> i32 {
+                match SafeAlkanesHostFunctionsImpl::is_deployed(&mut caller, id) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__hash160",
+            |mut caller: Caller<'_, AlkanesState>, data: i32, output: i32| {
+                if let Err(_e) = SafeAlkanesHostFunctionsImpl::hash160(&mut caller, data, output) {
+                    SafeAlkanesHostFunctionsImpl::_abort(caller);
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__keccak256",
+            |mut caller: Caller<'_, AlkanesState>, data: i32, output: i32| {
+                if let Err(_e) = SafeAlkanesHostFunctionsImpl::keccak256(&mut caller, data, output)
+                {
+                    SafeAlkanesHostFunctionsImpl::_abort(caller);
+                }
+            },
+        )?;
 
+        linker.func_wrap(
+            "env",
+            "__verify_schnorr",
+            |mut caller: Caller<'_, AlkanesState>, pubkey: i32, message: i32, signature: i32| {
+                match SafeAlkanesHostFunctionsImpl::verify_schnorr(
+                    &mut caller,
+                    pubkey,
+                    message,
+                    signature,
+                ) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__verify_ecdsa",
+            |mut caller: Caller<'_, AlkanesState>, pubkey: i32, message: i32, signature: i32| {
+                match SafeAlkanesHostFunctionsImpl::verify_ecdsa(
+                    &mut caller,
+                    pubkey,
+                    message,
+                    signature,
+                ) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
         linker.func_wrap(
             "env",
             "__returndatacopy",
@@ -228,6 +386,32 @@ impl AlkanesInstance {
                 }
             },
         )?;
+        linker.func_wrap(
+            "env",
+            "__request_prevouts",
+            |mut caller: Caller<'_, AlkanesState>| -> i32 {
+                match SafeAlkanesHostFunctionsImpl::request_prevouts(&mut caller) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__load_prevouts",
+            |mut caller: Caller<'_, AlkanesState>, output: i32| -> i32 {
+                match SafeAlkanesHostFunctionsImpl::load_prevouts(&mut caller, output) {
+                    Ok(v) => v,
+                    Err(_e) => {
+                        SafeAlkanesHostFunctionsImpl::_abort(caller);
+                        -1
+                    }
+                }
+            },
+        )?;
         /* removed below to prevent redundancy / requirement for archived chaindata */
         /*
         linker.func_wrap(
@@ -329,6 +513,105 @@ impl AlkanesInstance {
                 )
             },
         )?;
+        linker.func_wrap(
+            "env",
+            "__try_call",
+            |mut caller: Caller<'_, AlkanesState>,
+             cellpack_ptr: i32,
+             incoming_alkanes_ptr: i32,
+             checkpoint_ptr: i32,
+             start_fuel: u64|
+             -> i32 {
+                SafeAlkanesHostFunctionsImpl::handle_extcall::<TryCall<Call>>(
+                    &mut caller,
+                    cellpack_ptr,
+                    incoming_alkanes_ptr,
+                    checkpoint_ptr,
+                    start_fuel,
+                )
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__try_delegatecall",
+            |mut caller: Caller<'_, AlkanesState>,
+             cellpack_ptr: i32,
+             incoming_alkanes_ptr: i32,
+             checkpoint_ptr: i32,
+             start_fuel: u64|
+             -> i32 {
+                SafeAlkanesHostFunctionsImpl::handle_extcall::<TryCall<Delegatecall>>(
+                    &mut caller,
+                    cellpack_ptr,
+                    incoming_alkanes_ptr,
+                    checkpoint_ptr,
+                    start_fuel,
+                )
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "__try_staticcall",
+            |mut caller: Caller<'_, AlkanesState>,
+             cellpack_ptr: i32,
+             incoming_alkanes_ptr: i32,
+             checkpoint_ptr: i32,
+             start_fuel: u64|
+             -> i32 {
+                SafeAlkanesHostFunctionsImpl::handle_extcall::<TryCall<Staticcall>>(
+                    &mut caller,
+                    cellpack_ptr,
+                    incoming_alkanes_ptr,
+                    checkpoint_ptr,
+                    start_fuel,
+                )
+            },
+        )?;
+        Ok(linker)
+    }
+    pub fn from_alkane(
+        context: Arc<Mutex<AlkanesRuntimeContext>>,
+        binary: Arc<Vec<u8>>,
+        start_fuel: u64,
+    ) -> Result<Self> {
+        /*
+        let binary = context
+            .message
+            .atomic
+            .keyword("/alkanes/")
+            .select(&context.myself.clone().into())
+            .get();
+            */
+        let (engine, module) = load_module(&binary)?;
+        let (rules, memory_grown) = {
+            let mut context_guard = context.lock().unwrap();
+            let height = context_guard.message.height;
+            protocol_schedule().check_imports(&module, height)?;
+            let rules = protocol_rules(height);
+            if !rules.strict_static_calls {
+                context_guard.is_static = false;
+            }
+            if rules.call_guards && module.get_export("__nonreentrant").is_some() {
+                let myself = context_guard.myself.clone();
+                context_guard.locked.push(myself);
+            }
+            (rules, context_guard.memory_grown)
+        };
+        let mut store = Store::<AlkanesState>::new(
+            &engine,
+            AlkanesState {
+                had_failure: false,
+                limiter: AlkanesLimiter::new(
+                    memory_grown,
+                    rules.memory_budget,
+                    rules.fuel_per_memory_page,
+                ),
+                context: context.clone(),
+            },
+        );
+        store.limiter(|state| &mut state.limiter);
+        Store::<AlkanesState>::set_fuel(&mut store, start_fuel)?; // TODO: implement gas limits
+        let linker = Self::linker(&engine)?;
         let mut alkanes_instance = AlkanesInstance {
             instance: linker
                 .instantiate(&mut store, &module)?
@@ -340,9 +623,12 @@ impl AlkanesInstance {
 
         let current_pages = memory.size(&alkanes_instance.store);
 
-        if current_pages < 512 {
+        if current_pages < INITIAL_MEMORY_PAGES {
             memory
-                .grow(&mut alkanes_instance.store, 512 - current_pages)
+                .grow(
+                    &mut alkanes_instance.store,
+                    INITIAL_MEMORY_PAGES - current_pages,
+                )
                 .expect("Failed to grow memory");
         }
 
@@ -359,6 +645,9 @@ impl AlkanesInstance {
                 Ok(v) => {
                     if self.store.data().had_failure {
                         (v, true)
+                    } else if let Err(e) = self.charge_memory_growth() {
+                        err = Some(e);
+                        (ExtendedCallResponse::default(), true)
                     } else {
                         (v, false)
                     }
@@ -370,27 +659,64 @@ impl AlkanesInstance {
             }
         };
         self.reset();
-        if had_failure {
+        let static_violation = match had_failure {
+            true => None,
+            false => self.check_static_response(&call_response).err(),
+        };
+        if let Some(e) = static_violation {
+            let revert = self.revert(REVERT_CODE_VM, e.to_string());
             self.rollback();
-            if call_response.data.len() >= 4
-                && &call_response.data[0..4] == &[0x08, 0xc3, 0x79, 0xa0]
+            Err(revert)
+        } else if had_failure {
+            let height = self.store.data().context.lock().unwrap().message.height;
+            let revert = if let Some(revert) =
+                protocol_rules(height).decode_revert(&call_response.data)
             {
-                Err(anyhow!(format!(
-                    "ALKANES: revert: {}",
-                    String::from_utf8((&call_response.data[4..]).to_vec())
-                        .unwrap_or_else(|_| hex::encode(&call_response.data[4..]))
-                )))
+                self.revert(revert.code, format!("ALKANES: revert: {}", revert.message))
             } else if let Some(e) = err {
-                Err(anyhow!(format!("ALKANES: revert: {:?}", e)))
+                self.revert(REVERT_CODE_VM, format!("ALKANES: revert: {:?}", e))
             } else {
-                Err(anyhow!("ALKANES: revert"))
-            }
+                self.revert(REVERT_CODE_VM, "ALKANES: revert")
+            };
+            self.rollback();
+            Err(revert)
         } else {
             self.commit();
             Ok(call_response)
         }
     }
 
+    /// The contract cannot know its own call depth, and must not be able to claim another
+    /// alkane's id, so both are filled in here whatever the revert data said.
+    fn revert(&self, code: u32, message: impl Into<String>) -> anyhow::Error {
+        let context = self.store.data().context.lock().unwrap();
+        anyhow::Error::new(Revert::new(
+            code,
+            message,
+            context.myself.clone(),
+            context.depth,
+        ))
+    }
+
+    fn check_static_response(&self, response: &ExtendedCallResponse) -> Result<()> {
+        if !self.store.data().context.lock().unwrap().is_static {
+            return Ok(());
+        }
+        if !response.storage.0.is_empty() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted to write {} storage keys",
+                response.storage.0.len()
+            ));
+        }
+        if !response.alkanes.0.is_empty() {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted to transfer {} alkanes",
+                response.alkanes.0.len()
+            ));
+        }
+        Ok(())
+    }
+
     pub fn call_meta(&mut self) -> Result<Vec<u8>> {
         // Call the __meta function to get the ABI
         AlkanesExportsImpl::call_meta(self)
@@ -0,0 +1,83 @@
+use super::constants::{INITIAL_MEMORY_PAGES, MEMORY_LIMIT, WASM_PAGE_SIZE};
+use alkanes_support::utils::overflow_error;
+use anyhow::Result;
+use wasmi::errors::{MemoryError, TableError};
+use wasmi::{ResourceLimiter, StoreLimits, StoreLimitsBuilder};
+
+const INITIAL_MEMORY_SIZE: usize = INITIAL_MEMORY_PAGES as usize * WASM_PAGE_SIZE;
+
+/// Caps an instance's memory at `MEMORY_LIMIT` and keeps track of what it grows past the
+/// initial pages. That growth is charged fuel, and together with the growth of every frame
+/// further up the call stack it must fit the memory budget of the rules in force.
+pub struct AlkanesLimiter {
+    limits: StoreLimits,
+    ancestors: usize,
+    budget: usize,
+    fuel_per_page: u64,
+    memory_size: usize,
+    unpaid_pages: u64,
+}
+
+impl AlkanesLimiter {
+    /// `ancestors` is the memory already grown by the frames that led to this one.
+    pub fn new(ancestors: usize, budget: usize, fuel_per_page: u64) -> Self {
+        Self {
+            limits: StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build(),
+            ancestors,
+            budget,
+            fuel_per_page,
+            memory_size: 0,
+            unpaid_pages: 0,
+        }
+    }
+    /// Memory grown by this instance and by the frames above it.
+    pub fn grown(&self) -> usize {
+        self.ancestors + self.memory_size.saturating_sub(INITIAL_MEMORY_SIZE)
+    }
+    /// The fuel owed for pages grown since the last call, which the caller must deduct.
+    pub fn take_fuel(&mut self) -> Result<u64> {
+        overflow_error(
+            self.fuel_per_page
+                .checked_mul(std::mem::take(&mut self.unpaid_pages)),
+        )
+    }
+}
+
+impl ResourceLimiter for AlkanesLimiter {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<bool, MemoryError> {
+        if !self.limits.memory_growing(current, desired, maximum)? {
+            return Ok(false);
+        }
+        let grown = desired.saturating_sub(INITIAL_MEMORY_SIZE);
+        if self.ancestors.saturating_add(grown) > self.budget {
+            return Ok(false);
+        }
+        let pages =
+            (grown - current.saturating_sub(INITIAL_MEMORY_SIZE).min(grown)) / WASM_PAGE_SIZE;
+        self.unpaid_pages += pages as u64;
+        self.memory_size = desired;
+        Ok(true)
+    }
+    fn table_growing(
+        &mut self,
+        current: u32,
+        desired: u32,
+        maximum: Option<u32>,
+    ) -> Result<bool, TableError> {
+        self.limits.table_growing(current, desired, maximum)
+    }
+    fn instances(&self) -> usize {
+        self.limits.instances()
+    }
+    fn tables(&self) -> usize {
+        self.limits.tables()
+    }
+    fn memories(&self) -> usize {
+        self.limits.memories()
+    }
+}
@@ -1,18 +1,25 @@
+pub mod cache;
 pub mod constants;
 pub mod exports;
 pub mod extcall;
 pub mod fuel;
 pub mod host_functions;
 pub mod instance;
+pub mod limiter;
+pub mod precompiles;
 pub mod runtime;
+pub mod schedule;
 pub mod state;
 pub mod utils;
+pub mod validation;
 
 use self::constants::*;
 use self::exports::*;
 use self::extcall::*;
 use self::host_functions::*;
 use self::instance::*;
+use self::limiter::*;
 use self::runtime::*;
+use self::schedule::*;
 use self::state::*;
 use self::utils::*;
@@ -0,0 +1,78 @@
+use super::{forward_response, read_input, read_word, Precompile};
+use crate::vm::fuel::{FUEL_BIGNUM, FUEL_PER_BIGNUM_EXPONENT_BIT};
+use crate::vm::AlkanesRuntimeContext;
+use alkanes_support::{response::ExtendedCallResponse, utils::overflow_error};
+use anyhow::{anyhow, Result};
+use ruint::aliases::U256;
+use std::sync::{Arc, Mutex};
+
+pub const ADD: u128 = 0;
+pub const SUB: u128 = 1;
+pub const MUL: u128 = 2;
+pub const DIV: u128 = 3;
+pub const REM: u128 = 4;
+pub const MULMOD: u128 = 5;
+pub const POW: u128 = 6;
+
+/// Checked 256-bit unsigned arithmetic. Each operand is passed as two u128 inputs, low
+/// half first, after the opcode; the result is returned as 32 little-endian bytes.
+pub struct BigNum;
+
+fn operand(inputs: &[u128], index: usize) -> Result<U256> {
+    Ok(U256::from_le_bytes(read_word(inputs, 1 + index * 2)?))
+}
+
+fn overflow(op: &str) -> anyhow::Error {
+    anyhow!("bignum {} overflowed 256 bits", op)
+}
+
+impl Precompile for BigNum {
+    fn fuel(&self, inputs: &[u128]) -> Result<u64> {
+        match read_input(inputs, 0)? {
+            POW => {
+                let bits = operand(inputs, 1)?.bit_len() as u64;
+                overflow_error(
+                    FUEL_PER_BIGNUM_EXPONENT_BIT
+                        .checked_mul(bits)
+                        .and_then(|v| v.checked_add(FUEL_BIGNUM)),
+                )
+            }
+            _ => Ok(FUEL_BIGNUM),
+        }
+    }
+    fn execute(
+        &self,
+        context: Arc<Mutex<AlkanesRuntimeContext>>,
+        _fuel: u64,
+    ) -> Result<(ExtendedCallResponse, u64)> {
+        let inputs = context.lock().unwrap().inputs.clone();
+        let a = operand(&inputs, 0)?;
+        let b = operand(&inputs, 1)?;
+        let result = match read_input(&inputs, 0)? {
+            ADD => a.checked_add(b).ok_or_else(|| overflow("ADD"))?,
+            SUB => a
+                .checked_sub(b)
+                .ok_or_else(|| anyhow!("bignum SUB underflowed"))?,
+            MUL => a.checked_mul(b).ok_or_else(|| overflow("MUL"))?,
+            DIV => a
+                .checked_div(b)
+                .ok_or_else(|| anyhow!("bignum DIV by zero"))?,
+            REM => a
+                .checked_rem(b)
+                .ok_or_else(|| anyhow!("bignum REM by zero"))?,
+            MULMOD => {
+                let modulus = operand(&inputs, 2)?;
+                if modulus == U256::ZERO {
+                    return Err(anyhow!("bignum MULMOD by zero"));
+                }
+                a.mul_mod(b, modulus)
+            }
+            POW => a.checked_pow(b).ok_or_else(|| overflow("POW"))?,
+            opcode => return Err(anyhow!("unrecognized bignum opcode {}", opcode)),
+        };
+        Ok((
+            forward_response(&context, result.to_le_bytes::<32>().to_vec()),
+            0,
+        ))
+    }
+}
@@ -0,0 +1,75 @@
+use super::{forward_response, read_input, read_word, Precompile};
+use crate::vm::fuel::{FUEL_HASH, FUEL_PER_MERKLE_LEVEL};
+use crate::vm::AlkanesRuntimeContext;
+use alkanes_support::{response::ExtendedCallResponse, utils::overflow_error};
+use anyhow::{anyhow, Result};
+use bitcoin::hashes::{sha256d, Hash};
+use std::sync::{Arc, Mutex};
+
+pub const VERIFY: u128 = 0;
+pub const ROOT: u128 = 1;
+
+/// Bitcoin-style merkle proofs, where each node is the double SHA256 of its two children.
+///
+/// `VERIFY` takes `[root, leaf, index, count, siblings..]` and returns a single byte, 1 if
+/// the proof holds. `ROOT` takes `[leaf, index, count, siblings..]` and returns the 32 byte
+/// root. Hashes are passed in internal byte order as two u128 inputs, low half first, and
+/// bit n of `index` is set when the node at level n is a right child.
+pub struct Merkle;
+
+fn proof_start(opcode: u128) -> Result<usize> {
+    match opcode {
+        VERIFY => Ok(3),
+        ROOT => Ok(1),
+        _ => Err(anyhow!("unrecognized merkle opcode {}", opcode)),
+    }
+}
+
+fn compute_root(inputs: &[u128], start: usize) -> Result<[u8; 32]> {
+    let mut node = read_word(inputs, start)?;
+    let index = read_input(inputs, start + 2)?;
+    let count: usize = read_input(inputs, start + 3)?.try_into()?;
+    if count > 128 {
+        return Err(anyhow!("merkle proof of {} levels is too deep", count));
+    }
+    for level in 0..count {
+        let sibling = read_word(inputs, start + 4 + level * 2)?;
+        let mut preimage = Vec::with_capacity(64);
+        if (index >> level) & 1 == 0 {
+            preimage.extend(&node);
+            preimage.extend(&sibling);
+        } else {
+            preimage.extend(&sibling);
+            preimage.extend(&node);
+        }
+        node = sha256d::Hash::hash(&preimage).to_byte_array();
+    }
+    Ok(node)
+}
+
+impl Precompile for Merkle {
+    fn fuel(&self, inputs: &[u128]) -> Result<u64> {
+        let start = proof_start(read_input(inputs, 0)?)?;
+        let count = read_input(inputs, start + 3)?;
+        overflow_error(
+            u64::try_from(count)
+                .ok()
+                .and_then(|count| FUEL_PER_MERKLE_LEVEL.checked_mul(count))
+                .and_then(|v| v.checked_add(FUEL_HASH)),
+        )
+    }
+    fn execute(
+        &self,
+        context: Arc<Mutex<AlkanesRuntimeContext>>,
+        _fuel: u64,
+    ) -> Result<(ExtendedCallResponse, u64)> {
+        let inputs = context.lock().unwrap().inputs.clone();
+        let opcode = read_input(&inputs, 0)?;
+        let root = compute_root(&inputs, proof_start(opcode)?)?;
+        let data = match opcode {
+            VERIFY => vec![(read_word(&inputs, 1)? == root) as u8],
+            _ => root.to_vec(),
+        };
+        Ok((forward_response(&context, data), 0))
+    }
+}
@@ -0,0 +1,130 @@
+pub mod bignum;
+pub mod merkle;
+pub mod multicall;
+
+use super::AlkanesRuntimeContext;
+use alkanes_support::{id::AlkaneId, response::ExtendedCallResponse, utils::overflow_error};
+use anyhow::{anyhow, Result};
+#[allow(unused_imports)]
+use metashrew_core::{
+    println,
+    stdio::{stdout, Write},
+};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub use bignum::BigNum;
+pub use merkle::Merkle;
+pub use multicall::Multicall;
+
+pub const BIGNUM_PRECOMPILE_ID: AlkaneId = AlkaneId { block: 7, tx: 0 };
+pub const MERKLE_PRECOMPILE_ID: AlkaneId = AlkaneId { block: 7, tx: 1 };
+pub const MULTICALL_PRECOMPILE_ID: AlkaneId = AlkaneId { block: 7, tx: 2 };
+
+// run_special_cellpacks hands this back in place of a WASM binary. It lacks the `\0asm`
+// magic, so no deployed alkane can ever be mistaken for a precompile.
+const PRECOMPILE_MAGIC: &[u8] = b"\0precompile";
+
+/// A contract implemented natively in the indexer, living at a reserved `[7, n]` AlkaneId.
+pub trait Precompile: Send + Sync {
+    /// Fuel charged before the call runs, derived only from the call's inputs.
+    fn fuel(&self, inputs: &[u128]) -> Result<u64>;
+    /// Runs the call, returning its response and any fuel spent on nested calls on top of
+    /// the declared cost.
+    fn execute(
+        &self,
+        context: Arc<Mutex<AlkanesRuntimeContext>>,
+        fuel: u64,
+    ) -> Result<(ExtendedCallResponse, u64)>;
+}
+
+static PRECOMPILES: Lazy<BTreeMap<AlkaneId, Box<dyn Precompile>>> = Lazy::new(|| {
+    let mut registry: BTreeMap<AlkaneId, Box<dyn Precompile>> = BTreeMap::new();
+    registry.insert(BIGNUM_PRECOMPILE_ID, Box::new(BigNum));
+    registry.insert(MERKLE_PRECOMPILE_ID, Box::new(Merkle));
+    registry.insert(MULTICALL_PRECOMPILE_ID, Box::new(Multicall));
+    registry
+});
+
+pub fn precompile(id: &AlkaneId) -> Option<&'static dyn Precompile> {
+    PRECOMPILES.get(id).map(|precompile| precompile.as_ref())
+}
+
+/// Stand-in binary for the precompile at `id`, so the usual call paths can carry it to
+/// [`run_precompile`] without a WASM lookup.
+pub fn precompile_binary(id: &AlkaneId) -> Result<Arc<Vec<u8>>> {
+    if precompile(id).is_none() {
+        return Err(anyhow!(
+            "no precompile registered at [{}, {}]",
+            id.block,
+            id.tx
+        ));
+    }
+    let mut binary = PRECOMPILE_MAGIC.to_vec();
+    binary.extend(Vec::<u8>::from(id));
+    Ok(Arc::new(binary))
+}
+
+pub fn precompile_from_binary(binary: &[u8]) -> Option<&'static dyn Precompile> {
+    let id = binary.strip_prefix(PRECOMPILE_MAGIC)?;
+    precompile(&AlkaneId::try_from(id.to_vec()).ok()?)
+}
+
+pub fn run_precompile(
+    precompile: &dyn Precompile,
+    context: Arc<Mutex<AlkanesRuntimeContext>>,
+    start_fuel: u64,
+) -> Result<(ExtendedCallResponse, u64)> {
+    let inputs = context.lock().unwrap().inputs.clone();
+    let cost = precompile.fuel(&inputs)?;
+
+    #[cfg(feature = "debug-log")]
+    {
+        println!(
+            "Running precompile with {} fuel units, declared cost {}",
+            start_fuel, cost
+        );
+    }
+
+    if cost > start_fuel {
+        return Err(anyhow!(
+            "all fuel consumed by precompile: requested {} units, but only {} remaining",
+            cost,
+            start_fuel
+        ));
+    }
+    let (response, nested_fuel) = precompile.execute(context, start_fuel - cost)?;
+    Ok((response, overflow_error(cost.checked_add(nested_fuel))?))
+}
+
+/// Reads the 256-bit word passed as two u128 inputs (low half first) starting at `offset`.
+pub(crate) fn read_word(inputs: &[u128], offset: usize) -> Result<[u8; 32]> {
+    match inputs.get(offset..offset + 2) {
+        Some(&[lo, hi]) => {
+            let mut word = [0u8; 32];
+            word[..16].copy_from_slice(&lo.to_le_bytes());
+            word[16..].copy_from_slice(&hi.to_le_bytes());
+            Ok(word)
+        }
+        _ => Err(anyhow!("missing 256-bit input at position {}", offset)),
+    }
+}
+
+pub(crate) fn read_input(inputs: &[u128], offset: usize) -> Result<u128> {
+    inputs
+        .get(offset)
+        .cloned()
+        .ok_or_else(|| anyhow!("missing input at position {}", offset))
+}
+
+/// Response that hands any incoming alkanes straight back to the caller.
+pub(crate) fn forward_response(
+    context: &Arc<Mutex<AlkanesRuntimeContext>>,
+    data: Vec<u8>,
+) -> ExtendedCallResponse {
+    let mut response = ExtendedCallResponse::default();
+    response.alkanes = context.lock().unwrap().incoming_alkanes.clone();
+    response.data = data;
+    response
+}
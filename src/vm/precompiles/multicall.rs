@@ -0,0 +1,147 @@
+use super::{forward_response, read_input, Precompile};
+use crate::vm::extcall::{
+    check_checkpoint_depth, enter_call, return_call, revert_call, subcontext, Call,
+};
+use crate::vm::fuel::FUEL_EXTCALL;
+use crate::vm::utils::{run_after_special_metered, run_special_cellpacks};
+use crate::vm::AlkanesRuntimeContext;
+use alkanes_support::{
+    cellpack::Cellpack, id::AlkaneId, parcel::AlkaneTransferParcel, response::ExtendedCallResponse,
+    utils::overflow_error,
+};
+use anyhow::{anyhow, Result};
+use std::sync::{Arc, Mutex};
+
+pub const AGGREGATE: u128 = 0;
+
+/// Runs a batch of calls in order, with the router as their caller. `AGGREGATE` takes
+/// `[count, (block, tx, n, inputs..)..]`; if any call fails the whole batch reverts. The
+/// response holds each call's data prefixed by its length as a u32, and carries back the
+/// incoming alkanes together with whatever the calls returned.
+pub struct Multicall;
+
+fn decode_calls(inputs: &[u128]) -> Result<Vec<Cellpack>> {
+    let count = read_input(inputs, 1)?;
+    let mut offset = 2;
+    let mut calls = vec![];
+    for _ in 0..count {
+        let target = AlkaneId::new(read_input(inputs, offset)?, read_input(inputs, offset + 1)?);
+        let n: usize = read_input(inputs, offset + 2)?.try_into()?;
+        offset += 3;
+        let call_inputs = inputs
+            .get(offset..offset + n)
+            .ok_or_else(|| anyhow!("multicall inputs end before call {}", calls.len()))?;
+        offset += n;
+        calls.push(Cellpack {
+            target,
+            inputs: call_inputs.to_vec(),
+        });
+    }
+    Ok(calls)
+}
+
+fn call(
+    context: &Arc<Mutex<AlkanesRuntimeContext>>,
+    cellpack: &Cellpack,
+    fuel: u64,
+) -> Result<(ExtendedCallResponse, u64)> {
+    let (depth, callee_depth) = {
+        let mut context_guard = context.lock().unwrap();
+        // the batched calls may write state, so the router cannot be reached statically
+        if context_guard.is_static {
+            return Err(anyhow!(
+                "ALKANES: revert: static call attempted a nested __call through multicall"
+            ));
+        }
+        check_checkpoint_depth(&context_guard)?;
+        let depth = context_guard.message.atomic.checkpoint_depth();
+        context_guard.message.atomic.checkpoint();
+        (depth, context_guard.depth + 1)
+    };
+    let mut callee = cellpack.target.clone();
+    let mut fuel_used = 0;
+    match run_call(context, cellpack, fuel, &mut callee, &mut fuel_used) {
+        Ok(v) => {
+            context.lock().unwrap().message.atomic.commit();
+            Ok(v)
+        }
+        Err(e) => {
+            revert_call(
+                &mut context.lock().unwrap(),
+                &e,
+                &callee,
+                callee_depth,
+                depth,
+                fuel_used,
+            );
+            Err(e)
+        }
+    }
+}
+
+// `callee` is set to the alkane the call resolves to and `fuel_used` to what the call
+// consumed, whether or not it succeeds
+fn run_call(
+    context: &Arc<Mutex<AlkanesRuntimeContext>>,
+    cellpack: &Cellpack,
+    fuel: u64,
+    callee: &mut AlkaneId,
+    fuel_used: &mut u64,
+) -> Result<(ExtendedCallResponse, u64)> {
+    let (_caller, submyself, binary) = run_special_cellpacks(context.clone(), cellpack)?;
+    *callee = submyself.clone();
+    let subcontext = {
+        let context_guard = context.lock().unwrap();
+        context_guard.check_call(&submyself, false)?;
+        subcontext::<Call>(
+            &context_guard,
+            submyself,
+            cellpack,
+            AlkaneTransferParcel::default(),
+        )
+    };
+    enter_call::<Call>(&subcontext, fuel);
+    let (result, consumed) =
+        run_after_special_metered(Arc::new(Mutex::new(subcontext.clone())), binary, fuel);
+    *fuel_used = consumed;
+    let (response, gas_used) = result?;
+    return_call(
+        &mut context.lock().unwrap(),
+        &subcontext,
+        &response,
+        gas_used,
+    )?;
+    Ok((response, gas_used))
+}
+
+impl Precompile for Multicall {
+    fn fuel(&self, inputs: &[u128]) -> Result<u64> {
+        match read_input(inputs, 0)? {
+            AGGREGATE => {
+                let count = u64::try_from(decode_calls(inputs)?.len())?;
+                overflow_error(FUEL_EXTCALL.checked_mul(count))
+            }
+            opcode => Err(anyhow!("unrecognized multicall opcode {}", opcode)),
+        }
+    }
+    fn execute(
+        &self,
+        context: Arc<Mutex<AlkanesRuntimeContext>>,
+        fuel: u64,
+    ) -> Result<(ExtendedCallResponse, u64)> {
+        let inputs = context.lock().unwrap().inputs.clone();
+        let mut remaining = fuel;
+        let mut data = vec![];
+        let mut returned = AlkaneTransferParcel::default();
+        for cellpack in decode_calls(&inputs)? {
+            let (response, gas_used) = call(&context, &cellpack, remaining)?;
+            remaining = overflow_error(remaining.checked_sub(gas_used))?;
+            data.extend(&(response.data.len() as u32).to_le_bytes());
+            data.extend(&response.data);
+            returned.0.extend(response.alkanes.0);
+        }
+        let mut response = forward_response(&context, data);
+        response.alkanes.0.extend(returned.0);
+        Ok((response, fuel - remaining))
+    }
+}
@@ -9,8 +9,20 @@ use {
     std::fmt::Write,
 };
 
+use super::schedule::protocol_rules;
+use anyhow::{anyhow, Result};
 use protorune::message::MessageContextParcel;
 
+/// Checkpoints the message's atomic may hold before a further call is refused as a
+/// possible infinite recursion.
+pub const MAX_CHECKPOINT_DEPTH: usize = 75;
+
+/// Hard limit on how deeply calls may nest below the message's own frame. A frame running
+/// WASM takes two checkpoints, one when it is called and one when its instance executes, so
+/// MAX_CHECKPOINT_DEPTH stops such a chain first, as it always has; this bound only trips for
+/// chains through precompiles, which take one.
+pub const MAX_CALL_DEPTH: u32 = (MAX_CHECKPOINT_DEPTH / 2 + 1) as u32;
+
 #[derive(Default, Clone)]
 pub struct AlkanesRuntimeContext {
     pub myself: AlkaneId,
@@ -20,6 +32,17 @@ pub struct AlkanesRuntimeContext {
     pub inputs: Vec<u128>,
     pub message: Box<MessageContextParcel>,
     pub trace: Trace,
+    /// set for a frame entered through __staticcall and everything it calls; such a frame
+    /// may read state but fails as soon as it tries to change it
+    pub is_static: bool,
+    /// number of calls between the message's frame, at 0, and this one
+    pub depth: u32,
+    pub max_depth: u32,
+    /// alkanes further up the call stack that declared themselves non-reentrant
+    pub locked: Vec<AlkaneId>,
+    /// bytes of memory grown past the initial pages by the frames above this one, counted
+    /// against the memory budget together with this frame's own growth
+    pub memory_grown: usize,
 }
 
 impl fmt::Debug for AlkanesRuntimeContext {
@@ -29,6 +52,10 @@ impl fmt::Debug for AlkanesRuntimeContext {
             .field("caller", &self.caller)
             .field("incoming_alkanes", &self.incoming_alkanes)
             .field("inputs", &self.inputs)
+            .field("is_static", &self.is_static)
+            .field("depth", &self.depth)
+            .field("locked", &self.locked)
+            .field("memory_grown", &self.memory_grown)
             .finish()
     }
 }
@@ -49,7 +76,31 @@ impl AlkanesRuntimeContext {
             caller: AlkaneId::default(),
             trace: Trace::default(),
             inputs: cloned.inputs,
+            is_static: false,
+            depth: 0,
+            max_depth: MAX_CALL_DEPTH,
+            locked: vec![],
+            memory_grown: 0,
+        }
+    }
+    /// Checks that this frame may call into `target`: once the call guards activate, the call
+    /// must stay within the depth limit and, unless it is a delegatecall, must not re-enter a
+    /// locked alkane.
+    pub fn check_call(&self, target: &AlkaneId, isdelegate: bool) -> Result<()> {
+        if !protocol_rules(self.message.height).call_guards {
+            return Ok(());
+        }
+        if self.depth >= self.max_depth {
+            return Err(anyhow!("call depth limit of {} reached", self.max_depth));
+        }
+        if !isdelegate && self.locked.contains(target) {
+            return Err(anyhow!(
+                "re-entrant call into locked alkane [{}, {}]",
+                target.block,
+                target.tx
+            ));
         }
+        Ok(())
     }
     pub fn flatten(&self) -> Vec<u128> {
         let mut result = Vec::<u128>::new();
@@ -0,0 +1,261 @@
+use super::fuel::{FUEL_PER_MEMORY_PAGE, MINIMUM_FUEL, TOTAL_FUEL};
+use crate::network::genesis::GENESIS_BLOCK;
+use alkanes_support::revert::Revert;
+use alkanes_support::trace::TraceResponse;
+use anyhow::{anyhow, Result};
+use std::sync::RwLock;
+use wasmi::Module;
+
+/// How a transaction's size is measured when it is allocated a share of the block's fuel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VfsizeRule {
+    /// vsize of the transaction, except that the first input's witness is not counted for
+    /// transactions that deploy, so a contract binary does not use up the block's fuel
+    DiscountDeployWitness,
+}
+
+/// A named set of protocol rules, in force from `height` until the next set in the schedule
+/// activates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolRules {
+    pub name: &'static str,
+    pub height: u64,
+    /// fuel shared by all transactions in a block
+    pub total_fuel: u64,
+    /// fuel every message is granted however small its transaction
+    pub minimum_fuel: u64,
+    pub fuel_per_memory_page: u64,
+    /// memory grown past the initial pages, summed over every frame on the call stack, that
+    /// a call may use before further growth fails
+    pub memory_budget: usize,
+    pub vfsize: VfsizeRule,
+    /// host functions first made available by this rule set; a contract importing one
+    /// cannot be instantiated before the set activates
+    pub host_functions: &'static [&'static str],
+    /// whether a frame entered through __staticcall is held to read-only execution
+    pub strict_static_calls: bool,
+    /// whether a binary is checked before it is stored by a CREATE or CREATERESERVED deploy
+    pub validate_deploys: bool,
+    /// whether the fuel limit a protostone carries (tag 97) caps its message's fuel
+    pub fuel_limits: bool,
+    /// whether calls to the reserved `[7, n]` ids run the natively implemented precompiles
+    pub precompiles: bool,
+    /// whether a protostone's all-or-nothing flag (tag 99) makes its transaction's messages
+    /// revert together
+    pub all_or_nothing: bool,
+    /// whether reverts are encoded with TYPED_REVERT_SELECTOR rather than as `Error(string)`
+    pub typed_reverts: bool,
+    /// whether storage writes keep the per-contract key list read by __request_storage_keys;
+    /// keys last written before this activates are not listed
+    pub storage_key_index: bool,
+    /// whether calls are held to the call depth limit and may not re-enter an alkane that
+    /// exports `__nonreentrant`
+    pub call_guards: bool,
+}
+
+impl ProtocolRules {
+    /// Revert data as a caller or trace reader sees it under these rules.
+    pub fn encode_revert(&self, revert: &Revert) -> Vec<u8> {
+        if self.typed_reverts {
+            revert.encode()
+        } else {
+            revert.encode_legacy()
+        }
+    }
+    /// Reads revert data a contract returned, in the encodings these rules accept.
+    pub fn decode_revert(&self, data: &[u8]) -> Option<Revert> {
+        if self.typed_reverts {
+            Revert::decode(data)
+        } else {
+            Revert::decode_legacy(data)
+        }
+    }
+    /// A RevertContext response carrying `revert` encoded under these rules.
+    pub fn revert_response(&self, revert: &Revert, fuel_used: u64) -> TraceResponse {
+        let mut response = TraceResponse::from_revert(revert, fuel_used);
+        response.inner.data = self.encode_revert(revert);
+        response
+    }
+}
+
+/// Rules in force from the network's genesis block.
+pub const GENESIS_RULES: ProtocolRules = ProtocolRules {
+    name: "genesis",
+    height: GENESIS_BLOCK,
+    total_fuel: TOTAL_FUEL,
+    minimum_fuel: MINIMUM_FUEL,
+    fuel_per_memory_page: 0,
+    memory_budget: usize::MAX,
+    vfsize: VfsizeRule::DiscountDeployWitness,
+    host_functions: &[],
+    strict_static_calls: false,
+    validate_deploys: false,
+    fuel_limits: false,
+    precompiles: false,
+    all_or_nothing: false,
+    typed_reverts: false,
+    storage_key_index: false,
+    call_guards: false,
+};
+
+//use if regtest
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "dogecoin",
+    feature = "bellscoin",
+    feature = "fractal",
+    feature = "luckycoin"
+)))]
+pub const V2_BLOCK: u64 = 0;
+
+// not yet scheduled on the other networks
+#[cfg(any(
+    feature = "mainnet",
+    feature = "dogecoin",
+    feature = "bellscoin",
+    feature = "fractal",
+    feature = "luckycoin"
+))]
+pub const V2_BLOCK: u64 = u64::MAX;
+
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "dogecoin",
+    feature = "bellscoin",
+    feature = "fractal",
+    feature = "luckycoin"
+)))]
+const V2_MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+#[cfg(feature = "mainnet")]
+const V2_MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+#[cfg(feature = "dogecoin")]
+const V2_MEMORY_BUDGET: usize = 96 * 1024 * 1024;
+#[cfg(feature = "fractal")]
+const V2_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+#[cfg(feature = "luckycoin")]
+const V2_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+#[cfg(feature = "bellscoin")]
+const V2_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// Host functions, static call enforcement, memory fuel and budget, deploy validation, protostone fuel
+/// limits, all-or-nothing transactions, precompiles, typed reverts, the storage key index and
+/// the call depth and reentrancy guards added after genesis.
+pub const V2_RULES: ProtocolRules = ProtocolRules {
+    name: "v2",
+    height: V2_BLOCK,
+    fuel_per_memory_page: FUEL_PER_MEMORY_PAGE,
+    memory_budget: V2_MEMORY_BUDGET,
+    host_functions: &[
+        "__sha256",
+        "__hash160",
+        "__keccak256",
+        "__verify_schnorr",
+        "__verify_ecdsa",
+        "__emit",
+        "__request_storage_keys",
+        "__load_storage_keys",
+        "__request_prevouts",
+        "__load_prevouts",
+        "__header",
+        "__request_external_storage",
+        "__load_external_storage",
+        "__codehash",
+        "__is_deployed",
+        "__try_call",
+        "__try_delegatecall",
+        "__try_staticcall",
+    ],
+    strict_static_calls: true,
+    validate_deploys: true,
+    fuel_limits: true,
+    precompiles: true,
+    all_or_nothing: true,
+    typed_reverts: true,
+    storage_key_index: true,
+    call_guards: true,
+    ..GENESIS_RULES
+};
+
+// Upgrades are appended here, in order of height, as rule sets that override the set
+// before them, e.g. `ProtocolRules { name: "...", height: ..., ..V2_RULES }`
+static RULES: &[ProtocolRules] = &[GENESIS_RULES, V2_RULES];
+
+static _SCHEDULE: RwLock<ProtocolSchedule> = RwLock::new(ProtocolSchedule::new(RULES));
+
+/// The rule sets of this network ordered by activation height. The first set activates
+/// the protocol, and blocks below it are not indexed.
+#[derive(Clone, Copy, Debug)]
+pub struct ProtocolSchedule {
+    rules: &'static [ProtocolRules],
+}
+
+impl ProtocolSchedule {
+    pub const fn new(rules: &'static [ProtocolRules]) -> Self {
+        Self { rules }
+    }
+    pub fn is_active(&self, height: u64) -> bool {
+        height >= self.rules[0].height
+    }
+    /// The rule set in force at `height`. Heights before the first set get the first set.
+    pub fn at(&self, height: u64) -> &'static ProtocolRules {
+        let rules: &'static [ProtocolRules] = self.rules;
+        rules
+            .iter()
+            .rev()
+            .find(|rules| rules.height <= height)
+            .unwrap_or(&rules[0])
+    }
+    pub fn get(&self, name: &str) -> Option<&'static ProtocolRules> {
+        let rules: &'static [ProtocolRules] = self.rules;
+        rules.iter().find(|rules| rules.name == name)
+    }
+    /// The rule set that introduces host function `name`, if any set does. Host functions
+    /// no set introduces have been available since the protocol activated.
+    pub fn introduced_by(&self, name: &str) -> Option<&'static ProtocolRules> {
+        let rules: &'static [ProtocolRules] = self.rules;
+        rules
+            .iter()
+            .find(|rules| rules.host_functions.contains(&name))
+    }
+    pub fn host_function_active(&self, name: &str, height: u64) -> bool {
+        self.introduced_by(name)
+            .map(|rules| rules.height <= height)
+            .unwrap_or(true)
+    }
+    /// Fails when `module` imports a host function that is not yet available at `height`.
+    pub fn check_imports(&self, module: &Module, height: u64) -> Result<()> {
+        for import in module.imports() {
+            if !self.host_function_active(import.name(), height) {
+                let rules = self.introduced_by(import.name()).unwrap();
+                return Err(anyhow!(
+                    "host function {} is not available until block {} ({})",
+                    import.name(),
+                    rules.height,
+                    rules.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn protocol_schedule() -> ProtocolSchedule {
+    *_SCHEDULE.read().unwrap()
+}
+
+/// Replaces the network's schedule, so tests can index under rule sets at other heights.
+#[cfg(any(test, feature = "test-utils"))]
+pub fn set_protocol_schedule(rules: &'static [ProtocolRules]) {
+    *_SCHEDULE.write().unwrap() = ProtocolSchedule::new(rules);
+}
+
+/// Puts back the network's own schedule.
+#[cfg(any(test, feature = "test-utils"))]
+pub fn reset_protocol_schedule() {
+    set_protocol_schedule(RULES);
+}
+
+/// The rule set in force at `height` on this network.
+pub fn protocol_rules(height: u64) -> &'static ProtocolRules {
+    protocol_schedule().at(height)
+}
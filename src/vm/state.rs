@@ -1,9 +1,8 @@
-use super::AlkanesRuntimeContext;
+use super::{AlkanesLimiter, AlkanesRuntimeContext};
 use std::sync::{Arc, Mutex};
-use wasmi::*;
 
 pub struct AlkanesState {
     pub(super) had_failure: bool,
     pub(super) context: Arc<Mutex<AlkanesRuntimeContext>>,
-    pub(super) limiter: StoreLimits,
+    pub(super) limiter: AlkanesLimiter,
 }
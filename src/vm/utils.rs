@@ -1,11 +1,15 @@
+use super::cache::load_binary;
+use super::precompiles::{precompile_binary, precompile_from_binary, run_precompile};
+use super::schedule::protocol_rules;
+use super::validation::validate_binary;
 use super::{AlkanesInstance, AlkanesRuntimeContext, AlkanesState};
+use crate::stateroot::written;
 use crate::utils::{pipe_storagemap_to, transfer_from};
 use crate::vm::fuel::FUEL_PER_STORE_BYTE;
 use alkanes_support::trace::TraceEvent;
 use alkanes_support::{
-    cellpack::Cellpack, gz::decompress, id::AlkaneId, parcel::AlkaneTransferParcel,
-    response::ExtendedCallResponse, storage::StorageMap, utils::overflow_error,
-    witness::find_witness_payload,
+    cellpack::Cellpack, id::AlkaneId, parcel::AlkaneTransferParcel, response::ExtendedCallResponse,
+    storage::StorageMap, utils::overflow_error, witness::find_witness_payload,
 };
 use anyhow::{anyhow, Result};
 use bitcoin::OutPoint;
@@ -16,6 +20,7 @@ use metashrew_core::{
     stdio::{stdout, Write},
 };
 use metashrew_support::index_pointer::KeyValuePointer;
+use protorune::journal;
 use protorune_support::utils::consensus_encode;
 use std::sync::{Arc, Mutex};
 use wasmi::*;
@@ -46,7 +51,7 @@ pub fn get_memory<'a>(caller: &mut Caller<'_, AlkanesState>) -> Result<Memory> {
 }
 
 pub fn sequence_pointer(ptr: &AtomicPointer) -> AtomicPointer {
-    ptr.derive(&IndexPointer::from_keyword("/alkanes/sequence"))
+    journal::written(ptr.derive(&IndexPointer::from_keyword("/alkanes/sequence")))
 }
 
 fn set_alkane_id_to_tx_id(
@@ -62,16 +67,21 @@ fn set_alkane_id_to_tx_id(
     };
     let outpoint_bytes: Vec<u8> = consensus_encode(&outpoint)?;
 
-    context_guard
+    let pointer = context_guard
         .message
         .atomic
         .keyword("/alkanes_id_to_outpoint/")
-        .select(&alkane_id.clone().into())
-        .set(Arc::new(outpoint_bytes));
+        .select(&alkane_id.clone().into());
+    written(pointer).set(Arc::new(outpoint_bytes));
 
     Ok(())
 }
 
+/// Points at the compressed WASM stored for `id`; empty if nothing is deployed there.
+pub fn alkane_binary_pointer(atomic: &AtomicPointer, id: &AlkaneId) -> AtomicPointer {
+    atomic.keyword("/alkanes/").select(&id.clone().into())
+}
+
 pub fn run_special_cellpacks(
     context: Arc<Mutex<AlkanesRuntimeContext>>,
     cellpack: &Cellpack,
@@ -81,17 +91,16 @@ pub fn run_special_cellpacks(
     let mut next_sequence_pointer = sequence_pointer(&mut context.lock().unwrap().message.atomic);
     let next_sequence = next_sequence_pointer.get_value::<u128>();
     let original_target = cellpack.target.clone();
-    if cellpack.target.is_created(next_sequence) {
+    let rules = protocol_rules(context.lock().unwrap().message.height);
+    if cellpack.target.precompile().is_some() && rules.precompiles {
+        // precompiles run natively, so no WASM is looked up for them; before they were
+        // introduced a [7, n] target had no binary and the call failed
+        binary = precompile_binary(&payload.target)?;
+    } else if cellpack.target.is_created(next_sequence) {
         // contract already created, load the wasm from the index
-        let wasm_payload = context
-            .lock()
-            .unwrap()
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&payload.target.clone().into())
-            .get();
-        binary = Arc::new(decompress(wasm_payload.as_ref().clone())?);
+        let wasm_payload =
+            alkane_binary_pointer(&context.lock().unwrap().message.atomic, &payload.target).get();
+        binary = load_binary(&payload.target, wasm_payload)?;
     } else if cellpack.target.is_create() {
         // contract not created, create it by first loading the wasm from the witness
         // then storing it in the index.
@@ -104,15 +113,16 @@ pub fn run_special_cellpacks(
             block: 2,
             tx: next_sequence,
         };
-        let mut pointer = context
-            .lock()
-            .unwrap()
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&payload.target.clone().into());
-        pointer.set(wasm_payload.clone());
-        binary = Arc::new(decompress(wasm_payload.as_ref().clone())?);
+        // from v2 a rejected binary is never stored and does not consume the sequence number
+        binary = load_binary(&payload.target, wasm_payload.clone())?;
+        if rules.validate_deploys {
+            validate_binary(&binary)?;
+        }
+        written(alkane_binary_pointer(
+            &context.lock().unwrap().message.atomic,
+            &payload.target,
+        ))
+        .set(wasm_payload.clone());
         next_sequence_pointer.set_value(next_sequence + 1);
 
         set_alkane_id_to_tx_id(context.clone(), &payload.target)?;
@@ -130,15 +140,13 @@ pub fn run_special_cellpacks(
             block: 4,
             tx: number,
         };
-        let mut ptr = context
-            .lock()
-            .unwrap()
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&payload.target.clone().into());
+        let ptr = alkane_binary_pointer(&context.lock().unwrap().message.atomic, &payload.target);
         if ptr.get().as_ref().len() == 0 {
-            ptr.set(wasm_payload.clone());
+            binary = load_binary(&payload.target, wasm_payload.clone())?;
+            if rules.validate_deploys {
+                validate_binary(&binary)?;
+            }
+            written(ptr).set(wasm_payload.clone());
             set_alkane_id_to_tx_id(context.clone(), &payload.target)?;
         } else {
             return Err(anyhow!(format!(
@@ -146,32 +154,24 @@ pub fn run_special_cellpacks(
                 number
             )));
         }
-        binary = Arc::new(decompress(wasm_payload.clone().as_ref().clone())?);
     } else if let Some(factory) = cellpack.target.factory() {
         // we find the factory alkane wasm and set the current alkane to the factory wasm
         payload.target = AlkaneId::new(2, next_sequence);
-        next_sequence_pointer.set_value(next_sequence + 1);
-        let context_binary: Vec<u8> = context
-            .lock()
-            .unwrap()
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&factory.clone().into())
-            .get()
-            .as_ref()
-            .clone();
+        let context_binary: Vec<u8> =
+            alkane_binary_pointer(&context.lock().unwrap().message.atomic, &factory)
+                .get()
+                .as_ref()
+                .clone();
         let rc = Arc::new(context_binary);
-        context
-            .lock()
-            .unwrap()
-            .message
-            .atomic
-            .keyword("/alkanes/")
-            .select(&payload.target.clone().into())
-            .set(rc.clone()); // TODO: we don't need to store this twice
+        // a clone runs the factory's binary, which was checked when the factory was deployed
+        binary = load_binary(&payload.target, rc.clone())?;
+        next_sequence_pointer.set_value(next_sequence + 1);
+        written(alkane_binary_pointer(
+            &context.lock().unwrap().message.atomic,
+            &payload.target,
+        ))
+        .set(rc.clone()); // TODO: we don't need to store this twice
         set_alkane_id_to_tx_id(context.clone(), &payload.target)?;
-        binary = Arc::new(decompress(rc.as_ref().clone())?);
     }
     if &original_target != &payload.target {
         context
@@ -229,11 +229,12 @@ pub trait Saveable {
     fn to(&self) -> AlkaneId;
     fn storage_map(&self) -> StorageMap;
     fn alkanes(&self) -> AlkaneTransferParcel;
-    fn save(&self, atomic: &mut AtomicPointer) -> Result<()> {
+    fn save(&self, atomic: &mut AtomicPointer, height: u64) -> Result<()> {
         pipe_storagemap_to(
             &self.storage_map(),
             &mut atomic
                 .derive(&IndexPointer::from_keyword("/alkanes/").select(&self.from().into())),
+            height,
         );
         transfer_from(
             &self.alkanes(),
@@ -249,6 +250,43 @@ pub fn run_after_special(
     context: Arc<Mutex<AlkanesRuntimeContext>>,
     binary: Arc<Vec<u8>>,
     start_fuel: u64,
+) -> Result<(ExtendedCallResponse, u64)> {
+    run_after_special_metered(context, binary, start_fuel).0
+}
+
+/// Runs the call like `run_after_special`, and also returns the fuel it consumed, so a call
+/// that fails can be traced with what it used before failing.
+pub fn run_after_special_metered(
+    context: Arc<Mutex<AlkanesRuntimeContext>>,
+    binary: Arc<Vec<u8>>,
+    start_fuel: u64,
+) -> (Result<(ExtendedCallResponse, u64)>, u64) {
+    if let Some(precompile) = precompile_from_binary(&binary) {
+        let inputs = context.lock().unwrap().inputs.clone();
+        return match run_precompile(precompile, context, start_fuel) {
+            Ok((response, fuel_used)) => (Ok((response, fuel_used)), fuel_used),
+            // the declared cost is what a precompile burns before its work can fail
+            Err(e) => (
+                Err(e),
+                precompile.fuel(&inputs).unwrap_or(0).min(start_fuel),
+            ),
+        };
+    }
+    let mut instance = match AlkanesInstance::from_alkane(context.clone(), binary, start_fuel) {
+        Ok(v) => v,
+        Err(e) => return (Err(e), 0),
+    };
+    let result = run_instance(&mut instance, start_fuel);
+    let fuel_used = match &result {
+        Ok((_, fuel_used)) => *fuel_used,
+        Err(_) => start_fuel.saturating_sub(instance.store.get_fuel().unwrap_or(0)),
+    };
+    (result, fuel_used)
+}
+
+fn run_instance(
+    instance: &mut AlkanesInstance,
+    start_fuel: u64,
 ) -> Result<(ExtendedCallResponse, u64)> {
     #[cfg(feature = "debug-log")]
     {
@@ -259,7 +297,6 @@ pub fn run_after_special(
         );
     }
 
-    let mut instance = AlkanesInstance::from_alkane(context.clone(), binary.clone(), start_fuel)?;
     let response = instance.execute()?;
 
     let remaining_fuel = instance.store.get_fuel()?;
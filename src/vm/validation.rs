@@ -0,0 +1,75 @@
+use super::cache::load_deploy_module;
+use super::{
+    AlkanesInstance, AlkanesLimiter, AlkanesRuntimeContext, AlkanesState, INITIAL_MEMORY_PAGES,
+};
+use anyhow::{anyhow, Result};
+use std::sync::{Arc, Mutex};
+use wasmi::*;
+
+const REQUIRED_EXPORTS: [&str; 2] = ["__execute", "__meta"];
+
+/// Checks a binary before it is stored as an alkane, so that a deploy which could never
+/// be called is rejected up front instead of failing on every later call. The binary is
+/// compiled once, and the module is kept in the binary cache for the calls that follow.
+pub fn validate_binary(binary: &Arc<Vec<u8>>) -> Result<()> {
+    let (engine, module) = load_deploy_module(binary).map_err(|e| {
+        anyhow!(
+            "invalid WASM binary, or one using forbidden features such as floating point: {}",
+            e
+        )
+    })?;
+
+    for name in REQUIRED_EXPORTS {
+        match module.get_export(name) {
+            Some(ExternType::Func(_)) => {}
+            _ => return Err(anyhow!("WASM binary does not export function {}", name)),
+        }
+    }
+    match module.get_export("memory") {
+        Some(ExternType::Memory(_)) => {}
+        _ => return Err(anyhow!("WASM binary does not export its memory")),
+    }
+
+    let linker = AlkanesInstance::linker(&engine)?;
+    let mut store = Store::<AlkanesState>::new(
+        &engine,
+        AlkanesState {
+            had_failure: false,
+            limiter: AlkanesLimiter::new(0, usize::MAX, 0),
+            context: Arc::new(Mutex::new(AlkanesRuntimeContext::default())),
+        },
+    );
+    store.limiter(|state| &mut state.limiter);
+    for import in module.imports() {
+        if linker.get(&store, import.module(), import.name()).is_none() {
+            return Err(anyhow!(
+                "WASM binary imports unknown host function {}::{}",
+                import.module(),
+                import.name()
+            ));
+        }
+    }
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .map_err(|e| anyhow!("WASM binary cannot be instantiated: {}", e))?
+        .ensure_no_start(&mut store)
+        .map_err(|_| anyhow!("WASM binary must not declare a start function"))?;
+
+    // every call grows memory to the initial pages, which must fit MEMORY_LIMIT and the
+    // declared maximum
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or_else(|| anyhow!("WASM binary does not export its memory"))?;
+    let current_pages = memory.size(&store);
+    if current_pages < INITIAL_MEMORY_PAGES {
+        memory
+            .grow(&mut store, INITIAL_MEMORY_PAGES - current_pages)
+            .map_err(|_| {
+                anyhow!(
+                    "WASM binary memory cannot grow to {} pages",
+                    INITIAL_MEMORY_PAGES
+                )
+            })?;
+    }
+    Ok(())
+}